@@ -1,16 +1,79 @@
+mod decoder;
+mod profile;
 pub(crate) mod result;
+mod sysreg;
 mod v1;
 
-use result::ArmInstruction;
+use decoder::DecoderEntry;
+use profile::ArchProfile;
+use result::{
+    AliasPreference, ArmInstruction, EncodingIndex, SharedPseudocode, SystemRegister,
+};
 use std::collections::HashMap;
 use std::io::Read;
-use std::sync::Once;
 
 pub fn main() {
     let xml_files = extract_xml_files();
-    let instructions = v1::parse_all_instructions(xml_files);
-    let saved = save_instructions(instructions);
-    saved_list_to_rust_enum(saved);
+    let shared = xml_files
+        .get(v1::SHARED_PSEUDOCODE_FILE)
+        .map(|data| v1::parse_shared_pseudocode(data))
+        .unwrap_or_default();
+    let mut index_files: Vec<&String> = xml_files
+        .keys()
+        .filter(|filename| v1::is_encoding_index_file(filename))
+        .collect();
+    index_files.sort();
+    let indexes: Vec<EncodingIndex> = index_files
+        .into_iter()
+        .map(|filename| v1::parse_encoding_index(&xml_files[filename]))
+        .collect();
+    let mut sysreg_files: Vec<&String> = xml_files
+        .keys()
+        .filter(|filename| v1::is_sysreg_file(filename))
+        .collect();
+    sysreg_files.sort();
+    let registers: Vec<SystemRegister> = sysreg_files
+        .into_iter()
+        .flat_map(|filename| v1::parse_system_registers(&xml_files[filename]))
+        .collect();
+    let mut instructions = v1::parse_all_instructions(xml_files);
+    v1::link_aliases(&mut instructions);
+    v1::link_shared_functions(&mut instructions, &shared);
+    v1::link_encoding_groups(&mut instructions, &indexes);
+    // 대상 프로파일 필터 (e.g., ARM_PROFILE=armv8.2-a+nosve)
+    if let Ok(text) = std::env::var("ARM_PROFILE") {
+        match ArchProfile::parse(&text) {
+            Some(profile) => {
+                profile.retain(&mut instructions);
+                tracing::debug!("{text}: {} 인스트럭션", instructions.len());
+            }
+            None => tracing::warn!("ARM_PROFILE 해석 불가: {text}"),
+        }
+    }
+    save_shared_pseudocode(&shared);
+    save_encoding_index(&indexes);
+    save_system_registers(&registers);
+    save_pseudocode_report(&instructions);
+    // A32/T32는 result/arm32와 enum Aarch32로 따로 저장, 디코더는 A64만
+    let (aarch32, instructions): (Vec<_>, Vec<_>) = instructions
+        .into_iter()
+        .partition(ArmInstruction::is_aarch32);
+    let decoder_entries = DecoderEntry::collect(&instructions);
+    let saved = save_instructions(instructions, "result/arm");
+    let variants = saved_list_to_rust_enum(saved, "Aarch64", "result/arm.rs");
+    std::fs::write(
+        "result/arm_decoder.rs",
+        decoder::decoder_to_rust(
+            &decoder_entries,
+            &variants,
+            indexes.iter().find(|index| index.instruction_set == "A64"),
+        ),
+    )
+    .expect("ARM 디코더 모듈 생성 실패");
+    if !aarch32.is_empty() {
+        let saved = save_instructions(aarch32, "result/arm32");
+        saved_list_to_rust_enum(saved, "Aarch32", "result/arm32.rs");
+    }
 }
 
 /// tar.gz에서 XML 파일들 추출
@@ -44,33 +107,135 @@ fn extract_xml_files() -> HashMap<String, Vec<u8>> {
     xml_files
 }
 
-/// 인스트럭션을 MD 파일로 저장
-fn save_instructions(instructions: Vec<ArmInstruction>) -> Vec<(String, Vec<String>)> {
+/// 공유 의사코드 인덱스 저장
+fn save_shared_pseudocode(shared: &SharedPseudocode) {
+    std::fs::create_dir_all("result").expect("베이스 디렉토리 생성 불가");
+    std::fs::write("result/arm_shared_pseudocode.md", shared.to_md().join("\n"))
+        .expect("공유 의사코드 인덱스 생성 실패");
+}
+
+/// 인코딩 인덱스 계층 저장
+fn save_encoding_index(indexes: &[EncodingIndex]) {
+    let lines: Vec<String> = indexes.iter().flat_map(|index| index.to_md()).collect();
+    std::fs::create_dir_all("result").expect("베이스 디렉토리 생성 불가");
+    std::fs::write("result/arm_encoding_index.md", lines.join("\n"))
+        .expect("인코딩 인덱스 생성 실패");
+}
+
+/// 시스템 레지스터 MD 파일과 인코딩 → 이름 조회 모듈 저장
+fn save_system_registers(registers: &[SystemRegister]) {
+    tracing::debug!("Parsed {} system registers", registers.len());
+    std::fs::create_dir_all("result/arm_sysregs").expect("시스템 레지스터 디렉토리 생성 불가");
+    for register in registers {
+        let filepath = format!("result/arm_sysregs/{}.md", register.short_name);
+        std::fs::write(&filepath, register.to_md().join("\n"))
+            .unwrap_or_else(|e| tracing::warn!("{filepath} 생성 실패: {e}"));
+    }
+    std::fs::write("result/arm_sysregs.rs", sysreg::sysreg_lookup_to_rust(registers))
+        .expect("시스템 레지스터 조회 모듈 생성 실패");
+}
+
+/// Decode/Operation 의사코드 파싱 실패 리포트
+fn save_pseudocode_report(instructions: &[ArmInstruction]) {
+    let mut failed_instructions = 0;
+    let mut failed_lines = 0;
+    let mut details = Vec::new();
+    for instr in instructions {
+        let sections = [
+            ("Decode", instr.parse_decode()),
+            ("Operation", instr.parse_operation()),
+        ];
+        if sections
+            .iter()
+            .all(|(_, parsed)| parsed.failures.is_empty())
+        {
+            continue;
+        }
+        failed_instructions += 1;
+        details.push(String::new());
+        details.push(format!("## {} ({})", instr.heading, instr.id));
+        for (section, parsed) in sections {
+            failed_lines += parsed.failures.len();
+            for failure in parsed.failures {
+                details.push(format!(
+                    "- {section} line {}: `{}` — {}",
+                    failure.line, failure.text, failure.message
+                ));
+            }
+        }
+    }
+    tracing::debug!(
+        "의사코드 파싱 실패: {failed_instructions}/{} 인스트럭션, {failed_lines} 라인",
+        instructions.len()
+    );
+
+    let mut report = vec![
+        "# Pseudocode Parse Failures".to_owned(),
+        String::new(),
+        format!("- Instructions: {}", instructions.len()),
+        format!("- Instructions with failures: {failed_instructions}"),
+        format!("- Failed lines: {failed_lines}"),
+    ];
+    report.append(&mut details);
+
+    // 파싱하지 못한 에일리어스 선호 조건
+    let mut alias_failures = Vec::new();
+    for instr in instructions {
+        for alias in &instr.aliases {
+            for condition in &alias.preferred_conditions {
+                if let AliasPreference::Unparsed(message) = &condition.preference {
+                    alias_failures.push(format!(
+                        "- {} → {}: `{}` — {message}",
+                        instr.id, alias.name, condition.text
+                    ));
+                }
+            }
+        }
+    }
+    if !alias_failures.is_empty() {
+        report.push(String::new());
+        report.push("## Alias Preference Conditions".to_owned());
+        report.append(&mut alias_failures);
+    }
+    std::fs::create_dir_all("result").expect("베이스 디렉토리 생성 불가");
+    std::fs::write("result/arm_pseudocode_report.md", report.join("\n")).expect("리포트 생성 실패");
+}
+
+/// 인스트럭션을 `directory`에 MD 파일로 저장. 에일리어스 페이지는 enum 생성 목록에서 제외
+fn save_instructions(
+    instructions: Vec<ArmInstruction>,
+    directory: &str,
+) -> Vec<(String, Vec<String>)> {
     let mut saved: Vec<(String, Vec<String>)> = Vec::new();
-    static INIT_DIRECTORY: Once = Once::new();
-    INIT_DIRECTORY.call_once(|| {
-        std::fs::create_dir_all("result/arm").expect("ARM 결과 디렉토리 생성 불가");
-    });
+    std::fs::create_dir_all(directory).expect("ARM 결과 디렉토리 생성 불가");
 
     for instr in instructions {
         let slug = instr.filename_slug();
         let mnemonic = instr.get_instruction_name();
+        let is_alias = instr.alias_to.is_some();
         tracing::debug!("{} 페이지 생성중", instr.heading);
 
         let md_contents: Vec<String> = instr.into_md();
 
-        let filepath = format!("result/arm/{slug}.md");
+        let filepath = format!("{directory}/{slug}.md");
         std::fs::write(&filepath, md_contents.join("\n"))
             .unwrap_or_else(|e| tracing::warn!("{filepath} 생성 실패: {e}"));
 
-        saved.push((mnemonic, md_contents));
+        if !is_alias {
+            saved.push((mnemonic, md_contents));
+        }
     }
 
     saved
 }
 
-/// Rust enum 생성: 니모닉별로 첫 번째 variant의 docs 사용
-fn saved_list_to_rust_enum(saved: Vec<(String, Vec<String>)>) {
+/// `path`에 `enum_name` Rust enum 생성: 니모닉별로 첫 번째 variant의 docs 사용.
+/// 니모닉 → variant 이름 반환
+fn saved_list_to_rust_enum(
+    saved: Vec<(String, Vec<String>)>,
+    enum_name: &str,
+    path: &str,
+) -> HashMap<String, String> {
     // 니모닉별 첫 번째 variant의 docs만 사용
     let mut mnemonic_docs: HashMap<String, Vec<String>> = HashMap::new();
     for (mnemonic, docs) in &saved {
@@ -83,8 +248,10 @@ fn saved_list_to_rust_enum(saved: Vec<(String, Vec<String>)>) {
     keys.sort();
 
     let mut result = Vec::new();
-    result.push("enum Aarch64 {".into());
+    result.push("#[derive(Debug, Clone, Copy, PartialEq, Eq)]".into());
+    result.push(format!("pub enum {enum_name} {{"));
 
+    let mut variants = HashMap::new();
     let mut seen_variants: HashMap<String, usize> = HashMap::new();
     for mnemonic in keys {
         let docs = mnemonic_docs.remove(&mnemonic).unwrap();
@@ -104,10 +271,12 @@ fn saved_list_to_rust_enum(saved: Vec<(String, Vec<String>)>) {
         };
         *seen_variants.get_mut(&variant).unwrap() += 1;
         result.push(format!("    {final_variant},"));
+        variants.insert(mnemonic, final_variant);
     }
     result.push("}".into());
 
-    std::fs::write("result/arm.rs", result.join("\n")).expect("ARM 모듈 생성 실패");
+    std::fs::write(path, result.join("\n")).expect("ARM 모듈 생성 실패");
+    variants
 }
 
 /// 니모닉을 유효한 Rust enum variant 이름으로 변환
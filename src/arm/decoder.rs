@@ -0,0 +1,407 @@
+use super::result::{ArmInstruction, EncodingIndex, EncodingPattern};
+use std::collections::HashMap;
+
+/// 리프 하나에 남길 최대 후보 수 — 이하이면 선형 마스크 매칭
+const LEAF_SIZE: usize = 4;
+
+/// 디코더 테이블의 인코딩 한 줄
+#[derive(Debug, Clone)]
+pub(crate) struct DecoderEntry {
+    /// 인코딩 이름 (e.g., "ADD_32_addsub_imm")
+    pub(crate) name: String,
+    /// 소속 인스트럭션 니모닉 (Aarch64 variant 결정용)
+    pub(crate) mnemonic: String,
+    pub(crate) pattern: EncodingPattern,
+    /// 고정되지 않은 이름 있는 필드 (이름, hibit, width) — 다이어그램 순서
+    pub(crate) fields: Vec<(String, u8, u8)>,
+}
+
+impl DecoderEntry {
+    /// 인스트럭션의 인코딩들을 디코더 엔트리로 변환 (에일리어스 페이지 제외)
+    pub(crate) fn collect(instructions: &[ArmInstruction]) -> Vec<DecoderEntry> {
+        let mut entries = Vec::new();
+        for instr in instructions.iter().filter(|instr| instr.alias_to.is_none()) {
+            for enc in &instr.encodings {
+                if enc.pattern.mask == 0 {
+                    continue;
+                }
+                let fields = enc
+                    .bitfields
+                    .iter()
+                    .filter(|bf| !bf.name.is_empty())
+                    .filter(|bf| bf.mask() & !enc.pattern.mask != 0)
+                    .map(|bf| (bf.name.clone(), bf.hibit, bf.width))
+                    .collect();
+                entries.push(DecoderEntry {
+                    name: enc.name.clone(),
+                    mnemonic: instr.get_instruction_name(),
+                    pattern: enc.pattern.clone(),
+                    fields,
+                });
+            }
+        }
+        entries
+    }
+}
+
+/// 결정 트리 노드: 비트 하나로 분기하거나 후보를 순서대로 매칭
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Split {
+        bit: u8,
+        zero: usize,
+        one: usize,
+    },
+    /// 엔트리 인덱스 — 고정 비트가 많은 인코딩부터
+    Leaf(Vec<usize>),
+}
+
+/// 결정 트리 생성. 같은 후보 집합의 서브트리는 공유하며 루트는 0번 노드.
+/// `seed`: 먼저 분기할 비트 (인코딩 인덱스 최상위 그룹의 decode 비트), 상위 비트부터
+fn build_tree(entries: &[DecoderEntry], seed: u32) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut memo = HashMap::new();
+    let candidates: Vec<usize> = (0..entries.len()).collect();
+    build_node(entries, candidates, seed, &mut nodes, &mut memo);
+    nodes
+}
+
+fn build_node(
+    entries: &[DecoderEntry],
+    mut candidates: Vec<usize>,
+    seed: u32,
+    nodes: &mut Vec<Node>,
+    memo: &mut HashMap<(Vec<usize>, u32), usize>,
+) -> usize {
+    let key = (candidates.clone(), seed);
+    if let Some(&index) = memo.get(&key) {
+        return index;
+    }
+    let index = nodes.len();
+    memo.insert(key, index);
+
+    // 후보를 실제로 나누는 seed 비트가 남아 있으면 그 비트부터
+    let seeded = (0..32u8)
+        .rev()
+        .filter(|bit| seed & (1 << bit) != 0)
+        .find(|&bit| splits(entries, &candidates, bit));
+    let seed = seeded.map_or(0, |bit| seed & !(1 << bit));
+    let split = (candidates.len() > LEAF_SIZE)
+        .then(|| seeded.or_else(|| best_split(entries, &candidates)))
+        .flatten();
+    let Some(bit) = split else {
+        candidates.sort_by_key(|&i| std::cmp::Reverse(entries[i].pattern.mask.count_ones()));
+        nodes.push(Node::Leaf(candidates));
+        return index;
+    };
+
+    // 자리 확보 후 자식 생성
+    nodes.push(Node::Leaf(Vec::new()));
+    let flag = 1u32 << bit;
+    let side = |one: bool| -> Vec<usize> {
+        candidates
+            .iter()
+            .copied()
+            .filter(|&i| {
+                let pattern = &entries[i].pattern;
+                pattern.mask & flag == 0 || (pattern.value & flag != 0) == one
+            })
+            .collect()
+    };
+    let (zero_side, one_side) = (side(false), side(true));
+    let zero = build_node(entries, zero_side, seed, nodes, memo);
+    let one = build_node(entries, one_side, seed, nodes, memo);
+    nodes[index] = Node::Split { bit, zero, one };
+    index
+}
+
+/// 비트 하나가 후보를 0 쪽과 1 쪽으로 나누는지
+fn splits(entries: &[DecoderEntry], candidates: &[usize], bit: u8) -> bool {
+    let flag = 1u32 << bit;
+    let fixed = |one: bool| {
+        candidates.iter().any(|&i| {
+            let pattern = &entries[i].pattern;
+            pattern.mask & flag != 0 && (pattern.value & flag != 0) == one
+        })
+    };
+    fixed(false) && fixed(true)
+}
+
+/// 양쪽 자식 중 큰 쪽이 가장 작아지는 비트. 어느 비트로도 나뉘지 않으면 None
+fn best_split(entries: &[DecoderEntry], candidates: &[usize]) -> Option<u8> {
+    let mut best: Option<(usize, u8)> = None;
+    for bit in 0..32u8 {
+        let flag = 1u32 << bit;
+        let (mut zeros, mut ones, mut any) = (0, 0, 0);
+        for &i in candidates {
+            let pattern = &entries[i].pattern;
+            if pattern.mask & flag == 0 {
+                any += 1;
+            } else if pattern.value & flag == 0 {
+                zeros += 1;
+            } else {
+                ones += 1;
+            }
+        }
+        if zeros == 0 || ones == 0 {
+            continue;
+        }
+        let larger = zeros.max(ones) + any;
+        if best.is_none_or(|(size, _)| larger < size) {
+            best = Some((larger, bit));
+        }
+    }
+    best.map(|(_, bit)| bit)
+}
+
+/// 생성 모듈의 고정 부분: 타입 정의와 트리 탐색
+const DECODER_PRELUDE: &str = r#"use super::arm::Aarch64;
+
+/// 인코딩 하나의 매칭 정보
+#[derive(Debug)]
+pub struct Encoding {
+    pub name: &'static str,
+    pub instruction: Aarch64,
+    /// `(insn & mask) == value`
+    pub mask: u32,
+    pub value: u32,
+    /// `(insn & mask) != value` 제약
+    pub not_equal: &'static [(u32, u32)],
+    /// 오퍼랜드 필드 (이름, hibit, width) — 같은 이름은 상위 조각부터
+    pub fields: &'static [(&'static str, u8, u8)],
+}
+
+impl Encoding {
+    pub fn matches(&self, insn: u32) -> bool {
+        insn & self.mask == self.value
+            && self.not_equal.iter().all(|&(mask, value)| insn & mask != value)
+    }
+}
+
+/// 디코드된 인스트럭션의 오퍼랜드 필드
+#[derive(Debug, Clone, Copy)]
+pub struct Fields {
+    pub insn: u32,
+    pub encoding: &'static Encoding,
+}
+
+impl Fields {
+    /// 필드 값. 여러 조각으로 나뉜 필드는 상위 조각부터 이어 붙임
+    pub fn get(&self, name: &str) -> Option<u32> {
+        let mut result = None;
+        for &(field, hibit, width) in self.encoding.fields {
+            if field != name {
+                continue;
+            }
+            let low = hibit + 1 - width;
+            let bits = (self.insn >> low) & (u32::MAX >> (32 - width as u32));
+            result = Some(result.map_or(0, |high: u32| high << width) | bits);
+        }
+        result
+    }
+
+    /// (필드 이름, 값) — 인코딩 다이어그램 순서
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, u32)> + '_ {
+        let fields = self.encoding.fields;
+        fields
+            .iter()
+            .enumerate()
+            .filter(move |&(i, (name, _, _))| fields[..i].iter().all(|(other, _, _)| other != name))
+            .filter_map(move |(_, &(name, _, _))| Some((name, self.get(name)?)))
+    }
+}
+
+enum Node {
+    Split { bit: u8, zero: u32, one: u32 },
+    Leaf(&'static [u16]),
+}
+
+/// 32비트 A64 인스트럭션 디코드
+pub fn decode(insn: u32) -> Option<(Aarch64, Fields)> {
+    let mut node = 0;
+    loop {
+        match &NODES[node] {
+            Node::Split { bit, zero, one } => {
+                node = if (insn >> bit) & 1 == 0 { *zero } else { *one } as usize;
+            }
+            Node::Leaf(candidates) => {
+                let encoding = candidates
+                    .iter()
+                    .map(|&i| &ENCODINGS[i as usize])
+                    .find(|encoding| encoding.matches(insn))?;
+                return Some((encoding.instruction, Fields { insn, encoding }));
+            }
+        }
+    }
+}
+"#;
+
+/// 디코더 Rust 모듈 소스 생성. `variants`: 니모닉 → Aarch64 variant 이름.
+/// `index`가 있으면 최상위 분기를 그 그룹 decode 비트로 시작
+pub(crate) fn decoder_to_rust(
+    entries: &[DecoderEntry],
+    variants: &HashMap<String, String>,
+    index: Option<&EncodingIndex>,
+) -> String {
+    let entries: Vec<DecoderEntry> = entries
+        .iter()
+        .filter(|entry| variants.contains_key(&entry.mnemonic))
+        .cloned()
+        .collect();
+    let seed = index.map_or(0, |index| {
+        index
+            .groups
+            .iter()
+            .fold(0, |seed, group| seed | group.pattern.mask)
+    });
+    let nodes = build_tree(&entries, seed);
+
+    let mut result = vec![
+        "//! A64 디코더 — ARM XML 인코딩에서 자동 생성됨. `arm` 모듈과 같은 위치에 둘 것"
+            .to_owned(),
+        if seed != 0 {
+            format!(
+                "//! 최상위 분기는 인코딩 인덱스 그룹의 decode 비트 ({seed:#010x}), \
+                 그 아래는 인코딩 마스크에서 도출"
+            )
+        } else {
+            "//! 분기는 인코딩 마스크에서만 도출 (인코딩 인덱스 미사용)".to_owned()
+        },
+        DECODER_PRELUDE.to_owned(),
+    ];
+    result.push(format!("static NODES: [Node; {}] = [", nodes.len()));
+    for node in &nodes {
+        match node {
+            Node::Split { bit, zero, one } => result.push(format!(
+                "    Node::Split {{ bit: {bit}, zero: {zero}, one: {one} }},"
+            )),
+            Node::Leaf(candidates) => {
+                let candidates: Vec<String> = candidates.iter().map(|i| i.to_string()).collect();
+                result.push(format!("    Node::Leaf(&[{}]),", candidates.join(", ")));
+            }
+        }
+    }
+    result.push("];".to_owned());
+    result.push(String::new());
+
+    result.push(format!(
+        "pub static ENCODINGS: [Encoding; {}] = [",
+        entries.len()
+    ));
+    for entry in &entries {
+        let not_equal: Vec<String> = entry
+            .pattern
+            .not_equal
+            .iter()
+            .map(|(mask, value)| format!("({mask:#010x}, {value:#010x})"))
+            .collect();
+        let fields: Vec<String> = entry
+            .fields
+            .iter()
+            .map(|(name, hibit, width)| format!("({name:?}, {hibit}, {width})"))
+            .collect();
+        result.push("    Encoding {".to_owned());
+        result.push(format!("        name: {:?},", entry.name));
+        result.push(format!(
+            "        instruction: Aarch64::{},",
+            variants[&entry.mnemonic]
+        ));
+        result.push(format!("        mask: {:#010x},", entry.pattern.mask));
+        result.push(format!("        value: {:#010x},", entry.pattern.value));
+        result.push(format!("        not_equal: &[{}],", not_equal.join(", ")));
+        result.push(format!("        fields: &[{}],", fields.join(", ")));
+        result.push("    },".to_owned());
+    }
+    result.push("];".to_owned());
+    result.push(String::new());
+    result.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, mask: u32, value: u32, not_equal: &[(u32, u32)]) -> DecoderEntry {
+        DecoderEntry {
+            name: name.to_owned(),
+            mnemonic: name.split('_').next().unwrap().to_owned(),
+            pattern: EncodingPattern {
+                mask,
+                value,
+                not_equal: not_equal.to_vec(),
+                ..Default::default()
+            },
+            fields: Vec::new(),
+        }
+    }
+
+    /// 생성될 decode()와 같은 방식으로 트리 탐색
+    fn lookup<'a>(entries: &'a [DecoderEntry], nodes: &[Node], insn: u32) -> Option<&'a str> {
+        let mut node = 0;
+        loop {
+            match &nodes[node] {
+                Node::Split { bit, zero, one } => {
+                    node = if (insn >> bit) & 1 == 0 { *zero } else { *one };
+                }
+                Node::Leaf(candidates) => {
+                    return candidates
+                        .iter()
+                        .map(|&i| &entries[i])
+                        .find(|entry| {
+                            let pattern = &entry.pattern;
+                            insn & pattern.mask == pattern.value
+                                && pattern
+                                    .not_equal
+                                    .iter()
+                                    .all(|&(mask, value)| insn & mask != value)
+                        })
+                        .map(|entry| entry.name.as_str());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_decision_tree() {
+        let entries = [
+            entry("ADD_32", 0xFF80_0000, 0x1100_0000, &[]),
+            entry("ADD_64", 0xFF80_0000, 0x9100_0000, &[]),
+            entry("SUB_32", 0xFF80_0000, 0x5100_0000, &[]),
+            entry("SUB_64", 0xFF80_0000, 0xD100_0000, &[]),
+            entry("B", 0xFC00_0000, 0x1400_0000, &[]),
+            entry("BL", 0xFC00_0000, 0x9400_0000, &[]),
+            entry("LD1_I1", 0xBFFF_F000, 0x0CDF_7000, &[]),
+            entry(
+                "LD1_R1",
+                0xBFE0_F000,
+                0x0CC0_7000,
+                &[(0x001F_0000, 0x001F_0000)],
+            ),
+        ];
+        let nodes = build_tree(&entries, 0);
+        assert!(matches!(nodes[0], Node::Split { .. }));
+
+        assert_eq!(lookup(&entries, &nodes, 0x9100_0420), Some("ADD_64"));
+        assert_eq!(lookup(&entries, &nodes, 0x1100_0420), Some("ADD_32"));
+        assert_eq!(lookup(&entries, &nodes, 0xD100_0420), Some("SUB_64"));
+        assert_eq!(lookup(&entries, &nodes, 0x9400_0001), Some("BL"));
+        assert_eq!(lookup(&entries, &nodes, 0x4CDF_7020), Some("LD1_I1"));
+        assert_eq!(lookup(&entries, &nodes, 0x4CC3_7020), Some("LD1_R1"));
+        assert_eq!(lookup(&entries, &nodes, 0x0000_0000), None);
+
+        // op0 (28:25) 비트로 먼저 분기
+        let seeded = build_tree(&entries, 0x1E00_0000);
+        let Node::Split { bit, one, .. } = seeded[0] else {
+            panic!("root is a leaf");
+        };
+        assert_eq!(bit, 28);
+        // 27번 비트로는 나뉘지 않으므로 다음 seed 비트
+        assert!(matches!(seeded[one], Node::Split { bit: 26, .. }));
+        for insn in [0x9100_0420, 0x9400_0001, 0x4CDF_7020, 0x4CC3_7020] {
+            assert_eq!(
+                lookup(&entries, &seeded, insn),
+                lookup(&entries, &nodes, insn)
+            );
+        }
+    }
+}
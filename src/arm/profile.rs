@@ -0,0 +1,182 @@
+use super::result::{arch_version, ArchRequirement, ArmInstruction};
+use std::collections::HashSet;
+
+/// 출력 대상 프로파일. `-march` 형식 (e.g., "armv8.2-a+nosve", "armv8.0-a+lse")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ArchProfile {
+    version: (u8, u8),
+    /// `+feature`: 버전과 무관하게 사용
+    enabled: Vec<String>,
+    /// `+nofeature`: 버전과 무관하게 제외
+    disabled: Vec<String>,
+}
+
+impl ArchProfile {
+    pub(crate) fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().split('+');
+        let version = arch_version(parts.next()?)?;
+        let mut profile = Self {
+            version,
+            enabled: Vec::new(),
+            disabled: Vec::new(),
+        };
+        for part in parts {
+            let part = part.to_ascii_lowercase();
+            match part.strip_prefix("no") {
+                Some(feature) => profile.disabled.push(feature.to_owned()),
+                None => profile.enabled.push(part),
+            }
+        }
+        Some(profile)
+    }
+
+    /// 기능은 이름의 버전 (e.g., "ARMv8.1-LSE" → 8.1)과 요구 버전 중 높은 쪽이 프로파일 이하이면 포함
+    pub(crate) fn supports(&self, requirement: &ArchRequirement) -> bool {
+        let required = arch_version(&requirement.version).unwrap_or((8, 0));
+        if requirement.features.is_empty() {
+            return required <= self.version;
+        }
+        requirement.features.iter().all(|feature| {
+            let name = feature_name(feature);
+            if self.disabled.contains(&name) {
+                false
+            } else if self.enabled.contains(&name) {
+                true
+            } else {
+                arch_version(feature).map_or(required, |version| version.max(required))
+                    <= self.version
+            }
+        })
+    }
+
+    /// 지원하지 않는 인코딩 제거, 인코딩이 모두 빠진 인스트럭션과 그 에일리어스 링크 제거
+    pub(crate) fn retain(&self, instructions: &mut Vec<ArmInstruction>) {
+        let mut removed = HashSet::new();
+        instructions.retain_mut(|instr| {
+            if instr.encodings.is_empty() {
+                return true;
+            }
+            instr
+                .encodings
+                .retain(|enc| self.supports(&enc.requirement));
+            if instr.encodings.is_empty() {
+                removed.insert(instr.id.clone());
+            }
+            !instr.encodings.is_empty()
+        });
+        for instr in instructions {
+            instr.aliases.retain(|alias| !removed.contains(&alias.id));
+        }
+    }
+}
+
+/// "ARMv8.1-LSE" → "lse", "FEAT_LSE" → "lse", "SVE" → "sve"
+fn feature_name(feature: &str) -> String {
+    let name = match arch_version(feature) {
+        Some(_) => feature.split_once('-').map_or(feature, |(_, name)| name),
+        None => feature,
+    };
+    // 최신 XML은 FEAT_ 접두어 표기 사용
+    let name = match name.get(..5) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("FEAT_") => &name[5..],
+        _ => name,
+    };
+    name.to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arm::result::{ArmAlias, ArmEncoding};
+
+    fn requirement(variants: &[(&str, &str)]) -> ArchRequirement {
+        let mut requirement = ArchRequirement::default();
+        for (name, feature) in variants {
+            requirement.add(name, feature);
+        }
+        requirement
+    }
+
+    #[test]
+    fn test_profile_supports() {
+        let profile = ArchProfile::parse("armv8.2-a+nosve+bf16").unwrap();
+        assert_eq!(profile.version, (8, 2));
+        assert!(profile.supports(&requirement(&[])));
+        assert!(profile.supports(&requirement(&[("ARMv8.1", "ARMv8.1-LSE")])));
+        assert!(profile.supports(&requirement(&[("ARMv8.2-A", "")])));
+        assert!(!profile.supports(&requirement(&[("ARMv8.2", "SVE")])));
+        assert!(!profile.supports(&requirement(&[("ARMv8.3", "ARMv8.3-PAuth")])));
+        assert!(!profile.supports(&requirement(&[("", "ARMv8.4-TLBI")])));
+        // BF16은 명시적으로 켬
+        assert!(profile.supports(&requirement(&[("ARMv8.6", "ARMv8.2-BF16")])));
+        // FEAT_ 표기
+        assert!(profile.supports(&requirement(&[("ARMv8.6", "FEAT_BF16")])));
+        assert!(!profile.supports(&requirement(&[("ARMv8.2", "FEAT_SVE")])));
+        let nolse = ArchProfile::parse("armv8.2-a+nolse").unwrap();
+        assert!(!nolse.supports(&requirement(&[("ARMv8.1", "FEAT_LSE")])));
+        assert!(nolse.supports(&requirement(&[("ARMv8.1", "feat_crc32")])));
+        assert!(ArchProfile::parse("x86-64").is_none());
+    }
+
+    #[test]
+    fn test_profile_retain() {
+        let encoding = |name: &str, requirement: ArchRequirement| ArmEncoding {
+            name: name.to_owned(),
+            requirement,
+            ..Default::default()
+        };
+        let mut instructions = vec![
+            ArmInstruction {
+                id: "FADD_float".to_owned(),
+                encodings: vec![
+                    encoding(
+                        "FADD_H_floatdp2",
+                        requirement(&[("ARMv8.2", "ARMv8.2-FP16")]),
+                    ),
+                    encoding("FADD_S_floatdp2", requirement(&[])),
+                ],
+                ..Default::default()
+            },
+            ArmInstruction {
+                id: "LDADD".to_owned(),
+                encodings: vec![encoding(
+                    "LDADD_32",
+                    requirement(&[("ARMv8.1", "ARMv8.1-LSE")]),
+                )],
+                aliases: vec![ArmAlias {
+                    id: "STADD_LDADD".to_owned(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ArmInstruction {
+                id: "STADD_LDADD".to_owned(),
+                encodings: vec![encoding(
+                    "STADD_32S_LDADD",
+                    requirement(&[("ARMv8.1", "ARMv8.1-LSE")]),
+                )],
+                ..Default::default()
+            },
+        ];
+        let mut v80 = instructions.clone();
+        ArchProfile::parse("armv8.0-a").unwrap().retain(&mut v80);
+        assert_eq!(v80.len(), 1);
+        assert_eq!(v80[0].encodings.len(), 1);
+        assert_eq!(v80[0].encodings[0].name, "FADD_S_floatdp2");
+
+        ArchProfile::parse("armv8.2-a")
+            .unwrap()
+            .retain(&mut instructions);
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(instructions[0].encodings.len(), 2);
+        assert_eq!(instructions[1].aliases.len(), 1);
+
+        let mut without_stadd = instructions.clone();
+        without_stadd[2].encodings[0].requirement.add("ARMv8.5", "");
+        ArchProfile::parse("armv8.2-a")
+            .unwrap()
+            .retain(&mut without_stadd);
+        assert_eq!(without_stadd.len(), 2);
+        assert!(without_stadd[1].aliases.is_empty());
+    }
+}
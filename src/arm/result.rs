@@ -1,3 +1,6 @@
+use crate::pseudocode::asl;
+use std::collections::HashMap;
+
 #[derive(Debug, Default, Clone)]
 pub(crate) struct MdTable {
     pub(crate) headers: Vec<String>,
@@ -38,6 +41,209 @@ pub(crate) struct ArmEncoding {
     pub(crate) label: String,
     /// 어셈블리 템플릿 (e.g., "ADD <Wd|WSP>, <Wn|WSP>, #<imm>{, <shift>}")
     pub(crate) asm_template: String,
+    /// `asm_template`의 토큰, 오퍼랜드는 설명과 인코딩 필드에 연결됨
+    pub(crate) template: Vec<AsmToken>,
+    /// 소속 iclass 이름 (e.g., "Not setting the condition flags")
+    pub(crate) iclass: String,
+    /// 소속 iclass의 인코딩 다이어그램
+    pub(crate) bitfields: Vec<BitField>,
+    /// iclass 다이어그램 위에 걸리는 필드 제약 (`bitdiffs`, e.g., "sf == 0")
+    pub(crate) constraints: Vec<FieldConstraint>,
+    /// 소속 iclass의 디코드 의사코드
+    pub(crate) decode_pseudocode: String,
+    /// iclass 다이어그램 + 인코딩별 박스로 계산한 고정 비트
+    pub(crate) pattern: EncodingPattern,
+    /// 에일리어스 인코딩의 "is equivalent to" 매핑
+    pub(crate) equivalent_to: Option<ArmEquivalent>,
+    /// iclass + 인코딩 `<arch_variants>`
+    pub(crate) requirement: ArchRequirement,
+    /// 소속 iclass의 명령어 집합
+    pub(crate) isa: InstructionSet,
+}
+
+impl ArmEncoding {
+    /// 고정되지 않은 `cond` 필드가 있으면 조건 실행 인코딩 (A32 대부분, T32 `B<c>`)
+    pub(crate) fn is_conditional(&self) -> bool {
+        self.bitfields
+            .iter()
+            .any(|bf| bf.name == "cond" && bf.mask() & !self.pattern.mask != 0)
+    }
+}
+
+/// 인코딩의 명령어 집합. T32는 16비트와 32비트 (하프워드 두 개) 인코딩을 구분
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum InstructionSet {
+    #[default]
+    A64,
+    A32,
+    /// 16비트 T32 인코딩 (비트 15:0)
+    T16,
+    /// 32비트 T32 인코딩. 첫 하프워드가 비트 31:16 (`hw1 << 16 | hw2`)
+    T32,
+}
+
+impl InstructionSet {
+    /// iclass `isa` 속성과 regdiagram `form` (e.g., "32", "16", "16x2")
+    pub(crate) fn from_xml(isa: &str, form: &str) -> Self {
+        match isa {
+            "A32" => Self::A32,
+            "T16" => Self::T16,
+            "T32" if form == "16" => Self::T16,
+            "T32" => Self::T32,
+            _ => Self::A64,
+        }
+    }
+
+    /// 인스트럭션 단위 이름 (T16도 "T32")
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::A64 => "A64",
+            Self::A32 => "A32",
+            Self::T16 | Self::T32 => "T32",
+        }
+    }
+
+    pub(crate) fn is_aarch32(&self) -> bool {
+        *self != Self::A64
+    }
+
+    /// 인코딩 비트 수
+    pub(crate) fn width(&self) -> u8 {
+        match self {
+            Self::T16 => 16,
+            _ => 32,
+        }
+    }
+}
+
+/// 어셈블리 템플릿 토큰
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum AsmToken {
+    /// 고정 텍스트 (e.g., "ADD ", ", #"). 레지스터 목록 중괄호 "{ ", " }" 포함
+    Literal(String),
+    Operand(AsmOperand),
+    /// `{, <shift>}` 선택 그룹
+    Optional(Vec<AsmToken>),
+}
+
+/// 템플릿의 오퍼랜드 자리 (`<asmtemplate>`의 `<a>`)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct AsmOperand {
+    /// e.g., "<Wd|WSP>"
+    pub(crate) symbol: String,
+    /// 오퍼랜드 설명의 symbol link (e.g., "wd_wsp")
+    pub(crate) link: String,
+    /// e.g., "32-bit destination general-purpose register or WSP (field \"Rd\")"
+    pub(crate) hover: String,
+    /// `ArmInstruction::operand_explanations` 인덱스
+    pub(crate) explanation: Option<usize>,
+    /// 값이 인코딩되는 필드 (e.g., ["Rd"], ["size", "Q"])
+    pub(crate) fields: Vec<String>,
+}
+
+/// 아키텍처 요구 사항 (`<arch_variant name="ARMv8.1" feature="ARMv8.1-LSE" />`)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct ArchRequirement {
+    /// 최소 아키텍처 버전 (e.g., "ARMv8.1"), 비어 있으면 ARMv8.0
+    pub(crate) version: String,
+    /// 필요한 기능 (e.g., "ARMv8.1-LSE", "SVE")
+    pub(crate) features: Vec<String>,
+}
+
+impl ArchRequirement {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.version.is_empty() && self.features.is_empty()
+    }
+
+    /// `<arch_variant>` 하나 추가: 버전은 높은 쪽, 기능은 합집합
+    pub(crate) fn add(&mut self, name: &str, feature: &str) {
+        if let Some(version) = arch_version(name) {
+            if arch_version(&self.version).is_none_or(|current| current < version) {
+                self.version = format!("ARMv{}.{}", version.0, version.1);
+            }
+        }
+        if !feature.is_empty() && !self.features.iter().any(|f| f == feature) {
+            self.features.push(feature.to_owned());
+        }
+    }
+
+    /// 모든 인코딩에 공통인 요구 사항: 가장 낮은 버전, 공통 기능
+    pub(crate) fn common<'a>(mut requirements: impl Iterator<Item = &'a ArchRequirement>) -> Self {
+        let Some(first) = requirements.next() else {
+            return Self::default();
+        };
+        let mut result = first.clone();
+        for requirement in requirements {
+            if arch_version(&requirement.version) < arch_version(&result.version) {
+                result.version = requirement.version.clone();
+            }
+            result.features.retain(|f| requirement.features.contains(f));
+        }
+        result
+    }
+
+    /// e.g., "ARMv8.1, ARMv8.1-LSE"
+    fn to_md(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.version.is_empty() {
+            parts.push(self.version.clone());
+        }
+        parts.extend(self.features.iter().cloned());
+        parts.join(", ")
+    }
+}
+
+/// "ARMv8.2-A", "ARMv8.2", "ARMv8.2-FP16", "armv8.2-a" → (8, 2)
+pub(crate) fn arch_version(text: &str) -> Option<(u8, u8)> {
+    let rest = text.get(..4)?.eq_ignore_ascii_case("armv").then(|| &text[4..])?;
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(rest.len());
+    let (major, minor) = rest[..end].split_once('.').unwrap_or((&rest[..end], "0"));
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// 에일리어스 인코딩 → 정규 인스트럭션 인코딩
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ArmEquivalent {
+    /// 정규 형식 어셈블리 (e.g., "ORR <Wd>, WZR, <Wm>")
+    pub(crate) asm_template: String,
+    /// 정규 인코딩 이름 (e.g., "ORR_32_log_shift")
+    pub(crate) encoding: String,
+    /// 에일리어스 사용 조건 (e.g., "Unconditionally")
+    pub(crate) condition: String,
+    /// 에일리어스가 고정하는 정규 인코딩 필드 (필드 이름, 비트 문자열) (e.g., ("Rn", "11111"))
+    pub(crate) substitutions: Vec<(String, String)>,
+}
+
+/// 인코딩 필드 제약 하나: `sf == 0`, `Rm != 11111`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct FieldConstraint {
+    /// 필드 이름
+    pub(crate) field: String,
+    /// `==` 이면 true, `!=` 이면 false
+    pub(crate) equal: bool,
+    /// 비트 문자열 (e.g., "01", "1x")
+    pub(crate) value: String,
+}
+
+impl FieldConstraint {
+    pub(crate) fn to_md(&self) -> String {
+        let op = if self.equal { "==" } else { "!=" };
+        format!("{} {op} {}", self.field, self.value)
+    }
+}
+
+/// 인코딩 비트 패턴: `(insn & mask) == value`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct EncodingPattern {
+    pub(crate) mask: u32,
+    pub(crate) value: u32,
+    /// "should be" 비트 `(0)`/`(1)` — 매칭에는 쓰지 않음
+    pub(crate) should_be_mask: u32,
+    pub(crate) should_be_value: u32,
+    /// `!= 1111` 제약: `(insn & mask) != value`
+    pub(crate) not_equal: Vec<(u32, u32)>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -48,16 +254,536 @@ pub(crate) struct BitField {
     pub(crate) width: u8,
     /// 필드 이름
     pub(crate) name: String,
-    /// 상수 값 (있으면)
+    /// `<c>` 셀 내용 (e.g., "1", "(0)", "!= 1111"). 빈 셀은 비트마다 ""
     pub(crate) constants: Vec<String>,
 }
 
+impl BitField {
+    /// 필드가 차지하는 32비트 마스크
+    pub(crate) fn mask(&self) -> u32 {
+        let width = self.width.min(32) as u32;
+        let low = (self.hibit as u32 + 1).saturating_sub(width);
+        let ones = if width == 32 {
+            u32::MAX
+        } else {
+            (1 << width) - 1
+        };
+        ones << low
+    }
+}
+
+/// encodingindex.xml 계층의 그룹 하나 (`<node>`)
+#[derive(Debug, Default, Clone)]
+pub(crate) struct EncodingGroup {
+    /// groupname 또는 iclass (e.g., "dpimm", "addsub_imm")
+    pub(crate) id: String,
+    /// 표시 이름 (e.g., "Data Processing -- Immediate")
+    pub(crate) header: String,
+    /// UNALLOCATED 영역
+    pub(crate) unallocated: bool,
+    /// 루트부터 누적한 decode 비트
+    pub(crate) pattern: EncodingPattern,
+    pub(crate) children: Vec<EncodingGroup>,
+    /// iclass 리프에 속한 인스트럭션 id (e.g., "ADD_addsub_imm")
+    pub(crate) instructions: Vec<String>,
+}
+
+/// 인코딩 인덱스 파일 하나의 디코드 계층
+#[derive(Debug, Default, Clone)]
+pub(crate) struct EncodingIndex {
+    /// e.g., "A64"
+    pub(crate) instruction_set: String,
+    pub(crate) groups: Vec<EncodingGroup>,
+}
+
+impl EncodingIndex {
+    /// 인스트럭션 id → 소속 그룹 경로들 (header 목록, 루트부터)
+    pub(crate) fn instruction_paths(&self) -> HashMap<String, Vec<Vec<String>>> {
+        fn walk(
+            group: &EncodingGroup,
+            path: &mut Vec<String>,
+            paths: &mut HashMap<String, Vec<Vec<String>>>,
+        ) {
+            path.push(group.header.clone());
+            for id in &group.instructions {
+                paths.entry(id.clone()).or_default().push(path.clone());
+            }
+            for child in &group.children {
+                walk(child, path, paths);
+            }
+            path.pop();
+        }
+
+        let mut paths = HashMap::new();
+        for group in &self.groups {
+            walk(group, &mut Vec::new(), &mut paths);
+        }
+        paths
+    }
+
+    /// Markdown 트리로 변환
+    pub(crate) fn to_md(&self) -> Vec<String> {
+        fn walk(group: &EncodingGroup, depth: usize, result: &mut Vec<String>) {
+            let indent = "  ".repeat(depth);
+            let unallocated = if group.unallocated {
+                " (unallocated)"
+            } else {
+                ""
+            };
+            result.push(format!(
+                "{indent}- {} `{}`{unallocated} `mask={:#010x} value={:#010x}`",
+                group.header, group.id, group.pattern.mask, group.pattern.value
+            ));
+            for id in &group.instructions {
+                result.push(format!("{indent}  - {id}"));
+            }
+            for child in &group.children {
+                walk(child, depth + 1, result);
+            }
+        }
+
+        let mut result = vec![
+            format!("# {} Encoding Index", self.instruction_set),
+            "".to_owned(),
+        ];
+        for group in &self.groups {
+            walk(group, 0, &mut result);
+        }
+        result.push("".to_owned());
+        result
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub(crate) struct ArmAlias {
+    /// 에일리어스 페이지 id (e.g., "MOV_ORR_log_shift")
+    pub(crate) id: String,
     /// 에일리어스 이름
     pub(crate) name: String,
-    /// 선호 디스어셈블리 / 합법성 조건 텍스트
-    pub(crate) preferred_conditions: Vec<String>,
+    /// 선호 디스어셈블리 조건 - 하나라도 성립하면 에일리어스로 디스어셈블
+    pub(crate) preferred_conditions: Vec<AliasCondition>,
+}
+
+/// `<aliaspref labels="...">` 하나
+#[derive(Debug, Clone)]
+pub(crate) struct AliasCondition {
+    /// 적용 인코딩 라벨 (e.g., "32-bit"), 비어 있으면 모든 인코딩
+    pub(crate) label: String,
+    /// 원문 (e.g., "shift == '00' && imm6 == '000000'")
+    pub(crate) text: String,
+    pub(crate) preference: AliasPreference,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum AliasPreference {
+    Unconditionally,
+    Never,
+    When(asl::Expr),
+    /// 파싱 실패 메시지
+    Unparsed(String),
+}
+
+impl AliasCondition {
+    pub(crate) fn new(label: &str, text: &str) -> Self {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let preference = match text.as_str() {
+            "Unconditionally" => AliasPreference::Unconditionally,
+            "Never" => AliasPreference::Never,
+            _ => match asl::parse_expr(&text) {
+                Ok(expr) => AliasPreference::When(expr),
+                Err(failure) => AliasPreference::Unparsed(failure.message),
+            },
+        };
+        Self {
+            label: label.to_owned(),
+            text,
+            preference,
+        }
+    }
+
+    /// 인코딩 필드 값으로 조건 평가, 평가할 수 없으면 Err
+    pub(crate) fn holds(&self, fields: &dyn Fn(&str) -> Option<asl::Value>) -> Result<bool, String> {
+        match &self.preference {
+            AliasPreference::Unconditionally => Ok(true),
+            AliasPreference::Never => Ok(false),
+            AliasPreference::When(expr) => asl::eval_condition(expr, fields),
+            AliasPreference::Unparsed(message) => Err(message.clone()),
+        }
+    }
+
+    fn to_md(&self) -> String {
+        if self.label.is_empty() {
+            self.text.clone()
+        } else {
+            format!("[{}] {}", self.label, self.text)
+        }
+    }
+}
+
+/// 에일리어스 페이지가 가리키는 정규 인스트럭션
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ArmAliasTarget {
+    /// e.g., "ORR_log_shift"
+    pub(crate) id: String,
+    /// e.g., "ORR (shifted register)"
+    pub(crate) heading: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SharedKind {
+    #[default]
+    Function,
+    /// `X[]`, `Mem[]` 같은 읽기/쓰기 접근자
+    Accessor,
+    Constant,
+    Enumeration,
+    Type,
+}
+
+impl SharedKind {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            SharedKind::Function => "function",
+            SharedKind::Accessor => "accessor",
+            SharedKind::Constant => "constant",
+            SharedKind::Enumeration => "enumeration",
+            SharedKind::Type => "type",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct SharedFunction {
+    /// 정의 이름 (e.g., "AddWithCarry", "X")
+    pub(crate) name: String,
+    /// 정의 종류
+    pub(crate) kind: SharedKind,
+    /// 시그니처 (e.g., "(bits(N), bits(4)) AddWithCarry(bits(N) x, bits(N) y, bit carry_in)")
+    pub(crate) signature: String,
+    /// 시그니처 줄부터의 정의 본문
+    pub(crate) body: String,
+    /// 라이브러리 섹션 (e.g., "shared/functions/integer/AddWithCarry")
+    pub(crate) section: String,
+}
+
+/// shared_pseudocode.xml 정의 인덱스
+#[derive(Debug, Default, Clone)]
+pub(crate) struct SharedPseudocode {
+    pub(crate) functions: Vec<SharedFunction>,
+}
+
+impl SharedPseudocode {
+    /// 이름이 같은 모든 정의 (오버로드, 접근자 읽기/쓰기 형태)
+    pub(crate) fn find<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a SharedFunction> {
+        self.functions.iter().filter(move |f| f.name == name)
+    }
+
+    /// Markdown 인덱스로 변환: 이름순, 같은 이름은 원래 순서
+    pub(crate) fn to_md(&self) -> Vec<String> {
+        let mut functions: Vec<&SharedFunction> = self.functions.iter().collect();
+        functions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut result = vec!["# Shared Pseudocode".to_owned()];
+        for f in functions {
+            result.push("".to_owned());
+            result.push(format!("## {}", f.name));
+            result.push("".to_owned());
+            result.push(format!("- Kind: {}", f.kind.as_str()));
+            result.push(format!("- Section: `{}`", f.section));
+            result.push(format!("- Signature: `{}`", f.signature));
+            if matches!(f.kind, SharedKind::Function | SharedKind::Accessor) {
+                result.push("".to_owned());
+                result.push("```".to_owned());
+                result.push(f.body.clone());
+                result.push("```".to_owned());
+            }
+        }
+        result.push("".to_owned());
+        result
+    }
+}
+
+/// definition 값 표 한 행
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct OperandValue {
+    /// bitfield 열 값, `ValueTable::fields` 순서 (e.g., ["00", "1"]). `x`는 무관 비트
+    pub(crate) bits: Vec<String>,
+    /// 어셈블리 표기 (e.g., "LSL #12", "8B", "RESERVED")
+    pub(crate) symbol: String,
+    /// 필요 기능 (e.g., "ARMv8.5-MemTag")
+    pub(crate) feature: String,
+}
+
+/// definition 값 표 (`<table class="valuetable">`)
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ValueTable {
+    /// bitfield 열 머리글 (e.g., ["size", "Q"])
+    pub(crate) fields: Vec<String>,
+    /// symbol 열 머리글 (e.g., "<T>")
+    pub(crate) symbol: String,
+    pub(crate) rows: Vec<OperandValue>,
+}
+
+impl ValueTable {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// 표기 → 필드 값 (e.g., "LSL #12" → [("sh", "1")]). 대소문자 무시, 첫 번째 행
+    #[allow(dead_code)]
+    pub(crate) fn encode(&self, symbol: &str) -> Option<Vec<(&str, &str)>> {
+        let row = self
+            .rows
+            .iter()
+            .find(|row| row.symbol.eq_ignore_ascii_case(symbol.trim()))?;
+        Some(
+            self.fields
+                .iter()
+                .map(String::as_str)
+                .zip(row.bits.iter().map(String::as_str))
+                .collect(),
+        )
+    }
+
+    pub(crate) fn to_md_lines(&self) -> Vec<String> {
+        let has_feature = self.rows.iter().any(|row| !row.feature.is_empty());
+        let mut headers = self.fields.clone();
+        headers.push(format!("`{}`", self.symbol));
+        if has_feature {
+            headers.push("Feature".to_owned());
+        }
+        let table = MdTable {
+            headers,
+            rows: self
+                .rows
+                .iter()
+                .map(|row| {
+                    let mut cells = row.bits.clone();
+                    cells.resize(self.fields.len(), String::new());
+                    cells.push(row.symbol.clone());
+                    if has_feature {
+                        cells.push(row.feature.clone());
+                    }
+                    cells
+                })
+                .collect(),
+        };
+        table.to_md_lines()
+    }
+}
+
+/// 오퍼랜드 설명 하나 (`<explanation>`)
+#[derive(Debug, Default, Clone)]
+pub(crate) struct OperandExplanation {
+    /// e.g., "<shift>"
+    pub(crate) symbol: String,
+    /// e.g., "shift"
+    pub(crate) link: String,
+    /// 적용 인코딩 (`enclist`), 비어 있으면 전체
+    pub(crate) encodings: Vec<String>,
+    /// `encodedin` (e.g., "sh", "size:Q")
+    pub(crate) encoded_in: String,
+    pub(crate) description: String,
+    /// definition 값 표 (e.g., sh | <shift>: 0 → LSL #0)
+    pub(crate) values: ValueTable,
+}
+
+impl OperandExplanation {
+    /// `encodedin`의 필드 이름들 (e.g., "immh:immb" → ["immh", "immb"])
+    pub(crate) fn fields(&self) -> Vec<String> {
+        self.encoded_in
+            .split(':')
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .map(str::to_owned)
+            .collect()
+    }
+
+    pub(crate) fn applies_to(&self, encoding: &str) -> bool {
+        self.encodings.is_empty() || self.encodings.iter().any(|name| name == encoding)
+    }
+
+    /// 목록 항목 한 줄, 값 표가 있으면 들여쓴 표
+    fn to_md(&self) -> Vec<String> {
+        let mut line = format!("- `{}`", self.symbol);
+        if !self.encoded_in.is_empty() {
+            line.push_str(&format!(" (`{}`)", self.encoded_in));
+        }
+        if !self.description.is_empty() {
+            line.push_str(&format!(" — {}", self.description));
+        }
+        let mut result = vec![line];
+        if !self.values.is_empty() {
+            result.push("".to_owned());
+            let table = self.values.to_md_lines();
+            result.extend(table.into_iter().map(|row| format!("  {row}")));
+            result.push("".to_owned());
+        }
+        result
+    }
+}
+
+/// 시스템 레지스터 하나 (AArch64-*.xml `<register>`)
+#[derive(Debug, Default, Clone)]
+pub(crate) struct SystemRegister {
+    /// e.g., "SCTLR_EL1"
+    pub(crate) short_name: String,
+    /// e.g., "System Control Register (EL1)"
+    pub(crate) long_name: String,
+    /// 구현 조건 (`<reg_condition>`, e.g., "When FEAT_AA64EL2 is implemented")
+    pub(crate) condition: String,
+    pub(crate) requirement: ArchRequirement,
+    pub(crate) purpose: Vec<String>,
+    /// 필드 레이아웃. 조건에 따라 여러 개일 수 있음
+    pub(crate) fieldsets: Vec<RegisterFieldset>,
+    pub(crate) accessors: Vec<RegisterAccessor>,
+}
+
+/// `<fields>` 하나
+#[derive(Debug, Default, Clone)]
+pub(crate) struct RegisterFieldset {
+    /// 레이아웃 적용 조건 (e.g., "When FEAT_VHE is implemented and HCR_EL2.E2H == 1")
+    pub(crate) condition: String,
+    /// 레지스터 폭 (비트)
+    pub(crate) length: u32,
+    /// msb 내림차순
+    pub(crate) fields: Vec<RegisterField>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct RegisterField {
+    /// 이름, RES0/RES1 등 예약 필드는 비어 있음
+    pub(crate) name: String,
+    pub(crate) msb: u32,
+    pub(crate) lsb: u32,
+    /// 예약 필드 종류 (e.g., "RES0")
+    pub(crate) rwtype: String,
+    /// 리셋 값 (e.g., "0b0", "architecturally UNKNOWN")
+    pub(crate) reset: String,
+}
+
+/// MRS/MSR 등 레지스터 접근 방법
+#[derive(Debug, Default, Clone)]
+pub(crate) struct RegisterAccessor {
+    /// e.g., "MRS SCTLR_EL12" - 다른 이름으로 접근할 수 있음
+    pub(crate) accessor: String,
+    /// e.g., "MRS <Xt>, SCTLR_EL12"
+    pub(crate) instruction: String,
+    /// `<enc n="op0" v="0b11"/>` 목록
+    pub(crate) encoding: Vec<(String, String)>,
+    /// 접근 권한 의사코드
+    pub(crate) pseudocode: String,
+}
+
+impl RegisterAccessor {
+    /// 접근 인스트럭션의 레지스터 이름 (e.g., "MRS SCTLR_EL12" → "SCTLR_EL12")
+    pub(crate) fn register_name(&self) -> &str {
+        self.accessor
+            .split_whitespace()
+            .nth(1)
+            .unwrap_or(&self.accessor)
+    }
+
+    /// MSR (레지스터 쓰기) 이면 true
+    pub(crate) fn is_write(&self) -> bool {
+        self.instruction.starts_with("MSR")
+    }
+
+    /// MRS/MSR 인스트럭션 비트 [20:5] 값 `op0:op1:CRn:CRm:op2`. `x` 비트가 있으면 None
+    pub(crate) fn system_encoding(&self) -> Option<u16> {
+        let mut result = 0u16;
+        for (name, width) in [("op0", 2), ("op1", 3), ("CRn", 4), ("CRm", 4), ("op2", 3)] {
+            let (_, value) = self.encoding.iter().find(|(n, _)| n == name)?;
+            let bits = value.strip_prefix("0b").unwrap_or(value);
+            if bits.len() != width {
+                return None;
+            }
+            result = (result << width) | u16::from_str_radix(bits, 2).ok()?;
+        }
+        Some(result)
+    }
+}
+
+impl SystemRegister {
+    pub(crate) fn to_md(&self) -> Vec<String> {
+        let mut result = vec![format!("# {}", self.short_name)];
+        result.push("".to_owned());
+        result.push(self.long_name.clone());
+        if !self.condition.is_empty() || !self.requirement.is_empty() {
+            result.push("".to_owned());
+            if !self.condition.is_empty() {
+                result.push(format!("- Condition: {}", self.condition));
+            }
+            if !self.requirement.is_empty() {
+                result.push(format!("- Requires: {}", self.requirement.to_md()));
+            }
+        }
+        for para in &self.purpose {
+            result.push("".to_owned());
+            result.push(para.clone());
+        }
+
+        for fieldset in &self.fieldsets {
+            result.push("".to_owned());
+            if fieldset.condition.is_empty() {
+                result.push(format!("## Fields ({}-bit)", fieldset.length));
+            } else {
+                result.push(format!(
+                    "## Fields ({}-bit) — {}",
+                    fieldset.length, fieldset.condition
+                ));
+            }
+            result.push("".to_owned());
+            let table = MdTable {
+                headers: vec!["Bits".into(), "Name".into(), "Reset".into()],
+                rows: fieldset
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let bits = if field.msb == field.lsb {
+                            field.msb.to_string()
+                        } else {
+                            format!("{}:{}", field.msb, field.lsb)
+                        };
+                        let name = if field.name.is_empty() {
+                            field.rwtype.clone()
+                        } else {
+                            field.name.clone()
+                        };
+                        vec![bits, name, field.reset.clone()]
+                    })
+                    .collect(),
+            };
+            result.append(&mut table.to_md_lines());
+        }
+
+        if !self.accessors.is_empty() {
+            result.push("".to_owned());
+            result.push("## Accessors".to_owned());
+            for accessor in &self.accessors {
+                result.push("".to_owned());
+                result.push(format!("### {}", accessor.accessor));
+                result.push("".to_owned());
+                result.push(format!("- `{}`", accessor.instruction));
+                if !accessor.encoding.is_empty() {
+                    let encoding: Vec<String> = accessor
+                        .encoding
+                        .iter()
+                        .map(|(name, value)| format!("{name}={value}"))
+                        .collect();
+                    result.push(format!("- Encoding: `{}`", encoding.join(" ")));
+                }
+                if !accessor.pseudocode.is_empty() {
+                    result.push("".to_owned());
+                    result.push("```".to_owned());
+                    result.push(accessor.pseudocode.clone());
+                    result.push("```".to_owned());
+                }
+            }
+        }
+        result.push("".to_owned());
+        result
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -70,24 +796,32 @@ pub(crate) struct ArmInstruction {
     pub(crate) mnemonic: String,
     /// 인스트럭션 클래스 (e.g., "general", "advsimd", "sve")
     pub(crate) instr_class: String,
+    /// 명령어 집합 (e.g., "A64")
+    pub(crate) isa: String,
     /// 간단한 요약
     pub(crate) brief: String,
     /// 상세 설명
     pub(crate) description: Vec<String>,
     /// 인코딩 변형
     pub(crate) encodings: Vec<ArmEncoding>,
-    /// 인코딩 다이어그램 비트필드
+    /// 첫 iclass의 다이어그램 비트필드 (인코딩별은 `ArmEncoding::bitfields`)
     pub(crate) bitfields: Vec<BitField>,
-    /// 오퍼랜드 설명 (심볼, 설명)
-    pub(crate) operand_explanations: Vec<(String, String)>,
-    /// 디코드 의사코드
+    /// 오퍼랜드 설명
+    pub(crate) operand_explanations: Vec<OperandExplanation>,
+    /// 디코드 의사코드 (iclass가 여럿이면 `// iclass` 주석으로 구분해 합침)
     pub(crate) decode_pseudocode: String,
     /// 실행 의사코드
     pub(crate) operation: String,
     /// 에일리어스 참조와 선호 조건
     pub(crate) aliases: Vec<ArmAlias>,
+    /// 에일리어스 페이지이면 정규 인스트럭션
+    pub(crate) alias_to: Option<ArmAliasTarget>,
     /// 운영 노트
     pub(crate) operational_notes: Vec<String>,
+    /// 디코드/실행 의사코드가 참조하는 공유 함수 (이름순)
+    pub(crate) shared_functions: Vec<String>,
+    /// encodingindex 계층에서의 그룹 경로 (iclass마다 하나, 루트부터)
+    pub(crate) encoding_groups: Vec<Vec<String>>,
 }
 
 impl ArmInstruction {
@@ -95,24 +829,17 @@ impl ArmInstruction {
     /// e.g., "ADD (immediate)" + id "ADD_addsub_imm" → "ADD_immediate__ADD_addsub_imm"
     /// heading만으로는 충돌 가능하므로 항상 id를 접미사로 붙임
     pub(crate) fn filename_slug(&self) -> String {
-        let base = self
-            .heading
-            .replace('(', "")
-            .replace(')', "")
-            .replace(',', "")
-            .replace('/', "_")
-            .replace('.', "_")
-            .split_whitespace()
-            .collect::<Vec<_>>()
-            .join("_");
-        if base.is_empty() {
-            return self.id.clone();
-        }
-        if self.id.is_empty() {
-            return base;
-        }
-        // heading slug + XML id로 유일성 보장
-        format!("{base}__{}", self.id)
+        instruction_slug(&self.heading, &self.id)
+    }
+
+    /// 모든 인코딩에 공통인 아키텍처 요구 사항
+    pub(crate) fn requirement(&self) -> ArchRequirement {
+        ArchRequirement::common(self.encodings.iter().map(|enc| &enc.requirement))
+    }
+
+    /// A32/T32 인코딩이 있으면 AArch32 인스트럭션
+    pub(crate) fn is_aarch32(&self) -> bool {
+        self.encodings.iter().any(|enc| enc.isa.is_aarch32())
     }
 
     /// 인스트럭션 이름 반환 (니모닉)
@@ -120,6 +847,38 @@ impl ArmInstruction {
         self.mnemonic.clone()
     }
 
+    /// 디코드 의사코드 AST
+    pub(crate) fn parse_decode(&self) -> asl::ParsedPseudocode {
+        asl::parse(&self.decode_pseudocode)
+    }
+
+    /// 실행 의사코드 AST
+    pub(crate) fn parse_operation(&self) -> asl::ParsedPseudocode {
+        asl::parse(&self.operation)
+    }
+
+    /// `insn`을 `encoding`으로 디코드했을 때 선호되는 에일리어스
+    /// 같은 이름의 박스는 hibit 순서로 이어 붙임, 평가할 수 없는 조건은 성립하지 않는 것으로 취급
+    #[allow(dead_code)]
+    pub(crate) fn preferred_alias(&self, encoding: &ArmEncoding, insn: u32) -> Option<&ArmAlias> {
+        let fields = |name: &str| {
+            let (mut value, mut width) = (0u64, 0u32);
+            for bitfield in encoding.bitfields.iter().filter(|b| b.name == name) {
+                let mask = bitfield.mask();
+                value = (value << bitfield.width) | ((insn & mask) >> mask.trailing_zeros()) as u64;
+                width += bitfield.width as u32;
+            }
+            (width > 0).then(|| asl::Value::bits(value, width))
+        };
+        self.aliases.iter().find(|alias| {
+            alias
+                .preferred_conditions
+                .iter()
+                .filter(|condition| condition.label.is_empty() || condition.label == encoding.label)
+                .any(|condition| condition.holds(&fields).unwrap_or(false))
+        })
+    }
+
     /// Markdown으로 변환
     pub(crate) fn into_md(self) -> Vec<String> {
         let mut result = Vec::new();
@@ -131,24 +890,115 @@ impl ArmInstruction {
         result.push("".to_owned());
         result.push(self.brief.clone());
 
+        // 에일리어스 페이지: 정규 인스트럭션 링크
+        if let Some(target) = &self.alias_to {
+            result.push("".to_owned());
+            result.push(format!(
+                "Alias of [{}]({}.md)",
+                target.heading,
+                instruction_slug(&target.heading, &target.id)
+            ));
+        }
+
+        // 명령어 집합, 아키텍처 요구 사항
+        let requirement = self.requirement();
+        if !self.isa.is_empty() || !requirement.is_empty() {
+            result.push("".to_owned());
+            if !self.isa.is_empty() {
+                result.push(format!("- ISA: {}", self.isa));
+            }
+            if !requirement.is_empty() {
+                result.push(format!("- Requires: {}", requirement.to_md()));
+            }
+        }
+
         // 인코딩 변형 목록
         if !self.encodings.is_empty() {
             result.push("".to_owned());
             for enc in &self.encodings {
+                let pattern = if enc.isa == InstructionSet::T16 {
+                    format!(
+                        "`mask={:#06x} value={:#06x}`",
+                        enc.pattern.mask, enc.pattern.value
+                    )
+                } else {
+                    format!(
+                        "`mask={:#010x} value={:#010x}`",
+                        enc.pattern.mask, enc.pattern.value
+                    )
+                };
                 if !enc.label.is_empty() {
-                    result.push(format!("- `{}` — {}", enc.asm_template, enc.label));
+                    result.push(format!(
+                        "- `{}` — {} {pattern}",
+                        enc.asm_template, enc.label
+                    ));
                 } else {
-                    result.push(format!("- `{}`", enc.asm_template));
+                    result.push(format!("- `{}` {pattern}", enc.asm_template));
+                }
+                if enc.requirement != requirement {
+                    result.push(format!("  - Requires: {}", enc.requirement.to_md()));
+                }
+                if enc.isa.is_aarch32() {
+                    let mut line = format!("  - {} {}-bit", enc.isa.as_str(), enc.isa.width());
+                    if enc.is_conditional() {
+                        line.push_str(", conditional (`cond`)");
+                    }
+                    result.push(line);
+                }
+                if let Some(equivalent) = &enc.equivalent_to {
+                    let mut line = format!("  - Equivalent to `{}`", equivalent.asm_template);
+                    if !equivalent.condition.is_empty() {
+                        line.push_str(&format!(" — {}", equivalent.condition));
+                    }
+                    if !equivalent.substitutions.is_empty() {
+                        let substitutions: Vec<String> = equivalent
+                            .substitutions
+                            .iter()
+                            .map(|(field, bits)| format!("`{field} = {bits}`"))
+                            .collect();
+                        line.push_str(&format!(" ({})", substitutions.join(", ")));
+                    }
+                    result.push(line);
                 }
             }
         }
 
-        // 인코딩 다이어그램
-        if !self.bitfields.is_empty() {
+        // 디코드 계층 그룹 경로
+        if !self.encoding_groups.is_empty() {
+            result.push("".to_owned());
+            for path in &self.encoding_groups {
+                result.push(format!("- Group: {}", path.join(" / ")));
+            }
+        }
+
+        // 인코딩 다이어그램: iclass가 여럿이면 iclass별로
+        let iclasses = self.iclass_groups();
+        if iclasses.len() > 1 {
             result.push("".to_owned());
             result.push("## Encoding".to_owned());
+            for encodings in &iclasses {
+                result.push("".to_owned());
+                result.push(format!("### {}", encodings[0].iclass));
+                result.push("".to_owned());
+                result.append(&mut Self::diagram_to_md(
+                    &encodings[0].bitfields,
+                    encodings[0].isa,
+                ));
+                result.append(&mut Self::constraints_to_md(encodings));
+            }
+        } else if !self.bitfields.is_empty() {
             result.push("".to_owned());
-            result.append(&mut self.bitfields_to_md());
+            result.push("## Encoding".to_owned());
+            result.push("".to_owned());
+            let isa = self
+                .encodings
+                .first()
+                .map(|enc| enc.isa)
+                .unwrap_or_default();
+            result.append(&mut Self::diagram_to_md(&self.bitfields, isa));
+            if let Some(encodings) = iclasses.first() {
+                result.append(&mut Self::constraints_to_md(encodings));
+            }
         }
 
         // 상세 설명
@@ -166,8 +1016,12 @@ impl ArmInstruction {
             result.push("".to_owned());
             result.push("## Operands".to_owned());
             result.push("".to_owned());
-            for (sym, desc) in &self.operand_explanations {
-                result.push(format!("- `{sym}` — {desc}"));
+            for explanation in &self.operand_explanations {
+                result.append(&mut explanation.to_md());
+            }
+            // 마지막 값 표 뒤의 빈 줄
+            if result.last().is_some_and(String::is_empty) {
+                result.pop();
             }
         }
 
@@ -177,13 +1031,26 @@ impl ArmInstruction {
             result.push("## Aliases".to_owned());
             result.push("".to_owned());
             for alias in &self.aliases {
+                let name = if alias.id.is_empty() {
+                    alias.name.clone()
+                } else {
+                    format!(
+                        "[{}]({}.md)",
+                        alias.name,
+                        instruction_slug(&alias.name, &alias.id)
+                    )
+                };
                 if alias.preferred_conditions.is_empty() {
-                    result.push(format!("- {}", alias.name));
+                    result.push(format!("- {name}"));
                 } else {
                     result.push(format!(
-                        "- {} — Preferred when {}",
-                        alias.name,
-                        alias.preferred_conditions.join(" ; ")
+                        "- {name} — Preferred when {}",
+                        alias
+                            .preferred_conditions
+                            .iter()
+                            .map(AliasCondition::to_md)
+                            .collect::<Vec<_>>()
+                            .join(" ; ")
                     ));
                 }
             }
@@ -213,6 +1080,16 @@ impl ArmInstruction {
             result.push("```".to_owned());
         }
 
+        // 참조하는 공유 의사코드 함수
+        if !self.shared_functions.is_empty() {
+            result.push("".to_owned());
+            result.push("## Shared Pseudocode".to_owned());
+            result.push("".to_owned());
+            for name in &self.shared_functions {
+                result.push(format!("- `{name}`"));
+            }
+        }
+
         // 운영 노트
         if !self.operational_notes.is_empty() {
             result.push("".to_owned());
@@ -227,15 +1104,64 @@ impl ArmInstruction {
         result
     }
 
-    fn bitfields_to_md(&self) -> Vec<String> {
-        if self.bitfields.is_empty() {
+    /// 같은 iclass에 속한 연속된 인코딩끼리 묶음
+    fn iclass_groups(&self) -> Vec<&[ArmEncoding]> {
+        self.encodings
+            .chunk_by(|a, b| a.iclass == b.iclass)
+            .collect()
+    }
+
+    /// 인코딩별 필드 제약 목록
+    fn constraints_to_md(encodings: &[ArmEncoding]) -> Vec<String> {
+        let lines: Vec<String> = encodings
+            .iter()
+            .filter(|enc| !enc.constraints.is_empty())
+            .map(|enc| {
+                let constraints: Vec<String> = enc.constraints.iter().map(|c| c.to_md()).collect();
+                format!("- `{}`: `{}`", enc.name, constraints.join(" && "))
+            })
+            .collect();
+        if lines.is_empty() {
+            return lines;
+        }
+        let mut result = vec!["".to_owned()];
+        result.extend(lines);
+        result
+    }
+
+    /// T32 32비트 다이어그램은 하프워드별 표 (각각 비트 15:0)
+    fn diagram_to_md(bitfields: &[BitField], isa: InstructionSet) -> Vec<String> {
+        if isa != InstructionSet::T32 {
+            return Self::bitfields_to_md(bitfields);
+        }
+        let (hw1, hw2): (Vec<BitField>, Vec<BitField>) =
+            bitfields.iter().cloned().partition(|bf| bf.hibit >= 16);
+        let hw1: Vec<BitField> = hw1
+            .into_iter()
+            .map(|bf| BitField {
+                hibit: bf.hibit - 16,
+                ..bf
+            })
+            .collect();
+        let mut result = vec!["hw1".to_owned(), "".to_owned()];
+        result.append(&mut Self::bitfields_to_md(&hw1));
+        result.push("".to_owned());
+        result.push("hw2".to_owned());
+        result.push("".to_owned());
+        result.append(&mut Self::bitfields_to_md(&hw2));
+        result
+    }
+
+    fn bitfields_to_md(bitfields: &[BitField]) -> Vec<String> {
+        if bitfields.is_empty() {
             return Vec::new();
         }
 
-        // 헤더: 비트 위치
+        // 헤더: 비트 위치, 행: 필드 이름 / 고정 비트
         let mut headers = Vec::new();
+        let mut names = Vec::new();
         let mut values = Vec::new();
-        for bf in &self.bitfields {
+        for bf in bitfields {
             let label = if bf.width == 1 {
                 format!("{}", bf.hibit)
             } else {
@@ -247,19 +1173,43 @@ impl ArmInstruction {
             };
             headers.push(label);
 
-            if !bf.name.is_empty() && bf.constants.is_empty() {
-                values.push(bf.name.clone());
-            } else if !bf.constants.is_empty() {
-                values.push(bf.constants.join(""));
-            } else {
-                values.push("".to_owned());
-            }
+            names.push(bf.name.clone());
+            values.push(
+                bf.constants
+                    .iter()
+                    .map(|c| c.trim())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .trim()
+                    .to_owned(),
+            );
         }
 
         let table = MdTable {
             headers,
-            rows: vec![values],
+            rows: vec![names, values],
         };
         table.to_md_lines()
     }
 }
+
+/// heading + id 기반 파일명 슬러그. 에일리어스 링크도 같은 규칙으로 생성
+pub(crate) fn instruction_slug(heading: &str, id: &str) -> String {
+    let base = heading
+        .replace('(', "")
+        .replace(')', "")
+        .replace(',', "")
+        .replace('/', "_")
+        .replace('.', "_")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("_");
+    if base.is_empty() {
+        return id.to_owned();
+    }
+    if id.is_empty() {
+        return base;
+    }
+    // heading slug + XML id로 유일성 보장
+    format!("{base}__{id}")
+}
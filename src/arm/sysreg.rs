@@ -0,0 +1,120 @@
+use super::result::SystemRegister;
+
+const SYSREG_PRELUDE: &str = r#"//! AArch64 시스템 레지스터 조회 — ARM 시스템 레지스터 XML에서 자동 생성됨
+
+/// MRS/MSR 인스트럭션 비트 [20:5] (`op0:op1:CRn:CRm:op2`)로 레지스터 이름 조회.
+/// 같은 인코딩이 읽기/쓰기에서 다른 레지스터일 수 있음 (e.g., DBGDTRRX_EL0 / DBGDTRTX_EL0)
+pub fn system_register(encoding: u16, write: bool) -> Option<&'static str> {
+    let start = SYSTEM_REGISTERS.partition_point(|&(e, w, _)| (e, w) < (encoding, write));
+    SYSTEM_REGISTERS
+        .get(start)
+        .filter(|&&(e, w, _)| e == encoding && w == write)
+        .map(|&(_, _, name)| name)
+}
+
+/// MRS/MSR 인스트럭션 워드에서 레지스터 이름 조회
+pub fn system_register_of(insn: u32) -> Option<&'static str> {
+    // L (bit 21): 1이면 MRS
+    system_register(((insn >> 5) & 0xFFFF) as u16, insn & (1 << 21) == 0)
+}
+
+"#;
+
+/// (인코딩, 쓰기 여부, 접근 이름) 목록: 정렬, 중복 제거
+fn sysreg_entries(registers: &[SystemRegister]) -> Vec<(u16, bool, String)> {
+    let mut entries: Vec<(u16, bool, String)> = registers
+        .iter()
+        .flat_map(|register| &register.accessors)
+        .filter(|accessor| {
+            accessor.instruction.starts_with("MRS") || accessor.instruction.starts_with("MSR")
+        })
+        .filter_map(|accessor| {
+            Some((
+                accessor.system_encoding()?,
+                accessor.is_write(),
+                accessor.register_name().to_owned(),
+            ))
+        })
+        .collect();
+    entries.sort();
+    // 같은 (인코딩, 방향)에 이름이 여럿이면 첫 번째 (e.g., ICC_*/ICV_*)
+    entries.dedup_by(|b, a| a.0 == b.0 && a.1 == b.1);
+    entries
+}
+
+/// 인코딩 → 레지스터 이름 조회 모듈 소스
+pub(crate) fn sysreg_lookup_to_rust(registers: &[SystemRegister]) -> String {
+    let entries = sysreg_entries(registers);
+    let mut result = vec![SYSREG_PRELUDE.to_owned()];
+    result.push("/// (인코딩, 쓰기 여부, 이름) — 인코딩, 쓰기 여부 순 정렬".to_owned());
+    result.push(format!(
+        "pub static SYSTEM_REGISTERS: [(u16, bool, &str); {}] = [",
+        entries.len()
+    ));
+    for (encoding, write, name) in &entries {
+        result.push(format!("    ({encoding:#06x}, {write}, \"{name}\"),"));
+    }
+    result.push("];".to_owned());
+    result.push(String::new());
+    result.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arm::result::RegisterAccessor;
+
+    fn accessor(accessor: &str, instruction: &str, encoding: [&str; 5]) -> RegisterAccessor {
+        RegisterAccessor {
+            accessor: accessor.to_owned(),
+            instruction: instruction.to_owned(),
+            encoding: ["op0", "op1", "CRn", "CRm", "op2"]
+                .into_iter()
+                .zip(encoding)
+                .map(|(name, value)| (name.to_owned(), value.to_owned()))
+                .collect(),
+            pseudocode: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_sysreg_entries() {
+        let sctlr = ["0b11", "0b000", "0b0001", "0b0000", "0b000"];
+        let registers = vec![
+            SystemRegister {
+                short_name: "SCTLR_EL1".to_owned(),
+                accessors: vec![
+                    accessor("MRS SCTLR_EL1", "MRS <Xt>, SCTLR_EL1", sctlr),
+                    accessor("MSRregister SCTLR_EL1", "MSR SCTLR_EL1, <Xt>", sctlr),
+                    accessor(
+                        "MRS SCTLR_EL12",
+                        "MRS <Xt>, SCTLR_EL12",
+                        ["0b11", "0b101", "0b0001", "0b0000", "0b000"],
+                    ),
+                ],
+                ..Default::default()
+            },
+            SystemRegister {
+                short_name: "PAN".to_owned(),
+                accessors: vec![accessor(
+                    "MSRimmediate PAN",
+                    "MSR PAN, #<imm>",
+                    ["0b00", "0b000", "0b0100", "0bxxxx", "0b100"],
+                )],
+                ..Default::default()
+            },
+        ];
+        let entries = sysreg_entries(&registers);
+        assert_eq!(
+            entries,
+            vec![
+                (0xC080, false, "SCTLR_EL1".to_owned()),
+                (0xC080, true, "SCTLR_EL1".to_owned()),
+                (0xE880, false, "SCTLR_EL12".to_owned()),
+            ]
+        );
+        let source = sysreg_lookup_to_rust(&registers);
+        assert!(source.contains("pub static SYSTEM_REGISTERS: [(u16, bool, &str); 3] = ["));
+        assert!(source.contains("    (0xc080, true, \"SCTLR_EL1\"),"));
+    }
+}
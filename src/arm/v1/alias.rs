@@ -0,0 +1,225 @@
+use crate::arm::result::{ArmAlias, ArmEncoding, ArmInstruction};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// 에일리어스 페이지 ↔ 정규 인스트럭션 양방향 연결, 필드 치환 계산
+pub(crate) fn link_aliases(instructions: &mut [ArmInstruction]) {
+    let ids: HashMap<String, usize> = instructions
+        .iter()
+        .enumerate()
+        .map(|(i, instr)| (instr.id.clone(), i))
+        .collect();
+
+    // (에일리어스, 정규 인스트럭션) 인덱스 쌍
+    let mut links = Vec::new();
+    for (alias_index, alias) in instructions.iter().enumerate() {
+        let Some(target) = &alias.alias_to else {
+            continue;
+        };
+        match ids.get(&target.id) {
+            Some(&target_index) => links.push((alias_index, target_index)),
+            None => warn!("{}: 정규 인스트럭션 {} 없음", alias.id, target.id),
+        }
+    }
+
+    for (alias_index, target_index) in links {
+        let substitutions: Vec<Vec<(String, String)>> = instructions[alias_index]
+            .encodings
+            .iter()
+            .map(|enc| {
+                let target = enc.equivalent_to.as_ref().and_then(|equivalent| {
+                    instructions[target_index]
+                        .encodings
+                        .iter()
+                        .find(|target| target.name == equivalent.encoding)
+                });
+                target.map_or_else(Vec::new, |target| substitutions(enc, target))
+            })
+            .collect();
+
+        let target_heading = instructions[target_index].heading.clone();
+        let alias = &mut instructions[alias_index];
+        for (enc, substitutions) in alias.encodings.iter_mut().zip(substitutions) {
+            if let Some(equivalent) = &mut enc.equivalent_to {
+                equivalent.substitutions = substitutions;
+            }
+        }
+        let (id, heading) = (alias.id.clone(), alias.heading.clone());
+        if let Some(target) = &mut alias.alias_to {
+            target.heading = target_heading;
+        }
+
+        // 정규 인스트럭션 쪽 alias_list에 없으면 추가, 링크 이름은 에일리어스 heading으로 통일
+        let aliases = &mut instructions[target_index].aliases;
+        match aliases.iter_mut().find(|alias| alias.id == id) {
+            Some(existing) => existing.name = heading,
+            None => aliases.push(ArmAlias {
+                id,
+                name: heading,
+                preferred_conditions: Vec::new(),
+            }),
+        }
+    }
+}
+
+/// 에일리어스 인코딩이 고정하지만 정규 인코딩에서는 오퍼랜드인 필드 (e.g., MOV의 Rn = 11111)
+fn substitutions(alias: &ArmEncoding, target: &ArmEncoding) -> Vec<(String, String)> {
+    let mut result: Vec<(String, String)> = Vec::new();
+    for bitfield in &alias.bitfields {
+        let mask = bitfield.mask();
+        if bitfield.name.is_empty()
+            || alias.pattern.mask & mask != mask
+            || target.pattern.mask & mask == mask
+            || result.iter().any(|(name, _)| *name == bitfield.name)
+        {
+            continue;
+        }
+        let bits: String = (0..bitfield.width)
+            .map(|i| {
+                let position = bitfield.hibit - i;
+                if alias.pattern.value & (1 << position) != 0 {
+                    '1'
+                } else {
+                    '0'
+                }
+            })
+            .collect();
+        result.push((bitfield.name.clone(), bits));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arm::result::{
+        AliasCondition, ArmAliasTarget, ArmEquivalent, BitField, EncodingPattern,
+    };
+
+    fn bitfield(hibit: u8, width: u8, name: &str) -> BitField {
+        BitField {
+            hibit,
+            width,
+            name: name.to_owned(),
+            constants: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_link_aliases() {
+        let diagram = vec![
+            bitfield(31, 1, "sf"),
+            bitfield(20, 5, "Rm"),
+            bitfield(15, 6, "imm6"),
+            bitfield(9, 5, "Rn"),
+            bitfield(4, 5, "Rd"),
+        ];
+        let orr = ArmInstruction {
+            id: "ORR_log_shift".to_owned(),
+            heading: "ORR (shifted register)".to_owned(),
+            encodings: vec![ArmEncoding {
+                name: "ORR_32_log_shift".to_owned(),
+                bitfields: diagram.clone(),
+                pattern: EncodingPattern {
+                    mask: 0xFF20_0000,
+                    value: 0x2A00_0000,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mov = ArmInstruction {
+            id: "MOV_ORR_log_shift".to_owned(),
+            heading: "MOV (register)".to_owned(),
+            alias_to: Some(ArmAliasTarget {
+                id: "ORR_log_shift".to_owned(),
+                heading: String::new(),
+            }),
+            encodings: vec![ArmEncoding {
+                name: "MOV_ORR_32_log_shift".to_owned(),
+                bitfields: diagram,
+                pattern: EncodingPattern {
+                    mask: 0xFF20_FFE0,
+                    value: 0x2A00_03E0,
+                    ..Default::default()
+                },
+                equivalent_to: Some(ArmEquivalent {
+                    encoding: "ORR_32_log_shift".to_owned(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut instructions = vec![mov, orr];
+        link_aliases(&mut instructions);
+
+        let equivalent = instructions[0].encodings[0].equivalent_to.as_ref().unwrap();
+        assert_eq!(
+            equivalent.substitutions,
+            vec![
+                ("imm6".to_owned(), "000000".to_owned()),
+                ("Rn".to_owned(), "11111".to_owned())
+            ]
+        );
+        assert_eq!(
+            instructions[0].alias_to.as_ref().unwrap().heading,
+            "ORR (shifted register)"
+        );
+        assert_eq!(instructions[1].aliases.len(), 1);
+        assert_eq!(instructions[1].aliases[0].id, "MOV_ORR_log_shift");
+        assert_eq!(instructions[1].aliases[0].name, "MOV (register)");
+    }
+
+    #[test]
+    fn test_preferred_alias() {
+        let encoding = ArmEncoding {
+            name: "UBFM_32M_bitfield".to_owned(),
+            label: "32-bit".to_owned(),
+            bitfields: vec![
+                bitfield(31, 1, "sf"),
+                bitfield(30, 2, "opc"),
+                bitfield(22, 1, "N"),
+                bitfield(21, 6, "immr"),
+                bitfield(15, 6, "imms"),
+                bitfield(9, 5, "Rn"),
+                bitfield(4, 5, "Rd"),
+            ],
+            ..Default::default()
+        };
+        let alias = |id: &str, conditions: &[(&str, &str)]| ArmAlias {
+            id: id.to_owned(),
+            name: id.to_owned(),
+            preferred_conditions: conditions
+                .iter()
+                .map(|(label, text)| AliasCondition::new(label, text))
+                .collect(),
+        };
+        let ubfm = ArmInstruction {
+            aliases: vec![
+                alias(
+                    "LSL_UBFM",
+                    &[
+                        ("32-bit", "imms != '011111' && imms + 1 == immr"),
+                        ("64-bit", "imms != '111111' && imms + 1 == immr"),
+                    ],
+                ),
+                alias("LSR_UBFM", &[("32-bit", "imms == '011111'")]),
+                alias("UBFX_UBFM", &[("", "BFXPreferred(sf, opc<1>, imms, immr)")]),
+                alias("UXTB_UBFM", &[("", "immr == '000000' && imms == '000111'")]),
+            ],
+            ..Default::default()
+        };
+        let preferred = |insn: u32| ubfm.preferred_alias(&encoding, insn).map(|a| a.id.as_str());
+
+        // lsl w0, w1, #4 = ubfm w0, w1, #28, #27
+        assert_eq!(preferred(0x531C_6C20), Some("LSL_UBFM"));
+        // lsr w0, w1, #4 = ubfm w0, w1, #4, #31
+        assert_eq!(preferred(0x5304_7C20), Some("LSR_UBFM"));
+        // ubfx w0, w1, #4, #8 = ubfm w0, w1, #4, #11
+        assert_eq!(preferred(0x5304_2C20), Some("UBFX_UBFM"));
+        // uxtb w0, w1 = ubfm w0, w1, #0, #7
+        assert_eq!(preferred(0x5300_1C20), Some("UXTB_UBFM"));
+    }
+}
@@ -0,0 +1,235 @@
+use crate::arm::result::{BitField, EncodingPattern, FieldConstraint};
+
+/// 다이어그램 한 비트의 제약
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bit {
+    Any,
+    Fixed(bool),
+    /// `(0)`/`(1)` — 다르면 CONSTRAINED UNPREDICTABLE
+    ShouldBe(bool),
+}
+
+/// 박스의 `<c>` 셀들을 상위 비트부터 비트 제약으로 전개.
+/// 두 번째 값은 박스 전체에 걸린 `!=` 제약 (박스 비트 폭과 같은 길이의 비트 문자열)
+fn box_bits(bitfield: &BitField) -> (Vec<Bit>, Option<String>) {
+    let width = bitfield.width as usize;
+    let mut bits = Vec::new();
+    let mut not_equal = None;
+    for cell in &bitfield.constants {
+        let cell = cell.trim();
+        if let Some(rest) = cell.strip_prefix("!=") {
+            let value: String = rest.chars().filter(|c| !c.is_whitespace()).collect();
+            let start = bits.len();
+            bits.extend(std::iter::repeat_n(Bit::Any, value.len()));
+            // 셀이 박스 일부만 덮는 경우는 앞뒤를 x로 채움
+            let mut pattern = "x".repeat(start) + &value;
+            pattern.push_str(&"x".repeat(width.saturating_sub(pattern.len())));
+            not_equal = Some(pattern);
+            continue;
+        }
+        if cell.is_empty() {
+            bits.push(Bit::Any);
+            continue;
+        }
+        let mut chars = cell.chars().peekable();
+        while let Some(c) = chars.next() {
+            let bit = match c {
+                '(' => {
+                    let bit = Bit::ShouldBe(chars.next() == Some('1'));
+                    chars.next_if_eq(&')');
+                    bit
+                }
+                '0' => Bit::Fixed(false),
+                '1' => Bit::Fixed(true),
+                // 인코딩별 박스의 `z`: should-be-zero (FCMP #0.0의 Rm)
+                'z' => Bit::ShouldBe(false),
+                // `N`: 필드 전체가 1이면 안 됨 (Rm != 11111)
+                'N' => {
+                    not_equal = Some("1".repeat(width));
+                    Bit::Any
+                }
+                _ => Bit::Any,
+            };
+            bits.push(bit);
+        }
+    }
+    bits.resize(width, Bit::Any);
+    (bits, not_equal)
+}
+
+/// iclass 다이어그램에 인코딩별 박스를 덮어써서 32비트 mask/value 계산
+pub(super) fn encoding_pattern(diagram: &[BitField], overrides: &[BitField]) -> EncodingPattern {
+    let mut bits = [Bit::Any; 32];
+    // (hibit, width, 비트 문자열)
+    let mut not_equal: Vec<(u8, u8, String)> = Vec::new();
+    for (index, bitfield) in diagram.iter().chain(overrides).enumerate() {
+        let is_override = index >= diagram.len();
+        let hibit = bitfield.hibit.min(31) as usize;
+        let (box_bits, box_not_equal) = box_bits(bitfield);
+        for (i, bit) in box_bits.into_iter().enumerate() {
+            // 인코딩별 박스의 빈 셀은 iclass에서 이미 고정된 비트 — 그대로 둠
+            if is_override && bit == Bit::Any {
+                continue;
+            }
+            if let Some(position) = hibit.checked_sub(i) {
+                bits[position] = bit;
+            }
+        }
+        // 덮어쓴 박스에 걸려 있던 `!=` 제약은 제거
+        let low = bitfield
+            .hibit
+            .saturating_sub(bitfield.width.saturating_sub(1));
+        not_equal.retain(|(hi, width, _)| {
+            let other_low = hi.saturating_sub(width.saturating_sub(1));
+            *hi < low || other_low > bitfield.hibit
+        });
+        if let Some(value) = box_not_equal {
+            not_equal.push((bitfield.hibit, bitfield.width, value));
+        }
+    }
+
+    let mut pattern = EncodingPattern::default();
+    for (position, bit) in bits.iter().enumerate() {
+        let flag = 1u32 << position;
+        match bit {
+            Bit::Any => {}
+            Bit::Fixed(value) => {
+                pattern.mask |= flag;
+                pattern.value |= if *value { flag } else { 0 };
+            }
+            Bit::ShouldBe(value) => {
+                pattern.should_be_mask |= flag;
+                pattern.should_be_value |= if *value { flag } else { 0 };
+            }
+        }
+    }
+    for (hibit, _, value) in not_equal {
+        let (mut mask, mut bits) = (0u32, 0u32);
+        for (i, c) in value.chars().enumerate() {
+            let Some(position) = (hibit as usize).checked_sub(i) else {
+                break;
+            };
+            match c {
+                '0' => mask |= 1 << position,
+                '1' => {
+                    mask |= 1 << position;
+                    bits |= 1 << position;
+                }
+                _ => {}
+            }
+        }
+        pattern.not_equal.push((mask, bits));
+    }
+    pattern
+}
+
+/// `<encoding bitdiffs="sf == 0 && Rm != 11111">` 파싱
+pub(super) fn parse_bitdiffs(bitdiffs: &str) -> Vec<FieldConstraint> {
+    bitdiffs
+        .split("&&")
+        .filter_map(|term| {
+            let (field, equal, value) = if let Some((field, value)) = term.split_once("==") {
+                (field, true, value)
+            } else {
+                let (field, value) = term.split_once("!=")?;
+                (field, false, value)
+            };
+            Some(FieldConstraint {
+                field: field.trim().to_owned(),
+                equal,
+                value: value.trim().to_owned(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitfield(hibit: u8, width: u8, name: &str, constants: &[&str]) -> BitField {
+        BitField {
+            hibit,
+            width,
+            name: name.to_owned(),
+            constants: constants.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_encoding_pattern_add_imm() {
+        // ADD (immediate), 32-bit: sf == 0
+        let diagram = [
+            bitfield(31, 1, "sf", &[""]),
+            bitfield(30, 1, "op", &["0"]),
+            bitfield(29, 1, "S", &["0"]),
+            bitfield(28, 6, "", &["1", "0", "0", "0", "1", "0"]),
+            bitfield(22, 1, "sh", &[""]),
+            bitfield(21, 12, "imm12", &[""; 12]),
+            bitfield(9, 5, "Rn", &[""; 5]),
+            bitfield(4, 5, "Rd", &[""; 5]),
+        ];
+        let pattern = encoding_pattern(&diagram, &[bitfield(31, 1, "sf", &["0"])]);
+        assert_eq!(pattern.mask, 0xFF80_0000);
+        assert_eq!(pattern.value, 0x1100_0000);
+        assert_eq!(pattern.should_be_mask, 0);
+        assert!(pattern.not_equal.is_empty());
+
+        // LD1 (multiple structures): iclass가 고정한 opcode<1>은 인코딩 박스에서 빈 셀
+        let diagram = [bitfield(15, 4, "opcode", &["x", "x", "1", "x"])];
+        let pattern =
+            encoding_pattern(&diagram, &[bitfield(15, 4, "opcode", &["0", "1", "", "1"])]);
+        assert_eq!(pattern.mask, 0xF000);
+        assert_eq!(pattern.value, 0x7000);
+    }
+
+    #[test]
+    fn test_bitfield_mask() {
+        assert_eq!(bitfield(4, 5, "Rd", &[]).mask(), 0x1F);
+        assert_eq!(bitfield(21, 12, "imm12", &[]).mask(), 0x003F_FC00);
+        assert_eq!(bitfield(31, 32, "", &[]).mask(), u32::MAX);
+    }
+
+    #[test]
+    fn test_parse_bitdiffs() {
+        assert_eq!(
+            parse_bitdiffs("sf == 1 && Rm != 11111"),
+            vec![
+                FieldConstraint {
+                    field: "sf".to_owned(),
+                    equal: true,
+                    value: "1".to_owned(),
+                },
+                FieldConstraint {
+                    field: "Rm".to_owned(),
+                    equal: false,
+                    value: "11111".to_owned(),
+                },
+            ]
+        );
+        assert!(parse_bitdiffs("").is_empty());
+    }
+
+    #[test]
+    fn test_encoding_pattern_constraints() {
+        let diagram = [
+            bitfield(31, 4, "cond", &["!= 1111"]),
+            bitfield(27, 3, "", &["0x", ""]),
+            bitfield(24, 2, "", &["(1)", "(0)"]),
+            bitfield(20, 5, "Rm", &[""; 5]),
+        ];
+        let pattern = encoding_pattern(&diagram, &[]);
+        assert_eq!(pattern.mask, 0x0800_0000);
+        assert_eq!(pattern.value, 0);
+        assert_eq!(pattern.should_be_mask, 0x0180_0000);
+        assert_eq!(pattern.should_be_value, 0x0100_0000);
+        assert_eq!(pattern.not_equal, vec![(0xF000_0000, 0xF000_0000)]);
+
+        // 인코딩별 박스: Rm != 11111
+        let pattern = encoding_pattern(&diagram, &[bitfield(20, 5, "Rm", &["N"; 5])]);
+        assert_eq!(
+            pattern.not_equal,
+            vec![(0xF000_0000, 0xF000_0000), (0x001F_0000, 0x001F_0000)]
+        );
+    }
+}
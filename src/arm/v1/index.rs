@@ -0,0 +1,276 @@
+use super::encoding::encoding_pattern;
+use super::{get_attr, push_cell};
+use crate::arm::result::{ArmInstruction, BitField, EncodingGroup, EncodingIndex};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use tracing::{debug, warn};
+
+/// 디코드 계층이 들어 있는 인덱스 파일인지 (e.g., "encodingindex.xml", "t32_encindex.xml")
+pub(crate) fn is_encoding_index_file(filename: &str) -> bool {
+    filename == "encodingindex.xml" || filename.ends_with("_encindex.xml")
+}
+
+/// 파싱 중인 `<node>`: 그룹과 루트부터 누적한 decode 박스
+struct Frame {
+    group: EncodingGroup,
+    boxes: Vec<BitField>,
+}
+
+/// `<hierarchy>`의 `<node>` 트리와 `<instructiontable>`의 iclass → 인스트럭션 id 파싱
+pub(crate) fn parse_encoding_index(data: &[u8]) -> EncodingIndex {
+    let mut reader = Reader::from_reader(data);
+    reader.config_mut().trim_text(false);
+
+    let mut index = EncodingIndex::default();
+    let mut buf = Vec::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut in_hierarchy = false;
+    let mut in_header = false;
+    let mut in_decode = false;
+    let mut in_c = false;
+    let mut current_box = BitField::default();
+    let mut current_c_text = String::new();
+    let mut current_c_colspan = 1;
+    let mut decode_boxes: Vec<BitField> = Vec::new();
+    // instructiontable: iclass → 인스트럭션 id (문서 순서, 중복 제거)
+    let mut current_table = String::new();
+    let mut tables: HashMap<String, Vec<String>> = HashMap::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                b"encodingindex" => {
+                    index.instruction_set = get_attr(e, "instructionset").unwrap_or_default();
+                }
+                b"hierarchy" => in_hierarchy = true,
+                b"node" if in_hierarchy => {
+                    let boxes = stack.last().map(|f| f.boxes.clone()).unwrap_or_default();
+                    stack.push(Frame {
+                        group: EncodingGroup {
+                            id: get_attr(e, "groupname")
+                                .or_else(|| get_attr(e, "iclass"))
+                                .unwrap_or_default(),
+                            unallocated: get_attr(e, "unallocated").as_deref() == Some("1"),
+                            ..Default::default()
+                        },
+                        boxes,
+                    });
+                }
+                b"header" if !stack.is_empty() => in_header = true,
+                b"decode" if !stack.is_empty() => {
+                    in_decode = true;
+                    decode_boxes.clear();
+                }
+                b"box" if in_decode => {
+                    current_box = BitField {
+                        hibit: get_attr(e, "hibit")
+                            .and_then(|hibit| hibit.parse().ok())
+                            .unwrap_or(0),
+                        width: get_attr(e, "width")
+                            .and_then(|width| width.parse().ok())
+                            .unwrap_or(1),
+                        name: get_attr(e, "name").unwrap_or_default(),
+                        constants: Vec::new(),
+                    };
+                }
+                b"c" if in_decode => {
+                    in_c = true;
+                    current_c_text.clear();
+                    current_c_colspan = get_attr(e, "colspan")
+                        .and_then(|colspan| colspan.parse().ok())
+                        .unwrap_or(1);
+                }
+                b"instructiontable" => {
+                    current_table = get_attr(e, "iclass").unwrap_or_default();
+                }
+                b"td" if !current_table.is_empty() => {
+                    if let Some(id) = get_attr(e, "iformid") {
+                        let ids = tables.entry(current_table.clone()).or_default();
+                        if !ids.contains(&id) {
+                            ids.push(id);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(ref e)) => match e.name().as_ref() {
+                b"hierarchy" => in_hierarchy = false,
+                b"node" if in_hierarchy => {
+                    let Some(frame) = stack.pop() else {
+                        continue;
+                    };
+                    match stack.last_mut() {
+                        Some(parent) => parent.group.children.push(frame.group),
+                        None => index.groups.push(frame.group),
+                    }
+                }
+                b"header" => in_header = false,
+                b"decode" if in_decode => {
+                    in_decode = false;
+                    if let Some(frame) = stack.last_mut() {
+                        frame.boxes.append(&mut decode_boxes);
+                        frame.group.pattern = encoding_pattern(&frame.boxes, &[]);
+                    }
+                }
+                b"c" if in_c => {
+                    in_c = false;
+                    push_cell(&mut current_box, &current_c_text, current_c_colspan);
+                }
+                b"box" if in_decode => decode_boxes.push(current_box.clone()),
+                b"instructiontable" => current_table.clear(),
+                _ => {}
+            },
+            Ok(Event::Empty(ref e)) => match e.name().as_ref() {
+                b"c" if in_decode => {
+                    let colspan = get_attr(e, "colspan")
+                        .and_then(|colspan| colspan.parse().ok())
+                        .unwrap_or(1);
+                    push_cell(&mut current_box, "", colspan);
+                }
+                _ => {}
+            },
+            Ok(Event::Text(ref e)) => {
+                let text = e.unescape().unwrap_or_default();
+                if in_c {
+                    current_c_text.push_str(&text);
+                } else if in_header {
+                    if let Some(frame) = stack.last_mut() {
+                        frame.group.header.push_str(&text);
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("인코딩 인덱스 XML 파싱 오류: {}", e);
+                break;
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    for group in &mut index.groups {
+        assign_instructions(group, &mut tables);
+    }
+    debug!(
+        "Parsed {} encoding index with {} top-level groups",
+        index.instruction_set,
+        index.groups.len()
+    );
+    index
+}
+
+/// iclass 리프에 instructiontable의 인스트럭션 id 배정, header 공백 정리
+fn assign_instructions(group: &mut EncodingGroup, tables: &mut HashMap<String, Vec<String>>) {
+    group.header = group
+        .header
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if group.children.is_empty() {
+        group.instructions = tables.remove(&group.id).unwrap_or_default();
+    }
+    for child in &mut group.children {
+        assign_instructions(child, tables);
+    }
+}
+
+/// 각 인스트럭션에 소속 그룹 경로 기록
+pub(crate) fn link_encoding_groups(instructions: &mut [ArmInstruction], indexes: &[EncodingIndex]) {
+    let mut paths: HashMap<String, Vec<Vec<String>>> = HashMap::new();
+    for index in indexes {
+        for (id, mut instruction_paths) in index.instruction_paths() {
+            paths.entry(id).or_default().append(&mut instruction_paths);
+        }
+    }
+    for instr in instructions {
+        instr.encoding_groups = paths.remove(&instr.id).unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_encoding_index() {
+        let xml = br#"<encodingindex instructionset="A64">
+  <hierarchy>
+    <node iclass="unallocate1" unallocated="1">
+      <header>UNALLOCATED</header>
+      <decode>
+        <box hibit="28" width="5" name="op0" usename="1"><c colspan="5">00011</c></box>
+      </decode>
+    </node>
+    <node groupname="dpimm">
+      <header>Data Processing --
+        Immediate</header>
+      <decode>
+        <box hibit="28" width="5" name="op0" usename="1"><c colspan="5">100xx</c></box>
+      </decode>
+      <regdiagram form="32">
+        <box hibit="25" width="3" name="op0" usename="1"><c colspan="3" /></box>
+      </regdiagram>
+      <node iclass="addsub_imm">
+        <header>Add/subtract (immediate)</header>
+        <decode>
+          <box hibit="25" width="3" name="op0" usename="1"><c colspan="3">010</c></box>
+        </decode>
+      </node>
+    </node>
+  </hierarchy>
+  <iclass_sect id="addsub_imm" title="Add/subtract (immediate)">
+    <instructiontable iclass="addsub_imm" cols="5">
+      <tbody>
+        <tr class="instructiontable" encname="ADD_32_addsub_imm" iformfile="add_addsub_imm.xml">
+          <td bitwidth="1" class="bitfield">0</td>
+          <td class="iformname" iformid="ADD_addsub_imm">ADD (immediate)</td>
+        </tr>
+        <tr class="instructiontable" encname="ADD_64_addsub_imm" iformfile="add_addsub_imm.xml">
+          <td bitwidth="1" class="bitfield">1</td>
+          <td class="iformname" iformid="ADD_addsub_imm">ADD (immediate)</td>
+        </tr>
+        <tr class="instructiontable" encname="SUB_32_addsub_imm" iformfile="sub_addsub_imm.xml">
+          <td bitwidth="1" class="bitfield">0</td>
+          <td class="iformname" iformid="SUB_addsub_imm">SUB (immediate)</td>
+        </tr>
+      </tbody>
+    </instructiontable>
+  </iclass_sect>
+</encodingindex>"#;
+        let index = parse_encoding_index(xml);
+        assert_eq!(index.instruction_set, "A64");
+        assert_eq!(index.groups.len(), 2);
+
+        let unallocated = &index.groups[0];
+        assert!(unallocated.unallocated);
+        assert_eq!(unallocated.pattern.mask, 0x1F00_0000);
+        assert_eq!(unallocated.pattern.value, 0x0300_0000);
+
+        let dpimm = &index.groups[1];
+        assert_eq!(dpimm.header, "Data Processing -- Immediate");
+        assert_eq!(dpimm.pattern.mask, 0x1C00_0000);
+        assert_eq!(dpimm.children.len(), 1);
+        let addsub = &dpimm.children[0];
+        assert_eq!(addsub.pattern.mask, 0x1F80_0000);
+        assert_eq!(addsub.pattern.value, 0x1100_0000);
+        assert_eq!(
+            addsub.instructions,
+            vec!["ADD_addsub_imm", "SUB_addsub_imm"]
+        );
+
+        let mut instructions = vec![ArmInstruction {
+            id: "SUB_addsub_imm".to_owned(),
+            ..Default::default()
+        }];
+        link_encoding_groups(&mut instructions, &[index]);
+        assert_eq!(
+            instructions[0].encoding_groups,
+            vec![vec![
+                "Data Processing -- Immediate".to_owned(),
+                "Add/subtract (immediate)".to_owned()
+            ]]
+        );
+    }
+}
@@ -1,9 +1,25 @@
-use super::result::{ArmAlias, ArmEncoding, ArmInstruction, BitField};
+use super::result::{
+    AliasCondition, ArchRequirement, ArmAlias, ArmAliasTarget, ArmEncoding, ArmEquivalent,
+    ArmInstruction, BitField, InstructionSet, OperandExplanation, OperandValue,
+};
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
 use std::collections::HashMap;
+use template::TemplatePart;
 use tracing::{debug, warn};
 
+mod alias;
+mod encoding;
+mod index;
+mod shared;
+mod sysreg;
+mod template;
+
+pub(crate) use alias::link_aliases;
+pub(crate) use index::{is_encoding_index_file, link_encoding_groups, parse_encoding_index};
+pub(crate) use shared::{link_shared_functions, parse_shared_pseudocode, SHARED_PSEUDOCODE_FILE};
+pub(crate) use sysreg::{is_sysreg_file, parse_system_registers};
+
 /// 모든 XML 파일을 파싱하여 ArmInstruction 벡터 반환
 pub(crate) fn parse_all_instructions(xml_files: HashMap<String, Vec<u8>>) -> Vec<ArmInstruction> {
     let mut instructions = Vec::new();
@@ -11,6 +27,15 @@ pub(crate) fn parse_all_instructions(xml_files: HashMap<String, Vec<u8>>) -> Vec
     filenames.sort();
 
     for filename in filenames {
+        // 공유 의사코드 라이브러리는 parse_shared_pseudocode에서 처리
+        // 인코딩 인덱스는 parse_encoding_index에서 처리
+        // 시스템 레지스터는 parse_system_registers에서 처리
+        if filename == SHARED_PSEUDOCODE_FILE
+            || is_encoding_index_file(&filename)
+            || is_sysreg_file(&filename)
+        {
+            continue;
+        }
         let data = &xml_files[&filename];
         match parse_instruction_xml(data) {
             Some(instr) => {
@@ -19,7 +44,7 @@ pub(crate) fn parse_all_instructions(xml_files: HashMap<String, Vec<u8>>) -> Vec
                 }
             }
             None => {
-                // 에일리어스이거나 파싱 불가 — 스킵
+                // 파싱 불가 — 스킵
             }
         }
     }
@@ -28,7 +53,7 @@ pub(crate) fn parse_all_instructions(xml_files: HashMap<String, Vec<u8>>) -> Vec
     instructions
 }
 
-/// 단일 XML 파일 파싱. 에일리어스면 None 반환.
+/// 단일 XML 파일 파싱. 에일리어스 페이지는 `alias_to`가 채워진 엔트리로 반환
 fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
     let mut reader = Reader::from_reader(data);
     // trim_text를 끄면 공백이 보존됨
@@ -65,9 +90,16 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
     let mut in_aliasref_text = false;
     let mut in_aliaspref = false;
     let mut current_alias_name = String::new();
-    let mut current_alias_conditions: Vec<String> = Vec::new();
+    let mut current_alias_id = String::new();
+    let mut current_alias_conditions: Vec<AliasCondition> = Vec::new();
     let mut current_aliaspref_text = String::new();
     let mut current_aliaspref_labels = String::new();
+    // 에일리어스 페이지: <aliasto>, 인코딩별 <equivalent_to>
+    let mut alias_mnemonic = String::new();
+    let mut in_aliasto = false;
+    let mut in_equivalent_to = false;
+    let mut in_aliascond = false;
+    let mut current_equivalent = ArmEquivalent::default();
     let mut in_para = false;
     let mut in_list = false;
     let mut in_listitem = false;
@@ -76,39 +108,60 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
     let mut current_encoding = ArmEncoding::default();
     let mut current_box = BitField::default();
     let mut bitfields_captured = false;
+    // 비트 상수 셀과 현재 iclass 다이어그램 / 인코딩별 박스
+    let mut in_c = false;
+    let mut current_c_text = String::new();
+    let mut current_c_colspan = 1;
+    let mut current_diagram: Vec<BitField> = Vec::new();
+    // iclass <arch_variants>, 인코딩 <arch_variants>는 여기에 더해짐
+    let mut iclass_requirement = ArchRequirement::default();
+    let mut encoding_boxes: Vec<BitField> = Vec::new();
     let mut pstext_section = String::new();
-    let mut current_symbol = String::new();
+    let mut current_explanation = OperandExplanation::default();
     let mut current_explanation_text = String::new();
-    let mut asm_template_parts: Vec<String> = Vec::new();
+    let mut asm_template_parts: Vec<TemplatePart> = Vec::new();
+    // <asmtemplate> 안의 <a>: (link, hover)
+    let mut template_anchor: Option<(String, String)> = None;
     let mut para_text = String::new();
     let mut content_text = String::new();
     // 중첩 ps_section 깊이 (classes 내부 vs 최상위)
     let mut ps_section_depth = 0;
     // iclass별 decode pseudocode 분리
     let mut current_iclass_name = String::new();
+    // iclass `isa` (A64, A32, T32)과 regdiagram `form`으로 정한 명령어 집합
+    let mut iclass_isa = String::new();
+    let mut current_isa = InstructionSet::default();
     let mut decode_sections: Vec<(String, String)> = Vec::new();
     let mut current_decode = String::new();
+    // 현재 iclass의 첫 인코딩 인덱스 (iclass 끝에서 디코드 의사코드 배정)
+    let mut iclass_first_encoding = 0;
     // definition table 파싱
-    let mut def_table_rows: Vec<Vec<String>> = Vec::new();
-    let mut def_table_current_row: Vec<String> = Vec::new();
+    let mut def_table_rows: Vec<OperandValue> = Vec::new();
+    // (entry class, 텍스트)
+    let mut def_table_current_row: Vec<(String, String)> = Vec::new();
+    let mut def_table_entry_class = String::new();
     let mut def_table_entry_text = String::new();
     // operational notes 중복 방지
     let mut operational_notes_set: std::collections::HashSet<String> =
         std::collections::HashSet::new();
     // heading 텍스트
     let mut heading_text = String::new();
+    // 루트 요소 확인 여부
+    let mut root_checked = false;
 
     loop {
         match reader.read_event_into(&mut buf) {
             Ok(Event::Eof) => break,
             Ok(Event::Start(ref e)) => {
+                // onebigfile.xml(<allinstrs>) 같은 묶음·색인 파일은 명령어가 아님
+                if !root_checked {
+                    root_checked = true;
+                    if e.name().as_ref() != b"instructionsection" {
+                        return None;
+                    }
+                }
                 match e.name().as_ref() {
                     b"instructionsection" => {
-                        // type="alias" 이면 스킵
-                        let type_val = get_attr(e, "type").unwrap_or_default();
-                        if type_val == "alias" {
-                            return None;
-                        }
                         instr.id = get_attr(e, "id").unwrap_or_default();
                         in_instructionsection = true;
                     }
@@ -121,7 +174,9 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                         let val = get_attr(e, "value").unwrap_or_default();
                         match key.as_str() {
                             "mnemonic" => instr.mnemonic = val,
+                            "alias_mnemonic" => alias_mnemonic = val,
                             "instr-class" => instr.instr_class = val,
+                            "isa" => instr.isa = val,
                             _ => {}
                         }
                     }
@@ -142,6 +197,7 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                     b"alias_list" if in_instructionsection => in_alias_list = true,
                     b"aliasref" if in_alias_list => {
                         in_aliasref = true;
+                        current_alias_id = get_attr(e, "aliaspageid").unwrap_or_default();
                         current_alias_name.clear();
                         current_alias_conditions.clear();
                     }
@@ -153,14 +209,35 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                         current_aliaspref_text.clear();
                         current_aliaspref_labels = get_attr(e, "labels").unwrap_or_default();
                     }
+                    b"aliasto" if in_instructionsection => {
+                        in_aliasto = true;
+                        instr.alias_to = Some(ArmAliasTarget {
+                            id: get_attr(e, "iformid").unwrap_or_default(),
+                            heading: String::new(),
+                        });
+                    }
                     b"classes" if in_instructionsection => in_classes = true,
                     b"iclass" if in_classes => {
                         in_iclass = true;
                         current_iclass_name = get_attr(e, "name").unwrap_or_default();
+                        iclass_isa = get_attr(e, "isa").unwrap_or_default();
                         current_decode.clear();
+                        iclass_first_encoding = instr.encodings.len();
+                        // SVE 인스트럭션에는 <arch_variants>가 없음
+                        iclass_requirement = ArchRequirement::default();
+                        if instr.instr_class == "sve" {
+                            iclass_requirement.add("ARMv8.2", "SVE");
+                        }
                     }
-                    b"regdiagram" if in_iclass && !bitfields_captured => in_regdiagram = true,
-                    b"box" if in_regdiagram => {
+                    b"regdiagram" if in_iclass => {
+                        in_regdiagram = true;
+                        current_diagram.clear();
+                        current_isa = InstructionSet::from_xml(
+                            &iclass_isa,
+                            &get_attr(e, "form").unwrap_or_default(),
+                        );
+                    }
+                    b"box" if in_regdiagram || in_encoding => {
                         current_box = BitField::default();
                         current_box.hibit = get_attr(e, "hibit")
                             .unwrap_or_default()
@@ -172,19 +249,49 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                             .unwrap_or(1);
                         current_box.name = get_attr(e, "name").unwrap_or_default();
                     }
-                    b"c" if in_regdiagram => {}
+                    b"c" if in_regdiagram || in_encoding => {
+                        in_c = true;
+                        current_c_text.clear();
+                        current_c_colspan = get_attr(e, "colspan")
+                            .and_then(|colspan| colspan.parse().ok())
+                            .unwrap_or(1);
+                    }
                     b"encoding" if in_iclass => {
                         in_encoding = true;
+                        encoding_boxes.clear();
                         current_encoding = ArmEncoding::default();
                         current_encoding.name = get_attr(e, "name").unwrap_or_default();
                         current_encoding.label = get_attr(e, "label").unwrap_or_default();
+                        current_encoding.iclass = current_iclass_name.clone();
+                        current_encoding.requirement = iclass_requirement.clone();
+                        current_encoding.isa = current_isa;
+                        current_encoding.constraints =
+                            encoding::parse_bitdiffs(&get_attr(e, "bitdiffs").unwrap_or_default());
                     }
                     b"asmtemplate" if in_encoding => {
                         in_asmtemplate = true;
                         asm_template_parts.clear();
                     }
+                    b"equivalent_to" if in_encoding => {
+                        in_equivalent_to = true;
+                        current_equivalent = ArmEquivalent::default();
+                    }
+                    b"aliascond" if in_equivalent_to => in_aliascond = true,
                     b"a" if in_asmtemplate => {
+                        template_anchor = Some((
+                            get_attr(e, "link").unwrap_or_default(),
+                            get_attr(e, "hover").unwrap_or_default(),
+                        ));
                         // <a> 태그 내부 텍스트도 수집 — 공백 보존됨
+                        // equivalent_to의 정규 인코딩 링크: "orr_log_shift.xml#ORR_32_log_shift"
+                        if in_equivalent_to {
+                            if let Some((_, encoding)) = get_attr(e, "href")
+                                .as_deref()
+                                .and_then(|href| href.split_once('#'))
+                            {
+                                current_equivalent.encoding = encoding.to_owned();
+                            }
+                        }
                     }
                     b"ps_section" if in_instructionsection => {
                         ps_section_depth += 1;
@@ -200,11 +307,25 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                     b"explanations" if in_instructionsection => in_explanations = true,
                     b"explanation" if in_explanations => {
                         in_explanation = true;
-                        current_symbol.clear();
+                        current_explanation = OperandExplanation {
+                            encodings: get_attr(e, "enclist")
+                                .unwrap_or_default()
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|name| !name.is_empty())
+                                .map(str::to_owned)
+                                .collect(),
+                            ..Default::default()
+                        };
                         current_explanation_text.clear();
                     }
                     b"symbol" if in_explanation => {
                         in_symbol = true;
+                        current_explanation.link = get_attr(e, "link").unwrap_or_default();
+                    }
+                    b"account" if in_explanation => {
+                        current_explanation.encoded_in =
+                            get_attr(e, "encodedin").unwrap_or_default();
                     }
                     b"intro" if in_explanation => {
                         if in_explanations {
@@ -213,6 +334,8 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                     }
                     b"definition" if in_explanation => {
                         in_definition_intro = true;
+                        current_explanation.encoded_in =
+                            get_attr(e, "encodedin").unwrap_or_default();
                     }
                     b"table" if in_definition_intro => {
                         in_def_table = true;
@@ -227,6 +350,7 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                     }
                     b"entry" if in_def_table_row => {
                         in_def_table_entry = true;
+                        def_table_entry_class = get_attr(e, "class").unwrap_or_default();
                         def_table_entry_text.clear();
                     }
                     _ => {}
@@ -288,6 +412,7 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                         let alias_name = current_alias_name.trim();
                         if !alias_name.is_empty() {
                             instr.aliases.push(ArmAlias {
+                                id: current_alias_id.clone(),
                                 name: alias_name.to_owned(),
                                 preferred_conditions: current_alias_conditions.clone(),
                             });
@@ -298,25 +423,24 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                     }
                     b"text" if in_aliasref => in_aliasref_text = false,
                     b"aliaspref" => {
-                        let condition = current_aliaspref_text.trim();
-                        if !condition.is_empty() {
-                            if current_aliaspref_labels.is_empty() {
-                                current_alias_conditions.push(condition.to_owned());
-                            } else {
-                                current_alias_conditions.push(format!(
-                                    "[{}] {}",
-                                    current_aliaspref_labels, condition
-                                ));
-                            }
+                        if !current_aliaspref_text.trim().is_empty() {
+                            current_alias_conditions.push(AliasCondition::new(
+                                &current_aliaspref_labels,
+                                &current_aliaspref_text,
+                            ));
                         }
                         current_aliaspref_text.clear();
                         current_aliaspref_labels.clear();
                         in_aliaspref = false;
                     }
+                    b"aliasto" => in_aliasto = false,
                     b"classes" => in_classes = false,
                     b"iclass" => {
                         // iclass 끝: decode pseudocode 저장
                         let trimmed = current_decode.trim().to_owned();
+                        for enc in &mut instr.encodings[iclass_first_encoding..] {
+                            enc.decode_pseudocode = trimmed.clone();
+                        }
                         if !trimmed.is_empty() {
                             decode_sections.push((current_iclass_name.clone(), trimmed));
                         }
@@ -328,20 +452,50 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                             bitfields_captured = true;
                         }
                     }
+                    b"c" if in_c => {
+                        in_c = false;
+                        push_cell(&mut current_box, &current_c_text, current_c_colspan);
+                    }
                     b"box" if in_regdiagram => {
-                        instr.bitfields.push(current_box.clone());
+                        current_diagram.push(current_box.clone());
+                        if !bitfields_captured {
+                            instr.bitfields.push(current_box.clone());
+                        }
+                    }
+                    b"box" if in_encoding => {
+                        encoding_boxes.push(current_box.clone());
                     }
                     b"encoding" if in_iclass => {
                         in_encoding = false;
+                        current_encoding.pattern =
+                            encoding::encoding_pattern(&current_diagram, &encoding_boxes);
+                        current_encoding.bitfields = current_diagram.clone();
                         if !current_encoding.asm_template.is_empty() {
                             instr.encodings.push(current_encoding.clone());
                         }
                     }
                     b"asmtemplate" => {
                         in_asmtemplate = false;
-                        current_encoding.asm_template =
-                            asm_template_parts.join("").trim().to_owned();
+                        let template: String = asm_template_parts
+                            .iter()
+                            .map(|(text, _)| text.as_str())
+                            .collect();
+                        if in_equivalent_to {
+                            current_equivalent.asm_template = template.trim().to_owned();
+                        } else {
+                            current_encoding.asm_template = template.trim().to_owned();
+                            current_encoding.template =
+                                template::parse_asm_template(&asm_template_parts);
+                        }
                     }
+                    b"equivalent_to" => {
+                        in_equivalent_to = false;
+                        current_equivalent.condition =
+                            normalize_whitespace(&current_equivalent.condition);
+                        current_encoding.equivalent_to = Some(current_equivalent.clone());
+                    }
+                    b"aliascond" => in_aliascond = false,
+                    b"a" if in_asmtemplate => template_anchor = None,
                     b"ps_section" => {
                         ps_section_depth -= 1;
                         if ps_section_depth == 0 {
@@ -353,12 +507,18 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                     }
                     b"explanations" => in_explanations = false,
                     b"explanation" => {
-                        if !current_symbol.is_empty() && !current_explanation_text.is_empty() {
-                            // 공백 정규화: 모든 연속 공백/개행을 단일 공백으로
-                            let normalized = normalize_whitespace(&current_explanation_text);
+                        // 공백 정규화: 모든 연속 공백/개행을 단일 공백으로
+                        current_explanation.symbol =
+                            current_explanation.symbol.trim().to_owned();
+                        current_explanation.description =
+                            normalize_whitespace(&current_explanation_text);
+                        if !current_explanation.symbol.is_empty()
+                            && (!current_explanation.description.is_empty()
+                                || !current_explanation.values.rows.is_empty())
+                        {
                             instr
                                 .operand_explanations
-                                .push((current_symbol.trim().to_owned(), normalized));
+                                .push(std::mem::take(&mut current_explanation));
                         }
                         in_explanation = false;
                         in_account_intro = false;
@@ -372,25 +532,42 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                         in_definition_intro = false;
                     }
                     b"table" if in_def_table => {
-                        // definition table 완료 — 텍스트로 변환 (단일행, 개행 없이)
-                        if !def_table_rows.is_empty() {
-                            let table_text = format_def_table(&def_table_rows);
-                            if !current_explanation_text.is_empty() {
-                                current_explanation_text.push(' ');
-                            }
-                            current_explanation_text.push_str(&table_text);
-                        }
+                        // definition 값 표 완료
+                        current_explanation
+                            .values
+                            .rows
+                            .append(&mut def_table_rows);
                         in_def_table = false;
                     }
                     b"thead" => in_def_table_thead = false,
                     b"row" if in_def_table => {
-                        if !def_table_current_row.is_empty() && !in_def_table_thead {
-                            def_table_rows.push(def_table_current_row.clone());
+                        let column = |class: &str| -> Vec<String> {
+                            def_table_current_row
+                                .iter()
+                                .filter(|(c, _)| c == class)
+                                .map(|(_, text)| text.clone())
+                                .collect()
+                        };
+                        if def_table_current_row.is_empty() {
+                            // 빈 행
+                        } else if !in_def_table_thead {
+                            def_table_rows.push(OperandValue {
+                                bits: column("bitfield"),
+                                symbol: column("symbol").into_iter().next().unwrap_or_default(),
+                                feature: column("feature").join(", "),
+                            });
+                        } else if current_explanation.values.fields.is_empty() {
+                            let values = &mut current_explanation.values;
+                            values.fields = column("bitfield");
+                            values.symbol = column("symbol").into_iter().next().unwrap_or_default();
                         }
                         in_def_table_row = false;
                     }
                     b"entry" if in_def_table_entry => {
-                        def_table_current_row.push(def_table_entry_text.trim().to_owned());
+                        def_table_current_row.push((
+                            std::mem::take(&mut def_table_entry_class),
+                            normalize_whitespace(&def_table_entry_text),
+                        ));
                         in_def_table_entry = false;
                     }
                     _ => {}
@@ -407,6 +584,20 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                     heading_text.push_str(&text);
                 }
 
+                if in_aliasto {
+                    if let Some(target) = &mut instr.alias_to {
+                        target.heading.push_str(&text);
+                    }
+                }
+
+                if in_aliascond {
+                    current_equivalent.condition.push_str(&text);
+                }
+
+                if in_c {
+                    current_c_text.push_str(&text);
+                }
+
                 if in_para {
                     para_text.push_str(&text);
                 } else if in_content && in_operationalnotes {
@@ -418,7 +609,7 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                 }
 
                 if in_asmtemplate {
-                    asm_template_parts.push(text.clone());
+                    asm_template_parts.push((text.clone(), template_anchor.clone()));
                 }
 
                 if in_pstext {
@@ -445,7 +636,7 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                 }
 
                 if in_symbol {
-                    current_symbol.push_str(&text);
+                    current_explanation.symbol.push_str(&text);
                 }
 
                 if (in_account_intro || in_definition_intro) && !in_def_table {
@@ -470,14 +661,50 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
                         let val = get_attr(e, "value").unwrap_or_default();
                         match key.as_str() {
                             "mnemonic" if instr.mnemonic.is_empty() => instr.mnemonic = val,
+                            "alias_mnemonic" if alias_mnemonic.is_empty() => alias_mnemonic = val,
                             "instr-class" if instr.instr_class.is_empty() => {
                                 instr.instr_class = val
                             }
+                            "isa" if instr.isa.is_empty() => instr.isa = val,
                             _ => {}
                         }
                     }
-                    b"c" if in_regdiagram => {
-                        // 비트 상수
+                    // iclass에 isa 속성이 없으면 iclass docvar 사용
+                    b"docvar" if in_iclass && !in_encoding && iclass_isa.is_empty() => {
+                        if get_attr(e, "key").as_deref() == Some("isa") {
+                            iclass_isa = get_attr(e, "value").unwrap_or_default();
+                        }
+                    }
+                    b"entry" if in_def_table_row => {
+                        def_table_current_row
+                            .push((get_attr(e, "class").unwrap_or_default(), String::new()));
+                    }
+                    // 값 표의 feature 열 (e.g., "ARMv8.5-MemTag")
+                    b"arch_variant" if in_def_table_entry => {
+                        let feature = get_attr(e, "feature")
+                            .or_else(|| get_attr(e, "name"))
+                            .unwrap_or_default();
+                        if !def_table_entry_text.trim().is_empty() {
+                            def_table_entry_text.push_str(", ");
+                        }
+                        def_table_entry_text.push_str(&feature);
+                    }
+                    b"c" if in_regdiagram || in_encoding => {
+                        let colspan = get_attr(e, "colspan")
+                            .and_then(|colspan| colspan.parse().ok())
+                            .unwrap_or(1);
+                        push_cell(&mut current_box, "", colspan);
+                    }
+                    b"arch_variant" if in_iclass => {
+                        let requirement = if in_encoding {
+                            &mut current_encoding.requirement
+                        } else {
+                            &mut iclass_requirement
+                        };
+                        requirement.add(
+                            &get_attr(e, "name").unwrap_or_default(),
+                            &get_attr(e, "feature").unwrap_or_default(),
+                        );
                     }
                     _ => {}
                 }
@@ -496,6 +723,25 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
         return None;
     }
 
+    // 에일리어스 페이지의 mnemonic 은 정규 인스트럭션 것 — 에일리어스 니모닉 사용
+    if !alias_mnemonic.is_empty() {
+        instr.mnemonic = alias_mnemonic;
+    }
+    if let Some(target) = &mut instr.alias_to {
+        target.heading = target.heading.trim().to_owned();
+    }
+
+    // AArch32 페이지는 명령어 집합이 iclass에만 있음 (e.g., "A32, T32")
+    if instr.isa.is_empty() {
+        let mut isas: Vec<&str> = Vec::new();
+        for enc in &instr.encodings {
+            if !isas.contains(&enc.isa.as_str()) {
+                isas.push(enc.isa.as_str());
+            }
+        }
+        instr.isa = isas.join(", ");
+    }
+
     // mnemonic이 비어있으면 heading에서 첫 번째 단어 추출
     if instr.mnemonic.is_empty() {
         let first = instr
@@ -533,6 +779,9 @@ fn parse_instruction_xml(data: &[u8]) -> Option<ArmInstruction> {
         }
     }
 
+    // <explanations>는 인코딩 뒤에 나오므로 마지막에 연결
+    template::link_template_operands(&mut instr);
+
     Some(instr)
 }
 
@@ -541,17 +790,16 @@ fn normalize_whitespace(s: &str) -> String {
     s.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
-/// definition 내부 table을 텍스트로 변환
-fn format_def_table(rows: &[Vec<String>]) -> String {
-    let mut result = Vec::new();
-    for row in rows {
-        if row.len() >= 2 {
-            result.push(format!("{} → {}", row[0], row[1]));
-        } else if row.len() == 1 {
-            result.push(row[0].clone());
-        }
+/// `<c>` 셀 하나 추가: 빈 셀은 비트 폭만큼 "" 로 펼침
+fn push_cell(bitfield: &mut BitField, text: &str, colspan: usize) {
+    let text = text.trim();
+    if text.is_empty() {
+        bitfield
+            .constants
+            .extend(std::iter::repeat_n(String::new(), colspan));
+    } else {
+        bitfield.constants.push(text.to_owned());
     }
-    result.join(", ")
 }
 
 /// XML 요소에서 속성 값 추출
@@ -565,3 +813,192 @@ fn get_attr(e: &BytesStart, name: &str) -> Option<String> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_value_table() {
+        let xml = br#"<instructionsection id="DC_SYS" title="DC" type="alias">
+  <heading>DC</heading>
+  <explanations scope="all">
+    <explanation enclist="DC_SYS_CR_systeminstrs" symboldefcount="1">
+      <symbol link="dc_op">&lt;dc_op&gt;</symbol>
+      <definition encodedin="op1:CRm:op2">
+        <intro>Is a DC instruction name, as listed for the DC system instruction group, </intro>
+        <table class="valuetable">
+          <tgroup cols="5">
+            <thead>
+              <row>
+                <entry class="bitfield">op1</entry>
+                <entry class="bitfield">CRm</entry>
+                <entry class="bitfield">op2</entry>
+                <entry class="symbol">&lt;dc_op&gt;</entry>
+                <entry class="symbol">Architectural Feature</entry>
+              </row>
+            </thead>
+            <tbody>
+              <row>
+                <entry class="bitfield">000</entry>
+                <entry class="bitfield">0110</entry>
+                <entry class="bitfield">001</entry>
+                <entry class="symbol">IVAC</entry>
+                <entry class="feature" />
+              </row>
+              <row>
+                <entry class="bitfield">000</entry>
+                <entry class="bitfield">0110</entry>
+                <entry class="bitfield">011</entry>
+                <entry class="symbol">IGVAC</entry>
+                <entry class="feature">
+                  <arch_variants>
+                    <arch_variant feature="ARMv8.5-MemTag" />
+                  </arch_variants>
+                </entry>
+              </row>
+            </tbody>
+          </tgroup>
+        </table>
+      </definition>
+    </explanation>
+  </explanations>
+</instructionsection>"#;
+        let instr = parse_instruction_xml(xml).unwrap();
+        let values = &instr.operand_explanations[0].values;
+        assert_eq!(values.fields, vec!["op1", "CRm", "op2"]);
+        assert_eq!(values.symbol, "<dc_op>");
+        assert_eq!(
+            values.rows[1],
+            OperandValue {
+                bits: vec!["000".to_owned(), "0110".to_owned(), "011".to_owned()],
+                symbol: "IGVAC".to_owned(),
+                feature: "ARMv8.5-MemTag".to_owned(),
+            }
+        );
+        assert_eq!(values.rows[0].feature, "");
+        assert_eq!(
+            values.encode("ivac"),
+            Some(vec![("op1", "000"), ("CRm", "0110"), ("op2", "001")])
+        );
+        assert_eq!(values.encode("CIVAC"), None);
+
+        let md = instr.into_md();
+        let header = md
+            .iter()
+            .position(|line| line == "  | op1 | CRm | op2 | `<dc_op>` | Feature |")
+            .unwrap();
+        assert_eq!(md[header + 2], "  | 000 | 0110 | 001 | IVAC |  |");
+        assert_eq!(
+            md[header + 3],
+            "  | 000 | 0110 | 011 | IGVAC | ARMv8.5-MemTag |"
+        );
+    }
+
+    #[test]
+    fn test_parse_aarch32_instruction_sets() {
+        let xml = br#"<instructionsection id="ADD_i" title="ADD, ADDS (immediate) -- AArch32" type="instruction">
+  <docvars>
+    <docvar key="instr-class" value="general" />
+    <docvar key="mnemonic" value="ADD" />
+  </docvars>
+  <heading>ADD, ADDS (immediate)</heading>
+  <classes>
+    <iclass name="A1" oneof="3" id="a1" no_encodings="1" isa="A32">
+      <docvars><docvar key="isa" value="A32" /></docvars>
+      <regdiagram form="32" psname="aarch32/instrs/ADD_i/ADD_i_A1.txt">
+        <box hibit="31" width="4" name="cond" usename="1"><c colspan="4">!= 1111</c></box>
+        <box hibit="27" width="3" settings="3"><c>0</c><c>0</c><c>1</c></box>
+        <box hibit="24" width="4" settings="4"><c>0</c><c>1</c><c>0</c><c>0</c></box>
+        <box hibit="20" name="S" usename="1"><c></c></box>
+        <box hibit="19" width="4" name="Rn" usename="1"><c colspan="4"></c></box>
+        <box hibit="15" width="4" name="Rd" usename="1"><c colspan="4"></c></box>
+        <box hibit="11" width="12" name="imm12" usename="1"><c colspan="12"></c></box>
+      </regdiagram>
+      <encoding name="ADD_i_A1" oneofinclass="1" oneof="3" label="A1">
+        <asmtemplate><text>ADD</text><text>{</text><a link="c">&lt;c&gt;</a><text>}</text><text> </text><a link="rd">&lt;Rd&gt;</a><text>, #</text><a link="const">&lt;const&gt;</a></asmtemplate>
+      </encoding>
+    </iclass>
+    <iclass name="T1" oneof="3" id="t1" no_encodings="1">
+      <docvars><docvar key="isa" value="T32" /></docvars>
+      <regdiagram form="16" psname="aarch32/instrs/ADD_i/ADD_i_T1.txt">
+        <box hibit="15" width="7" settings="7"><c>0</c><c>0</c><c>0</c><c>1</c><c>1</c><c>1</c><c>0</c></box>
+        <box hibit="8" width="3" name="imm3" usename="1"><c colspan="3"></c></box>
+        <box hibit="5" width="3" name="Rn" usename="1"><c colspan="3"></c></box>
+        <box hibit="2" width="3" name="Rd" usename="1"><c colspan="3"></c></box>
+      </regdiagram>
+      <encoding name="ADD_i_T1" oneofinclass="1" oneof="3" label="T1">
+        <asmtemplate><text>ADDS </text><a link="rd">&lt;Rd&gt;</a><text>, #</text><a link="imm3">&lt;imm3&gt;</a></asmtemplate>
+      </encoding>
+    </iclass>
+    <iclass name="T3" oneof="3" id="t3" no_encodings="1" isa="T32">
+      <regdiagram form="16x2" psname="aarch32/instrs/ADD_i/ADD_i_T3.txt" tworows="1">
+        <box hibit="31" width="5" settings="5"><c>1</c><c>1</c><c>1</c><c>1</c><c>0</c></box>
+        <box hibit="26" name="i" usename="1"><c></c></box>
+        <box hibit="25" settings="1"><c>0</c></box>
+        <box hibit="24" width="4" settings="4"><c>1</c><c>0</c><c>0</c><c>0</c></box>
+        <box hibit="20" name="S" usename="1"><c></c></box>
+        <box hibit="19" width="4" name="Rn" usename="1"><c colspan="4"></c></box>
+        <box hibit="15" settings="1"><c>0</c></box>
+        <box hibit="14" width="3" name="imm3" usename="1"><c colspan="3"></c></box>
+        <box hibit="11" width="4" name="Rd" usename="1"><c colspan="4"></c></box>
+        <box hibit="7" width="8" name="imm8" usename="1"><c colspan="8"></c></box>
+      </regdiagram>
+      <encoding name="ADD_i_T3" oneofinclass="1" oneof="3" label="T3">
+        <asmtemplate><text>ADD.W </text><a link="rd">&lt;Rd&gt;</a><text>, #</text><a link="const">&lt;const&gt;</a></asmtemplate>
+      </encoding>
+    </iclass>
+  </classes>
+</instructionsection>"#;
+        let instr = parse_instruction_xml(xml).unwrap();
+        assert!(instr.is_aarch32());
+        assert_eq!(instr.isa, "A32, T32");
+        let encodings: Vec<(InstructionSet, u32, u32, bool)> = instr
+            .encodings
+            .iter()
+            .map(|enc| {
+                (
+                    enc.isa,
+                    enc.pattern.mask,
+                    enc.pattern.value,
+                    enc.is_conditional(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            encodings,
+            vec![
+                (InstructionSet::A32, 0x0FE0_0000, 0x0280_0000, true),
+                (InstructionSet::T16, 0xFE00, 0x1C00, false),
+                (InstructionSet::T32, 0xFBE0_8000, 0xF100_0000, false),
+            ]
+        );
+        assert_eq!(
+            instr.encodings[0].pattern.not_equal,
+            vec![(0xF000_0000, 0xF000_0000)]
+        );
+
+        let md = instr.into_md();
+        assert!(md.contains(&"- `ADDS <Rd>, #<imm3>` — T1 `mask=0xfe00 value=0x1c00`".to_owned()));
+        assert!(md.contains(&"  - A32 32-bit, conditional (`cond`)".to_owned()));
+        assert!(md.contains(&"  - T32 16-bit".to_owned()));
+        // T32 다이어그램은 하프워드별 표
+        let t3 = md.iter().position(|line| line == "### T3").unwrap();
+        assert_eq!(md[t3 + 2], "hw1");
+        assert_eq!(md[t3 + 4], "| 15:11 | 10 | 9 | 8:5 | 4 | 3:0 |");
+        assert_eq!(md[t3 + 9], "hw2");
+        assert_eq!(md[t3 + 11], "| 15 | 14:12 | 11:8 | 7:0 |");
+    }
+
+    #[test]
+    fn test_skip_non_instructionsection_root() {
+        let xml = br#"<allinstrs id="A64instructiondetails">
+  <instructionsection id="shared_pseudocode" title="Shared Pseudocode Functions" type="pseudocode">
+    <heading>Shared Pseudocode Functions</heading>
+  </instructionsection>
+</allinstrs>"#;
+        assert!(parse_instruction_xml(xml).is_none());
+        let files = HashMap::from([("onebigfile.xml".to_owned(), xml.to_vec())]);
+        assert!(parse_all_instructions(files).is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+use super::get_attr;
+use crate::arm::result::{ArmInstruction, SharedFunction, SharedKind, SharedPseudocode};
+use crate::pseudocode::asl;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::BTreeSet;
+use tracing::{debug, warn};
+
+/// 공유 의사코드 라이브러리 파일명
+pub(crate) const SHARED_PSEUDOCODE_FILE: &str = "shared_pseudocode.xml";
+
+/// pstext 안의 `<anchor>` 정의 위치
+struct Anchor {
+    /// pstext 텍스트 내 시작 오프셋
+    offset: usize,
+    /// e.g., "function: boolean IsZero(bits(N) x)"
+    hover: String,
+    name: String,
+}
+
+/// shared_pseudocode.xml 파싱: `<anchor>` 정의마다 인덱스 엔트리 하나
+pub(crate) fn parse_shared_pseudocode(data: &[u8]) -> SharedPseudocode {
+    let mut reader = Reader::from_reader(data);
+    reader.config_mut().trim_text(false);
+
+    let mut shared = SharedPseudocode::default();
+    let mut buf = Vec::new();
+    let mut section = String::new();
+    let mut in_pstext = false;
+    let mut in_anchor = false;
+    let mut text = String::new();
+    let mut anchors: Vec<Anchor> = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                b"ps" => section = get_attr(e, "name").unwrap_or_default(),
+                b"pstext" => {
+                    in_pstext = true;
+                    text.clear();
+                    anchors.clear();
+                }
+                b"anchor" if in_pstext => {
+                    in_anchor = true;
+                    anchors.push(Anchor {
+                        offset: text.len(),
+                        hover: get_attr(e, "hover").unwrap_or_default(),
+                        name: String::new(),
+                    });
+                }
+                _ => {}
+            },
+            Ok(Event::End(ref e)) => match e.name().as_ref() {
+                b"pstext" => {
+                    in_pstext = false;
+                    shared
+                        .functions
+                        .extend(definitions_from_pstext(&text, &anchors, &section));
+                }
+                b"anchor" => in_anchor = false,
+                _ => {}
+            },
+            Ok(Event::Text(ref e)) if in_pstext => {
+                let t = e.unescape().unwrap_or_default();
+                if in_anchor {
+                    if let Some(anchor) = anchors.last_mut() {
+                        anchor.name.push_str(&t);
+                    }
+                }
+                text.push_str(&t);
+            }
+            Err(e) => {
+                warn!("공유 의사코드 XML 파싱 오류: {}", e);
+                break;
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    debug!(
+        "Parsed {} shared pseudocode definitions",
+        shared.functions.len()
+    );
+    shared
+}
+
+/// 정의 하나의 본문: 앵커 줄부터 다음 정의 줄 전까지 (뒤따르는 주석 헤더 제외)
+fn definitions_from_pstext(text: &str, anchors: &[Anchor], section: &str) -> Vec<SharedFunction> {
+    let line_start = |offset: usize| text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let mut definitions = Vec::new();
+    for (i, anchor) in anchors.iter().enumerate() {
+        let start = line_start(anchor.offset);
+        // 같은 줄의 앵커(열거형 멤버 등)는 본문을 공유
+        let end = anchors[i + 1..]
+            .iter()
+            .map(|next| line_start(next.offset))
+            .find(|&next| next != start)
+            .unwrap_or(text.len());
+        let mut lines: Vec<&str> = text[start..end].lines().collect();
+        while lines
+            .last()
+            .is_some_and(|line| line.trim().is_empty() || line.trim_start().starts_with("//"))
+        {
+            lines.pop();
+        }
+
+        let (kind, signature) = match anchor.hover.split_once(": ") {
+            Some(("function", signature)) => (SharedKind::Function, signature),
+            Some(("accessor", signature)) => (SharedKind::Accessor, signature),
+            _ => {
+                let kind = match anchor.hover.split_whitespace().next() {
+                    Some("constant") => SharedKind::Constant,
+                    Some("enumeration") => SharedKind::Enumeration,
+                    _ => SharedKind::Type,
+                };
+                (kind, anchor.hover.as_str())
+            }
+        };
+        definitions.push(SharedFunction {
+            name: anchor.name.trim().to_owned(),
+            kind,
+            signature: signature.to_owned(),
+            body: lines.join("\n"),
+            section: section.to_owned(),
+        });
+    }
+    definitions
+}
+
+/// 디코드/실행 의사코드가 호출하는 공유 함수·접근자 기록
+pub(crate) fn link_shared_functions(
+    instructions: &mut [ArmInstruction],
+    shared: &SharedPseudocode,
+) {
+    for instr in instructions {
+        let mut names: BTreeSet<String> =
+            asl::referenced_functions(&instr.parse_decode().statements);
+        names.extend(asl::referenced_functions(
+            &instr.parse_operation().statements,
+        ));
+        instr.shared_functions = names
+            .into_iter()
+            .filter(|name| {
+                shared
+                    .find(name)
+                    .any(|f| matches!(f.kind, SharedKind::Function | SharedKind::Accessor))
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shared_pseudocode() {
+        let xml = br#"<instructionsection><ps_section>
+<ps name="shared/functions/registers/X" secttype="Library"><pstext section="Functions">// X[] - assignment form
+// =====================
+
+<anchor link="impl-aarch64.X.write.1" hover="accessor: X[integer n] = bits(width) value">X</anchor>[integer n] = bits(width) value
+    assert n &gt;= 0 &amp;&amp; n &lt;= 31;
+    return;
+
+// X[] - non-assignment form
+// =========================
+
+bits(width) <anchor link="impl-aarch64.X.read.1" hover="accessor: bits(width) X[integer n]">X</anchor>[integer n]
+    return <a link="impl-shared.Zeros.0" file="shared_pseudocode.xml" hover="function: bits(N) Zeros()">Zeros</a>();
+</pstext></ps>
+<ps name="shared/functions/common/SRType" secttype="Library"><pstext section="Functions">enumeration <anchor link="SRType" hover="enumeration SRType {SRType_LSL, SRType_ROR}">SRType</anchor> {<anchor link="SRType_LSL" hover="enumeration SRType {SRType_LSL, SRType_ROR}">SRType_LSL</anchor>, SRType_ROR};
+</pstext></ps>
+</ps_section></instructionsection>"#;
+        let shared = parse_shared_pseudocode(xml);
+        assert_eq!(shared.functions.len(), 4);
+
+        let x: Vec<_> = shared.find("X").collect();
+        assert_eq!(x.len(), 2);
+        assert_eq!(x[0].kind, SharedKind::Accessor);
+        assert_eq!(x[0].signature, "X[integer n] = bits(width) value");
+        assert_eq!(
+            x[0].body,
+            "X[integer n] = bits(width) value\n    assert n >= 0 && n <= 31;\n    return;"
+        );
+        assert_eq!(x[0].section, "shared/functions/registers/X");
+        assert_eq!(x[1].body, "bits(width) X[integer n]\n    return Zeros();");
+
+        let lsl = shared.find("SRType_LSL").next().unwrap();
+        assert_eq!(lsl.kind, SharedKind::Enumeration);
+        assert_eq!(lsl.body, "enumeration SRType {SRType_LSL, SRType_ROR};");
+    }
+}
@@ -0,0 +1,331 @@
+use super::get_attr;
+use crate::arm::result::{RegisterAccessor, RegisterField, RegisterFieldset, SystemRegister};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use tracing::warn;
+
+/// 시스템 레지스터 페이지 (e.g., "AArch64-sctlr_el1.xml")
+pub(crate) fn is_sysreg_file(filename: &str) -> bool {
+    filename.starts_with("AArch64-") && filename.ends_with(".xml")
+}
+
+/// 텍스트를 모으는 중인 요소
+#[derive(PartialEq, Eq)]
+enum Capture {
+    None,
+    ShortName,
+    LongName,
+    Condition,
+    Purpose,
+    FieldsCondition,
+    FieldName,
+    FieldMsb,
+    FieldLsb,
+    FieldReset,
+    AccessInstruction,
+    Pseudocode,
+}
+
+/// 레지스터 페이지 하나 파싱: AArch64 `<register>`만 반환
+pub(crate) fn parse_system_registers(data: &[u8]) -> Vec<SystemRegister> {
+    let mut reader = Reader::from_reader(data);
+    reader.config_mut().trim_text(false);
+
+    let mut registers = Vec::new();
+    let mut buf = Vec::new();
+    let mut register: Option<SystemRegister> = None;
+    let mut capture = Capture::None;
+    let mut text = String::new();
+    // partial_fieldset 안의 `<fields>`는 건너뜀
+    let mut fields_depth = 0;
+    let mut in_purpose = false;
+    let mut current_fieldset = RegisterFieldset::default();
+    let mut current_field: Option<RegisterField> = None;
+    let mut current_accessor: Option<RegisterAccessor> = None;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(ref e)) if register.is_none() => {
+                if e.name().as_ref() == b"register"
+                    && get_attr(e, "execution_state").as_deref() == Some("AArch64")
+                    && get_attr(e, "is_register").as_deref() != Some("False")
+                {
+                    register = Some(SystemRegister::default());
+                }
+            }
+            Ok(Event::Start(ref e)) => {
+                // 중첩 요소 (<a>, <arm-defined-word> 등)는 바깥 캡처 유지
+                let next = match e.name().as_ref() {
+                    b"reg_short_name" => Some(Capture::ShortName),
+                    b"reg_long_name" => Some(Capture::LongName),
+                    b"reg_condition" => Some(Capture::Condition),
+                    b"reg_purpose" => {
+                        in_purpose = true;
+                        None
+                    }
+                    b"para" if in_purpose => Some(Capture::Purpose),
+                    b"fields" => {
+                        fields_depth += 1;
+                        if fields_depth == 1 {
+                            current_fieldset = RegisterFieldset {
+                                length: get_attr(e, "length")
+                                    .and_then(|length| length.parse().ok())
+                                    .unwrap_or(0),
+                                ..Default::default()
+                            };
+                        }
+                        None
+                    }
+                    b"fields_condition" if fields_depth == 1 => Some(Capture::FieldsCondition),
+                    b"field" if fields_depth == 1 => {
+                        current_field = Some(RegisterField {
+                            rwtype: get_attr(e, "rwtype").unwrap_or_default(),
+                            ..Default::default()
+                        });
+                        None
+                    }
+                    b"field_name" if fields_depth == 1 && current_field.is_some() => {
+                        Some(Capture::FieldName)
+                    }
+                    b"field_msb" if fields_depth == 1 && current_field.is_some() => {
+                        Some(Capture::FieldMsb)
+                    }
+                    b"field_lsb" if fields_depth == 1 && current_field.is_some() => {
+                        Some(Capture::FieldLsb)
+                    }
+                    b"field_resets" if fields_depth == 1 && current_field.is_some() => {
+                        Some(Capture::FieldReset)
+                    }
+                    b"access_mechanism" => {
+                        current_accessor = Some(RegisterAccessor {
+                            accessor: get_attr(e, "accessor").unwrap_or_default(),
+                            ..Default::default()
+                        });
+                        None
+                    }
+                    b"access_instruction" if current_accessor.is_some() => {
+                        Some(Capture::AccessInstruction)
+                    }
+                    b"pstext" if current_accessor.is_some() => Some(Capture::Pseudocode),
+                    _ => None,
+                };
+                if let Some(next) = next {
+                    capture = next;
+                    text.clear();
+                }
+            }
+            Ok(Event::End(ref e)) if e.name().as_ref() == b"register" => {
+                registers.extend(register.take());
+            }
+            Ok(Event::End(ref e)) => {
+                let Some(reg) = register.as_mut() else {
+                    buf.clear();
+                    continue;
+                };
+                let value = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                match (e.name().as_ref(), &capture) {
+                    (b"reg_short_name", _) => reg.short_name = value,
+                    (b"reg_long_name", _) => reg.long_name = value,
+                    (b"reg_condition", _) => reg.condition = value,
+                    (b"reg_purpose", _) => in_purpose = false,
+                    (b"para", Capture::Purpose) => {
+                        if !value.is_empty() {
+                            reg.purpose.push(value)
+                        }
+                    }
+                    (b"fields_condition", Capture::FieldsCondition) => {
+                        current_fieldset.condition = value
+                    }
+                    (b"fields", _) => {
+                        if fields_depth == 1 {
+                            reg.fieldsets.push(std::mem::take(&mut current_fieldset));
+                        }
+                        fields_depth -= 1;
+                    }
+                    (b"field", _) if fields_depth == 1 => {
+                        current_fieldset.fields.extend(current_field.take());
+                    }
+                    (b"field_name" | b"field_msb" | b"field_lsb" | b"field_resets", _) => {
+                        if let Some(field) = current_field.as_mut() {
+                            match capture {
+                                Capture::FieldName => field.name = value,
+                                Capture::FieldMsb => field.msb = value.parse().unwrap_or(0),
+                                Capture::FieldLsb => field.lsb = value.parse().unwrap_or(0),
+                                Capture::FieldReset => field.reset = value,
+                                _ => {}
+                            }
+                        }
+                    }
+                    (b"access_mechanism", _) => reg.accessors.extend(current_accessor.take()),
+                    (b"access_instruction" | b"pstext", _) => {
+                        if let Some(accessor) = current_accessor.as_mut() {
+                            match capture {
+                                Capture::AccessInstruction => accessor.instruction = value,
+                                Capture::Pseudocode => accessor.pseudocode = text.trim().to_owned(),
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {
+                        buf.clear();
+                        continue;
+                    }
+                }
+                capture = Capture::None;
+            }
+            Ok(Event::Empty(ref e)) => match e.name().as_ref() {
+                b"enc" => {
+                    if let Some(accessor) = current_accessor.as_mut() {
+                        accessor.encoding.push((
+                            get_attr(e, "n").unwrap_or_default(),
+                            get_attr(e, "v").unwrap_or_default(),
+                        ));
+                    }
+                }
+                b"arch_variant" | b"reg_variant" => {
+                    if let Some(reg) = register.as_mut() {
+                        reg.requirement.add(
+                            &get_attr(e, "name").unwrap_or_default(),
+                            &get_attr(e, "feature").unwrap_or_default(),
+                        );
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Text(ref e)) if capture != Capture::None => {
+                text.push_str(&e.unescape().unwrap_or_default());
+            }
+            Err(e) => {
+                warn!("시스템 레지스터 XML 파싱 오류: {}", e);
+                break;
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    for reg in &mut registers {
+        for fieldset in &mut reg.fieldsets {
+            fieldset
+                .fields
+                .sort_by_key(|field| std::cmp::Reverse(field.msb));
+        }
+    }
+    registers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_system_registers() {
+        let xml = br#"<register_page>
+  <registers>
+    <register execution_state="AArch32" is_register="True">
+      <reg_short_name>SCTLR</reg_short_name>
+    </register>
+    <register execution_state="AArch64" is_register="True" is_internal="True">
+      <reg_short_name>SCTLR_EL1</reg_short_name>
+      <reg_long_name>System Control Register (EL1)</reg_long_name>
+      <reg_condition otherwise="UNKNOWN">When <arm-defined-word>FEAT_AA64EL1</arm-defined-word> is implemented</reg_condition>
+      <reg_purpose>
+        <purpose_text>
+          <para>Provides top level control of the system, including its memory
+            system, at EL1 and EL0.</para>
+        </purpose_text>
+      </reg_purpose>
+      <reg_variants><reg_variant name="ARMv8.0-A" /></reg_variants>
+      <reg_fieldsets>
+        <fields length="64">
+          <fields_condition>When FEAT_VHE is implemented</fields_condition>
+          <field id="0_63_37" rwtype="RES0">
+            <field_msb>63</field_msb>
+            <field_lsb>37</field_lsb>
+            <field_description order="before"><para>Reserved, RES0.</para></field_description>
+          </field>
+          <field id="M_0_0" rwtype="">
+            <field_name>M</field_name>
+            <field_msb>0</field_msb>
+            <field_lsb>0</field_lsb>
+            <field_values>
+              <field_value_instance><field_value>0b0</field_value></field_value_instance>
+            </field_values>
+            <field_resets>
+              <field_reset reset_type="Warm">
+                <field_reset_standard_text>Arch</field_reset_standard_text> 0b0
+              </field_reset>
+            </field_resets>
+          </field>
+          <field id="A_1_1" rwtype="" has_partial_fieldset="True">
+            <field_name>A</field_name>
+            <field_msb>1</field_msb>
+            <field_lsb>1</field_lsb>
+            <partial_fieldset>
+              <fields length="1">
+                <field id="A_0_0"><field_name>NESTED</field_name></field>
+              </fields>
+            </partial_fieldset>
+          </field>
+        </fields>
+      </reg_fieldsets>
+      <access_mechanisms>
+        <access_mechanism accessor="MRS SCTLR_EL1" type="SystemAccessor">
+          <encoding>
+            <access_instruction>MRS &lt;Xt&gt;, SCTLR_EL1</access_instruction>
+            <enc n="op0" v="0b11" />
+            <enc n="op1" v="0b000" />
+            <enc n="CRn" v="0b0001" />
+            <enc n="CRm" v="0b0000" />
+            <enc n="op2" v="0b000" />
+          </encoding>
+          <access_permission>
+            <ps name="MRS SCTLR_EL1" sections="1" secttype="access_permission">
+              <pstext section="access_permission">if PSTATE.EL == EL0 then
+    UNDEFINED;
+elsif PSTATE.EL == EL1 then
+    X[t] = <a link="SCTLR_EL1">SCTLR_EL1</a>;</pstext>
+            </ps>
+          </access_permission>
+        </access_mechanism>
+      </access_mechanisms>
+    </register>
+  </registers>
+</register_page>"#;
+        let registers = parse_system_registers(xml);
+        assert_eq!(registers.len(), 1);
+        let sctlr = &registers[0];
+        assert_eq!(sctlr.short_name, "SCTLR_EL1");
+        assert_eq!(sctlr.long_name, "System Control Register (EL1)");
+        assert_eq!(sctlr.condition, "When FEAT_AA64EL1 is implemented");
+        assert_eq!(sctlr.requirement.version, "ARMv8.0");
+        assert_eq!(
+            sctlr.purpose,
+            vec!["Provides top level control of the system, including its memory system, at EL1 and EL0."]
+        );
+
+        assert_eq!(sctlr.fieldsets.len(), 1);
+        let fieldset = &sctlr.fieldsets[0];
+        assert_eq!(fieldset.length, 64);
+        assert_eq!(fieldset.condition, "When FEAT_VHE is implemented");
+        let fields: Vec<(&str, u32, u32, &str)> = fieldset
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.msb, f.lsb, f.rwtype.as_str()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![("", 63, 37, "RES0"), ("A", 1, 1, ""), ("M", 0, 0, "")]
+        );
+        assert_eq!(fieldset.fields[2].reset, "Arch 0b0");
+
+        assert_eq!(sctlr.accessors.len(), 1);
+        let accessor = &sctlr.accessors[0];
+        assert_eq!(accessor.instruction, "MRS <Xt>, SCTLR_EL1");
+        assert_eq!(accessor.register_name(), "SCTLR_EL1");
+        assert!(!accessor.is_write());
+        assert_eq!(accessor.system_encoding(), Some(0xC080));
+        assert!(accessor.pseudocode.ends_with("X[t] = SCTLR_EL1;"));
+    }
+}
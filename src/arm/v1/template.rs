@@ -0,0 +1,256 @@
+use crate::arm::result::{ArmInstruction, AsmOperand, AsmToken, OperandExplanation};
+
+/// `<asmtemplate>` 조각: 텍스트와 `<a>`이면 (link, hover)
+pub(crate) type TemplatePart = (String, Option<(String, String)>);
+
+enum Item {
+    Char(char),
+    Operand(AsmOperand),
+}
+
+/// `<asmtemplate>` 조각 → 토큰. `{ ... }`처럼 안쪽 양끝이 공백인 중괄호는 레지스터 목록으로 보고 텍스트로 남김
+pub(crate) fn parse_asm_template(parts: &[TemplatePart]) -> Vec<AsmToken> {
+    let mut items = Vec::new();
+    for (text, anchor) in parts {
+        match anchor {
+            Some((link, hover)) => items.push(Item::Operand(AsmOperand {
+                symbol: text.clone(),
+                link: link.clone(),
+                hover: hover.clone(),
+                ..Default::default()
+            })),
+            None => items.extend(text.chars().map(Item::Char)),
+        }
+    }
+    // asm_template과 같이 앞뒤 공백 제거
+    while matches!(items.first(), Some(Item::Char(c)) if c.is_whitespace()) {
+        items.remove(0);
+    }
+    while matches!(items.last(), Some(Item::Char(c)) if c.is_whitespace()) {
+        items.pop();
+    }
+    parse_items(&items)
+}
+
+fn parse_items(items: &[Item]) -> Vec<AsmToken> {
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < items.len() {
+        match &items[i] {
+            Item::Operand(operand) => tokens.push(AsmToken::Operand(operand.clone())),
+            Item::Char('{') => {
+                if let Some(end) = matching_brace(items, i) {
+                    let inner = &items[i + 1..end];
+                    let is_list = matches!(inner.first(), Some(Item::Char(' ')))
+                        && matches!(inner.last(), Some(Item::Char(' ')));
+                    if !is_list {
+                        tokens.push(AsmToken::Optional(parse_items(inner)));
+                        i = end + 1;
+                        continue;
+                    }
+                }
+                push_literal(&mut tokens, '{');
+            }
+            Item::Char(c) => push_literal(&mut tokens, *c),
+        }
+        i += 1;
+    }
+    tokens
+}
+
+fn matching_brace(items: &[Item], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, item) in items.iter().enumerate().skip(open) {
+        match item {
+            Item::Char('{') => depth += 1,
+            Item::Char('}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn push_literal(tokens: &mut Vec<AsmToken>, c: char) {
+    match tokens.last_mut() {
+        Some(AsmToken::Literal(text)) => text.push(c),
+        _ => tokens.push(AsmToken::Literal(c.to_string())),
+    }
+}
+
+/// 템플릿 오퍼랜드 ↔ 같은 link, 같은 인코딩의 `<explanation>` 연결
+pub(crate) fn link_template_operands(instr: &mut ArmInstruction) {
+    let explanations = &instr.operand_explanations;
+    for enc in &mut instr.encodings {
+        link_operands(&mut enc.template, &enc.name, explanations);
+    }
+}
+
+fn link_operands(tokens: &mut [AsmToken], encoding: &str, explanations: &[OperandExplanation]) {
+    for token in tokens {
+        match token {
+            AsmToken::Operand(operand) => {
+                // enclist에서 빠진 인코딩이 있음 (e.g., STADDL_LDADDL_32_memop) — 같은 심볼로 대체
+                operand.explanation = explanations
+                    .iter()
+                    .position(|explanation| {
+                        explanation.link == operand.link && explanation.applies_to(encoding)
+                    })
+                    .or_else(|| {
+                        explanations.iter().position(|explanation| {
+                            explanation.link == operand.link && explanation.symbol == operand.symbol
+                        })
+                    });
+                operand.fields = operand
+                    .explanation
+                    .map(|i| explanations[i].fields())
+                    .unwrap_or_default();
+            }
+            AsmToken::Optional(tokens) => link_operands(tokens, encoding, explanations),
+            AsmToken::Literal(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arm::result::{ArmEncoding, ValueTable};
+
+    fn render(tokens: &[AsmToken]) -> String {
+        tokens
+            .iter()
+            .map(|token| match token {
+                AsmToken::Literal(text) => text.clone(),
+                AsmToken::Operand(operand) => operand.symbol.clone(),
+                AsmToken::Optional(tokens) => format!("{{{}}}", render(tokens)),
+            })
+            .collect()
+    }
+
+    fn parts(parts: &[(&str, Option<&str>)]) -> Vec<TemplatePart> {
+        parts
+            .iter()
+            .map(|(text, link)| {
+                (
+                    text.to_string(),
+                    link.map(|link| (link.to_owned(), String::new())),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_asm_template() {
+        let add = parse_asm_template(&parts(&[
+            ("ADD  ", None),
+            ("<Wd|WSP>", Some("wd_wsp")),
+            (", #", None),
+            ("<imm>", Some("imm")),
+            ("{", None),
+            (", ", None),
+            ("<shift>", Some("shift")),
+            ("}", None),
+        ]));
+        assert_eq!(add.len(), 5);
+        assert_eq!(add[0], AsmToken::Literal("ADD  ".to_owned()));
+        let AsmToken::Optional(optional) = &add[4] else {
+            panic!("expected optional group: {add:?}");
+        };
+        assert_eq!(render(optional), ", <shift>");
+        assert_eq!(render(&add), "ADD  <Wd|WSP>, #<imm>{, <shift>}");
+
+        // 레지스터 목록은 텍스트, 안쪽 선택 그룹은 중첩
+        let ld1 = parse_asm_template(&parts(&[
+            ("LD1  ", None),
+            ("{", None),
+            (" ", None),
+            ("<Vt>", Some("vt")),
+            (" ", None),
+            ("}", None),
+            (", [", None),
+            ("<Xn|SP>", Some("xn_sp")),
+            ("{, ", None),
+            ("<extend>", Some("extend")),
+            (" {", None),
+            ("#<amount>", Some("amount")),
+            ("}}]", None),
+        ]));
+        assert_eq!(ld1[0], AsmToken::Literal("LD1  { ".to_owned()));
+        assert_eq!(ld1[2], AsmToken::Literal(" }, [".to_owned()));
+        let AsmToken::Optional(extend) = &ld1[4] else {
+            panic!("expected optional group: {ld1:?}");
+        };
+        assert!(matches!(extend.last(), Some(AsmToken::Optional(_))));
+        assert_eq!(
+            render(&ld1),
+            "LD1  { <Vt> }, [<Xn|SP>{, <extend> {#<amount>}}]"
+        );
+    }
+
+    #[test]
+    fn test_link_template_operands() {
+        let explanation = |link: &str, encodings: &[&str], encoded_in: &str| OperandExplanation {
+            symbol: format!("<{link}>"),
+            link: link.to_owned(),
+            encodings: encodings.iter().map(|name| name.to_string()).collect(),
+            encoded_in: encoded_in.to_owned(),
+            description: String::new(),
+            values: ValueTable::default(),
+        };
+        let template = parse_asm_template(&parts(&[
+            ("MOVI  ", None),
+            ("<Vd>", Some("vd")),
+            (".", None),
+            ("<T>", Some("t")),
+        ]));
+        let mut instr = ArmInstruction {
+            encodings: vec![
+                ArmEncoding {
+                    name: "MOVI_asimdimm_N_b".to_owned(),
+                    template: template.clone(),
+                    ..Default::default()
+                },
+                ArmEncoding {
+                    name: "MOVI_asimdimm_L_hl".to_owned(),
+                    template,
+                    ..Default::default()
+                },
+            ],
+            operand_explanations: vec![
+                explanation("vd", &[], "Rd"),
+                explanation("t", &["MOVI_asimdimm_N_b"], "Q"),
+                explanation("t", &["MOVI_asimdimm_L_hl"], "cmode<1>:Q"),
+            ],
+            ..Default::default()
+        };
+        link_template_operands(&mut instr);
+
+        let operands = |enc: &ArmEncoding| -> Vec<(Option<usize>, Vec<String>)> {
+            enc.template
+                .iter()
+                .filter_map(|token| match token {
+                    AsmToken::Operand(operand) => {
+                        Some((operand.explanation, operand.fields.clone()))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(
+            operands(&instr.encodings[0]),
+            vec![
+                (Some(0), vec!["Rd".to_owned()]),
+                (Some(1), vec!["Q".to_owned()])
+            ]
+        );
+        assert_eq!(
+            operands(&instr.encodings[1])[1],
+            (Some(2), vec!["cmode<1>".to_owned(), "Q".to_owned()])
+        );
+    }
+}
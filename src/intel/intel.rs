@@ -6,15 +6,29 @@ use crate::pdf::{
     self,
     v2::{PdfBoxes, PdfString},
 };
+use crate::pseudocode;
 use result::Instruction;
 use std::{collections::HashMap, sync::Once};
 
+/// Vol.2 chapter 2 pages holding the exception class tables (Type 1-12, E1-E12 ...)
+const EXCEPTION_CLASS_PAGES: (u32, u32) = (40, 129);
+/// Set to expand "See Table 2-21, Type 4 Class Exception Conditions" references into the
+/// class table rows; unset keeps the references as written
+const EXPAND_EXCEPTION_CLASSES: &str = "INTEL_EXPAND_EXCEPTION_CLASSES";
+
 pub fn main() {
     let mut result = Vec::new();
     for (from, to) in [(129, 734), (742, 1476), (1481, 2196), (2198, 2266)] {
         let data = extract_text(from, to);
         result.append(&mut v2::parse_instructions(data));
     }
+    // e.g., INTEL_EXPAND_EXCEPTION_CLASSES=1
+    if std::env::var(EXPAND_EXCEPTION_CLASSES).is_ok_and(|value| value != "0") {
+        let (from, to) = EXCEPTION_CLASS_PAGES;
+        let classes = v2::parse_exception_classes(extract_text(from, to));
+        v2::expand_exception_classes(&mut result, &classes);
+    }
+    save_operation_report(&result);
     let saved_instructions = save_instructions(result);
     saved_list_to_rust_enum(saved_instructions);
 }
@@ -51,6 +65,51 @@ fn extract_text(from: u32, to: u32) -> Vec<(Vec<PdfString>, PdfBoxes)> {
     results
 }
 
+/// Parse every Operation section and list the lines that failed to parse
+/// (mostly PDF extraction damage)
+fn save_operation_report(blocks: &[Instruction]) {
+    let mut failed_instructions = 0;
+    let mut failed_lines = 0;
+    let mut details = Vec::new();
+    for block in blocks.iter().filter(|block| !block.operation.is_empty()) {
+        let operation = block.get_reformed_operation().join("\n");
+        let parsed = pseudocode::intel::parse(&operation);
+        if parsed.failures.is_empty() {
+            continue;
+        }
+        failed_instructions += 1;
+        failed_lines += parsed.failures.len();
+        details.push(String::new());
+        details.push(format!("## {}", block.title));
+        details.push(String::new());
+        for failure in parsed.failures {
+            details.push(format!(
+                "- line {}: `{}` — {}",
+                failure.line, failure.text, failure.message
+            ));
+        }
+    }
+    let total = blocks
+        .iter()
+        .filter(|block| !block.operation.is_empty())
+        .count();
+    tracing::debug!(
+        "Operation 파싱 실패: {failed_instructions}/{total} 인스트럭션, {failed_lines} 라인"
+    );
+
+    let mut report = vec![
+        "# Operation Parse Failures".to_owned(),
+        String::new(),
+        format!("- Instructions with Operation: {total}"),
+        format!("- Instructions with failures: {failed_instructions}"),
+        format!("- Failed lines: {failed_lines}"),
+    ];
+    report.append(&mut details);
+    std::fs::create_dir_all("result").expect("베이스 디렉토리 생성 불가");
+    std::fs::write("result/intel_operation_report.md", report.join("\n"))
+        .expect("리포트 생성 실패");
+}
+
 /// return is parsed instruction names
 fn save_instructions(blocks: Vec<Instruction>) -> HashMap<String, Vec<String>> {
     blocks.into_iter().map(save_instruction).flatten().collect()
@@ -36,6 +36,698 @@ impl MdTable {
     }
 }
 
+/// 필수 프리픽스 (레거시 66/F2/F3 혹은 VEX/EVEX의 pp 필드)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MandatoryPrefix {
+    /// NP - 66/F2/F3 프리픽스 사용 불가
+    #[default]
+    Np,
+    P66,
+    PF2,
+    PF3,
+}
+
+/// VEX/EVEX 구분
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VectorPrefixKind {
+    Vex,
+    Evex,
+}
+
+/// VEX.L / EVEX.L'L 필드
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VectorLength {
+    /// 128 혹은 L0
+    L128,
+    /// 256 혹은 L1
+    L256,
+    L512,
+    /// LZ / LLZ - 0이어야 함
+    Zero,
+    /// LIG / LLIG - 무시됨
+    Ignored,
+}
+
+/// VEX.W / EVEX.W / REX.W 필드
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WBit {
+    W0,
+    W1,
+    /// WIG - 무시됨
+    Ignored,
+}
+
+/// VEX/EVEX 오피코드 맵 (mmmmm)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OpcodeMap {
+    Map0F,
+    Map0F38,
+    Map0F3A,
+    /// MAP4, MAP5, MAP6 등
+    Map(u8),
+}
+
+/// VEX.vvvv 레지스터 역할 (구버전 SDM의 NDS/NDD/DDS 표기)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VvvvRole {
+    Nds,
+    Ndd,
+    Dds,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RexPrefix {
+    /// REX.W 필요 여부
+    pub(crate) w: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VectorPrefix {
+    pub(crate) kind: VectorPrefixKind,
+    pub(crate) length: Option<VectorLength>,
+    pub(crate) pp: MandatoryPrefix,
+    pub(crate) map: Option<OpcodeMap>,
+    pub(crate) w: Option<WBit>,
+    pub(crate) vvvv: Option<VvvvRole>,
+}
+
+/// ModR/M 바이트 사용 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ModRm {
+    /// /r - reg 필드가 레지스터 오퍼랜드
+    Reg,
+    /// /digit - reg 필드가 오피코드 확장
+    Digit(u8),
+}
+
+/// 오피코드 하위 3비트에 더해지는 레지스터 (+rb/+rw/+rd/+ro/+i)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OpcodeRegister {
+    Byte,
+    Word,
+    Dword,
+    Qword,
+    /// +i - FPU 스택 레지스터
+    FpuStack,
+}
+
+/// 즉시값 크기 (ib/iw/id/io, /is4)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ImmediateSize {
+    Byte,
+    Word,
+    Dword,
+    Qword,
+    /// /is4 - 상위 4비트가 레지스터인 imm8
+    Is4,
+}
+
+/// 코드 오프셋 크기 (cb/cw/cd/cp/co/ct)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CodeOffset {
+    Byte,
+    Word,
+    Dword,
+    /// 6바이트 far 포인터
+    Pointer,
+    Qword,
+    /// 10바이트
+    Tbyte,
+}
+
+/// Opcode 컬럼을 파싱한 인코딩 정보
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct X86Encoding {
+    /// 필수 프리픽스가 아닌 레거시 프리픽스 (F0, F3 REP 등)
+    pub(crate) legacy_prefixes: Vec<u8>,
+    /// 레거시 인코딩의 필수 프리픽스 (0F 이스케이프 앞의 66/F2/F3 혹은 NP)
+    pub(crate) mandatory_prefix: Option<MandatoryPrefix>,
+    pub(crate) rex: Option<RexPrefix>,
+    pub(crate) vector: Option<VectorPrefix>,
+    /// 오피코드 바이트 (레거시 인코딩은 0F/0F 38/0F 3A 이스케이프 포함)
+    pub(crate) opcode: Vec<u8>,
+    pub(crate) modrm: Option<ModRm>,
+    /// /vsib - ModR/M 메모리 오퍼랜드가 VSIB 형식
+    pub(crate) vsib: bool,
+    pub(crate) opcode_register: Option<OpcodeRegister>,
+    pub(crate) immediates: Vec<ImmediateSize>,
+    pub(crate) code_offset: Option<CodeOffset>,
+    /// 인식하지 못한 토큰
+    pub(crate) unparsed: Vec<String>,
+}
+
+/// 인스트럭션 변형 (Opcode 테이블의 한 줄)
+#[derive(Debug, Default, Clone)]
+pub(crate) struct InstructionVariant {
+    /// 니모닉과 오퍼랜드 (ADDPD xmm1, xmm2/m128)
+    pub(crate) instruction: String,
+    /// Opcode 컬럼 파싱 결과
+    pub(crate) encoding: X86Encoding,
+    /// Op/En 컬럼 값 (RM, MR, A, FV-RVM 등)
+    pub(crate) op_en: String,
+    /// Instruction Operand Encoding 테이블에서 op_en에 해당하는 줄
+    pub(crate) operand_encoding: Option<OperandEncoding>,
+    /// 64-Bit Mode 지원 여부
+    pub(crate) mode_64: Option<ModeValidity>,
+    /// Compat/Leg Mode 지원 여부
+    pub(crate) mode_compat_legacy: Option<ModeValidity>,
+    /// CPUID Feature Flag. 모든 그룹이 필요하고, 그룹 안에서는 하나만 있으면 됨
+    /// (e.g., "AVX512VL OR AVX10.1" → `[[AVX512VL, AVX10.1]]`)
+    pub(crate) cpuid_features: Vec<Vec<String>>,
+}
+
+/// 모드별 지원 여부 (64-Bit Mode, Compat/Leg Mode 컬럼)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ModeValidity {
+    /// V / Valid
+    Valid,
+    /// I / Invalid
+    Invalid,
+    /// N.E. - 해당 모드에서 인코딩 불가
+    NotEncodable,
+    /// N.S. - 64비트 모드에서 주소 크기 오버라이드 프리픽스가 필요하며 사용하지 않아야 함
+    NotSupported,
+    /// N.P. - REX 프리픽스가 64비트 모드에서 레거시 인스트럭션에 영향을 주지 않음
+    NotPrefixed,
+    /// N.I. - 64비트 모드에서 새 인스트럭션으로 취급
+    NewInstruction,
+    /// 인식하지 못한 값 - 원본 텍스트 유지
+    Other(String),
+}
+
+impl ModeValidity {
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            ModeValidity::Valid => "V",
+            ModeValidity::Invalid => "I",
+            ModeValidity::NotEncodable => "N.E.",
+            ModeValidity::NotSupported => "N.S.",
+            ModeValidity::NotPrefixed => "N.P.",
+            ModeValidity::NewInstruction => "N.I.",
+            ModeValidity::Other(text) => text.as_str(),
+        }
+    }
+}
+
+/// 오퍼랜드가 어디서 인코딩되는지
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum OperandSource {
+    /// ModRM:reg
+    ModRmReg,
+    /// ModRM:r/m
+    ModRmRm,
+    /// VEX.vvvv / EVEX.vvvv
+    Vvvv,
+    /// imm8/imm16/imm32/imm64
+    Immediate,
+    /// imm8[7:4] (is4 레지스터)
+    ImmediateHigh,
+    /// opcode + rd
+    OpcodeRegister,
+    /// 기타 암시적 오퍼랜드 (AL/AX/EAX/RAX, Implicit XMM0, 1 등) - 원본 텍스트 유지
+    Implicit(String),
+}
+
+/// 오퍼랜드 하나의 인코딩 (e.g., "ModRM:reg (r, w)")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OperandSpec {
+    pub(crate) source: OperandSource,
+    pub(crate) read: bool,
+    pub(crate) write: bool,
+}
+
+/// Instruction Operand Encoding 테이블의 한 줄
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct OperandEncoding {
+    /// Op/En 값
+    pub(crate) op_en: String,
+    /// EVEX 튜플 타입 (Full, Tuple1 Scalar 등)
+    pub(crate) tuple_type: Option<String>,
+    /// Operand1..Operand4 - N/A는 None
+    pub(crate) operands: Vec<Option<OperandSpec>>,
+}
+
+impl OperandSpec {
+    /// 원래 표기로 변환 (e.g., "ModRM:reg (r, w)").
+    /// vvvv는 변형의 프리픽스에 맞춰 VEX.vvvv / EVEX.vvvv, 프리픽스를 모르면 vvvv
+    pub(crate) fn text(&self, vector: Option<VectorPrefixKind>) -> String {
+        let source = match &self.source {
+            OperandSource::ModRmReg => "ModRM:reg",
+            OperandSource::ModRmRm => "ModRM:r/m",
+            OperandSource::Vvvv => match vector {
+                Some(VectorPrefixKind::Vex) => "VEX.vvvv",
+                Some(VectorPrefixKind::Evex) => "EVEX.vvvv",
+                None => "vvvv",
+            },
+            OperandSource::Immediate => "imm",
+            OperandSource::ImmediateHigh => "imm8[7:4]",
+            OperandSource::OpcodeRegister => "opcode + rd",
+            OperandSource::Implicit(text) => text.as_str(),
+        };
+        let access = match (self.read, self.write) {
+            (true, true) => " (r, w)",
+            (true, false) => " (r)",
+            (false, true) => " (w)",
+            (false, false) => "",
+        };
+        format!("{source}{access}")
+    }
+}
+
+impl MandatoryPrefix {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MandatoryPrefix::Np => "NP",
+            MandatoryPrefix::P66 => "66",
+            MandatoryPrefix::PF2 => "F2",
+            MandatoryPrefix::PF3 => "F3",
+        }
+    }
+}
+
+impl X86Encoding {
+    /// 프리픽스 요약 (e.g., "66 REX.W", "EVEX.512.66.0F.W1")
+    pub(crate) fn prefix_text(&self) -> String {
+        let mut parts: Vec<String> = self
+            .legacy_prefixes
+            .iter()
+            .map(|b| format!("{b:02X}"))
+            .collect();
+        if let Some(mandatory) = &self.mandatory_prefix {
+            parts.push(mandatory.as_str().to_owned());
+        }
+        if let Some(rex) = &self.rex {
+            parts.push(if rex.w { "REX.W" } else { "REX" }.to_owned());
+        }
+        if let Some(vector) = &self.vector {
+            let mut fields = vec![match vector.kind {
+                VectorPrefixKind::Vex => "VEX".to_owned(),
+                VectorPrefixKind::Evex => "EVEX".to_owned(),
+            }];
+            if let Some(length) = &vector.length {
+                fields.push(
+                    match length {
+                        VectorLength::L128 => "128",
+                        VectorLength::L256 => "256",
+                        VectorLength::L512 => "512",
+                        VectorLength::Zero => "LZ",
+                        VectorLength::Ignored => "LIG",
+                    }
+                    .to_owned(),
+                );
+            }
+            // pp가 없는 VEX/EVEX는 SDM 표기대로 생략 (VEX.128.0F.WIG)
+            if vector.pp != MandatoryPrefix::Np {
+                fields.push(vector.pp.as_str().to_owned());
+            }
+            if let Some(map) = &vector.map {
+                fields.push(match map {
+                    OpcodeMap::Map0F => "0F".to_owned(),
+                    OpcodeMap::Map0F38 => "0F38".to_owned(),
+                    OpcodeMap::Map0F3A => "0F3A".to_owned(),
+                    OpcodeMap::Map(n) => format!("MAP{n}"),
+                });
+            }
+            if let Some(w) = &vector.w {
+                fields.push(
+                    match w {
+                        WBit::W0 => "W0",
+                        WBit::W1 => "W1",
+                        WBit::Ignored => "WIG",
+                    }
+                    .to_owned(),
+                );
+            }
+            parts.push(fields.join("."));
+        }
+        parts.join(" ")
+    }
+    /// 오피코드 바이트 (e.g., "0F 58", "B8+rd")
+    pub(crate) fn opcode_text(&self) -> String {
+        let mut text = self
+            .opcode
+            .iter()
+            .map(|b| format!("{b:02X}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if let Some(register) = &self.opcode_register {
+            text.push_str(match register {
+                OpcodeRegister::Byte => "+rb",
+                OpcodeRegister::Word => "+rw",
+                OpcodeRegister::Dword => "+rd",
+                OpcodeRegister::Qword => "+ro",
+                OpcodeRegister::FpuStack => "+i",
+            });
+        }
+        text
+    }
+    /// ModR/M 사용 방식 (e.g., "/r", "/0", "/r (VSIB)")
+    pub(crate) fn modrm_text(&self) -> String {
+        let text = match &self.modrm {
+            Some(ModRm::Reg) => "/r".to_owned(),
+            Some(ModRm::Digit(digit)) => format!("/{digit}"),
+            None => String::new(),
+        };
+        if self.vsib {
+            format!("{text} (VSIB)")
+        } else {
+            text
+        }
+    }
+    /// 즉시값과 코드 오프셋 (e.g., "ib", "iw ib", "cd")
+    pub(crate) fn immediate_text(&self) -> String {
+        let mut parts: Vec<&str> = self
+            .immediates
+            .iter()
+            .map(|imm| match imm {
+                ImmediateSize::Byte => "ib",
+                ImmediateSize::Word => "iw",
+                ImmediateSize::Dword => "id",
+                ImmediateSize::Qword => "io",
+                ImmediateSize::Is4 => "/is4",
+            })
+            .collect();
+        if let Some(offset) = &self.code_offset {
+            parts.push(match offset {
+                CodeOffset::Byte => "cb",
+                CodeOffset::Word => "cw",
+                CodeOffset::Dword => "cd",
+                CodeOffset::Pointer => "cp",
+                CodeOffset::Qword => "co",
+                CodeOffset::Tbyte => "ct",
+            });
+        }
+        parts.join(" ")
+    }
+}
+
+impl Instruction {
+    /// 플래그별 효과를 표로 변환
+    fn flags_to_table(&self) -> MdTable {
+        MdTable {
+            headers: vec!["Flag".to_owned(), "Effect".to_owned(), "Source".to_owned()],
+            rows: self
+                .flags
+                .iter()
+                .map(|entry| {
+                    vec![
+                        entry.flag.as_str().to_owned(),
+                        entry.effect.as_str().to_owned(),
+                        entry.source.clone(),
+                    ]
+                })
+                .collect(),
+        }
+    }
+    /// 변형별 인코딩을 표로 변환
+    fn encodings_to_table(&self) -> MdTable {
+        let headers = [
+            "Instruction",
+            "Prefix",
+            "Opcode",
+            "ModR/M",
+            "Immediate",
+            "Op/En",
+            "Operands",
+            "64-Bit Mode",
+            "Compat/Leg Mode",
+            "CPUID Feature Flag",
+        ]
+        .iter()
+        .map(|h| (*h).to_owned())
+        .collect();
+        let rows = self
+            .variants
+            .iter()
+            .filter(|variant| !variant.encoding.opcode.is_empty())
+            .map(|variant| {
+                vec![
+                    variant.instruction.clone(),
+                    variant.encoding.prefix_text(),
+                    variant.encoding.opcode_text(),
+                    variant.encoding.modrm_text(),
+                    variant.encoding.immediate_text(),
+                    match variant
+                        .operand_encoding
+                        .as_ref()
+                        .and_then(|encoding| encoding.tuple_type.as_ref())
+                    {
+                        Some(tuple_type) => format!("{} ({tuple_type})", variant.op_en),
+                        None => variant.op_en.clone(),
+                    },
+                    variant
+                        .operand_encoding
+                        .as_ref()
+                        .map(|encoding| {
+                            encoding
+                                .operands
+                                .iter()
+                                .flatten()
+                                .map(|spec| {
+                                    spec.text(variant.encoding.vector.as_ref().map(|v| v.kind))
+                                })
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        })
+                        .unwrap_or_default(),
+                    variant
+                        .mode_64
+                        .as_ref()
+                        .map(|v| v.as_str().to_owned())
+                        .unwrap_or_default(),
+                    variant
+                        .mode_compat_legacy
+                        .as_ref()
+                        .map(|v| v.as_str().to_owned())
+                        .unwrap_or_default(),
+                    variant
+                        .cpuid_features
+                        .iter()
+                        .map(|group| match group.as_slice() {
+                            [flag] => flag.clone(),
+                            _ => format!("({})", group.join(" OR ")),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ]
+            })
+            .collect();
+        MdTable { headers, rows }
+    }
+}
+
+/// EFLAGS 및 FPU 상태 플래그
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Flag {
+    Cf,
+    Pf,
+    Af,
+    Zf,
+    Sf,
+    Of,
+    Df,
+    If,
+    Tf,
+    Ac,
+    C0,
+    C1,
+    C2,
+    C3,
+}
+
+impl Flag {
+    pub(crate) const ALL: [Flag; 14] = [
+        Flag::Cf,
+        Flag::Pf,
+        Flag::Af,
+        Flag::Zf,
+        Flag::Sf,
+        Flag::Of,
+        Flag::Df,
+        Flag::If,
+        Flag::Tf,
+        Flag::Ac,
+        Flag::C0,
+        Flag::C1,
+        Flag::C2,
+        Flag::C3,
+    ];
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Flag::Cf => "CF",
+            Flag::Pf => "PF",
+            Flag::Af => "AF",
+            Flag::Zf => "ZF",
+            Flag::Sf => "SF",
+            Flag::Of => "OF",
+            Flag::Df => "DF",
+            Flag::If => "IF",
+            Flag::Tf => "TF",
+            Flag::Ac => "AC",
+            Flag::C0 => "C0",
+            Flag::C1 => "C1",
+            Flag::C2 => "C2",
+            Flag::C3 => "C3",
+        }
+    }
+}
+
+/// 플래그가 어떻게 바뀌는지
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FlagEffect {
+    /// 결과에 따라 설정
+    SetByResult,
+    /// 0으로 설정
+    Cleared,
+    /// 1로 설정
+    Set,
+    Undefined,
+    Unaffected,
+    /// 조건에 따라 다름 (if ... otherwise ...)
+    Conditional,
+}
+
+impl FlagEffect {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            FlagEffect::SetByResult => "set by result",
+            FlagEffect::Cleared => "cleared",
+            FlagEffect::Set => "set",
+            FlagEffect::Undefined => "undefined",
+            FlagEffect::Unaffected => "unaffected",
+            FlagEffect::Conditional => "conditional",
+        }
+    }
+}
+
+/// Flags Affected 섹션에서 파싱한 플래그 하나의 효과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FlagEffectEntry {
+    pub(crate) flag: Flag,
+    pub(crate) effect: FlagEffect,
+    /// 근거가 된 원문 문장
+    pub(crate) source: String,
+}
+
+/// 예외 섹션의 동작 모드 (출력 순서대로 정렬됨)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ExceptionMode {
+    FloatingPoint,
+    SimdFloatingPoint,
+    Protected,
+    RealAddress,
+    Virtual8086,
+    Compatibility,
+    Bit64,
+    /// 기타 섹션 - 제목 유지
+    Other(String),
+}
+
+impl ExceptionMode {
+    /// 섹션 제목에서 모드 추출 (e.g., "Protected Mode Exceptions")
+    pub(crate) fn from_heading(heading: &str) -> Self {
+        Self::from_text(heading).unwrap_or_else(|| ExceptionMode::Other(heading.trim().to_owned()))
+    }
+    /// 문장 안의 모드 이름 인식 (e.g., "Same exceptions as in protected mode.")
+    pub(crate) fn from_text(text: &str) -> Option<Self> {
+        let lower = text.to_lowercase().replace('-', " ");
+        let mode = if lower.contains("simd floating point") {
+            ExceptionMode::SimdFloatingPoint
+        } else if lower.contains("floating point") || lower.contains("numeric") {
+            ExceptionMode::FloatingPoint
+        } else if lower.contains("protected mode") {
+            ExceptionMode::Protected
+        } else if lower.contains("real address") || lower.contains("real mode") {
+            ExceptionMode::RealAddress
+        } else if lower.contains("virtual 8086") {
+            ExceptionMode::Virtual8086
+        } else if lower.contains("compatibility mode") {
+            ExceptionMode::Compatibility
+        } else if lower.contains("64 bit mode") {
+            ExceptionMode::Bit64
+        } else {
+            return None;
+        };
+        Some(mode)
+    }
+    /// 섹션 제목 (from_heading의 역)
+    pub(crate) fn heading(&self) -> &str {
+        match self {
+            ExceptionMode::FloatingPoint => "Floating-Point Exceptions",
+            ExceptionMode::SimdFloatingPoint => "SIMD Floating-Point Exceptions",
+            ExceptionMode::Protected => "Protected Mode Exceptions",
+            ExceptionMode::RealAddress => "Real-Address Mode Exceptions",
+            ExceptionMode::Virtual8086 => "Virtual-8086 Mode Exceptions",
+            ExceptionMode::Compatibility => "Compatibility Mode Exceptions",
+            ExceptionMode::Bit64 => "64-Bit Mode Exceptions",
+            ExceptionMode::Other(heading) => heading,
+        }
+    }
+}
+
+/// 예외 벡터 (e.g., #GP(0), #UD, #PF(fault-code))
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ExceptionVector {
+    /// # 뒤의 이름 (GP, UD, PF, AC, IS 등)
+    pub(crate) name: String,
+    /// 괄호 안의 에러 코드 (0, fault-code, selector 등)
+    pub(crate) error_code: Option<String>,
+}
+
+impl ExceptionVector {
+    pub(crate) fn text(&self) -> String {
+        match &self.error_code {
+            Some(code) => format!("#{}({code})", self.name),
+            None => format!("#{}", self.name),
+        }
+    }
+}
+
+/// 예외 목록 대신 쓰인 참조
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ExceptionReference {
+    /// "See Table 2-21, Type 4 Class Exception Conditions"
+    Class {
+        /// 테이블 번호 (2-21)
+        table: Option<String>,
+        /// 예외 클래스 (4, E4, E9NF 등)
+        class: String,
+    },
+    /// "Same exceptions as in protected mode."
+    Mode(ExceptionMode),
+}
+
+/// 예외 섹션의 한 항목
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ExceptionEntry {
+    pub(crate) mode: ExceptionMode,
+    /// 예외 벡터 - 참조나 설명 문장이면 None
+    pub(crate) vector: Option<ExceptionVector>,
+    /// 발생 조건 (벡터가 없으면 원문)
+    pub(crate) condition: String,
+    pub(crate) reference: Option<ExceptionReference>,
+}
+
+/// 예외 클래스 테이블 (Vol.2 2장, e.g., "Table 2-21. Type 4 Class Exception Conditions")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ExceptionClass {
+    /// 테이블 번호 (2-21)
+    pub(crate) table: String,
+    /// 예외 클래스 (4, E4, E9NF 등)
+    pub(crate) class: String,
+    pub(crate) entries: Vec<ExceptionClassEntry>,
+}
+
+/// 예외 클래스 테이블의 한 행
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ExceptionClassEntry {
+    pub(crate) vector: ExceptionVector,
+    /// 해당 예외가 발생하는 모드 (X 표시된 열)
+    pub(crate) modes: Vec<ExceptionMode>,
+    /// 발생 조건 (Cause of Exception)
+    pub(crate) condition: String,
+}
+
 #[derive(Debug, Default, Clone)]
 pub(crate) struct Instruction {
     /// 메인 인스트럭션 (AAA)
@@ -44,14 +736,20 @@ pub(crate) struct Instruction {
     pub(crate) summary: String,
     /// 인스트럭션 변형별 설명 (인스트럭션 니모닉, 설명)
     pub(crate) instructions: Vec<(String, String)>,
+    /// 인스트럭션 변형별 인코딩
+    pub(crate) variants: Vec<InstructionVariant>,
     /// 상세설명
     pub(crate) description: Vec<String>,
     /// c 가상코드
     pub(crate) operation: String,
     /// 영향 받는 플래그
     pub(crate) flag_affected: String,
+    /// 플래그별 효과 (Flags Affected 파싱 결과)
+    pub(crate) flags: Vec<FlagEffectEntry>,
     /// 오류
     pub(crate) exceptions: HashMap<String, Vec<String>>,
+    /// 예외 파싱 결과 (모드 순서대로)
+    pub(crate) exception_entries: Vec<ExceptionEntry>,
     /// c/c++ 대체함수
     pub(crate) c_and_cpp_equivalent: Vec<String>,
     /// 기타 섹션 (섹션 이름, 본문 라인) - 등장 순서 유지
@@ -175,6 +873,15 @@ impl Instruction {
             }
         }
 
+        // 변형별 인코딩
+        let encodings = self.encodings_to_table();
+        if !encodings.rows.is_empty() {
+            result.push("".to_owned());
+            result.push("## Encoding".to_owned());
+            result.push("".to_owned());
+            result.append(&mut encodings.to_md_lines());
+        }
+
         // 테이블 (Opcode, Instruction Operand Encoding 등)
         for (section_name, table) in &self.tables {
             result.push("".to_owned());
@@ -209,6 +916,10 @@ impl Instruction {
             result.push("## Flags Affected".to_owned());
             result.push("".to_owned());
             result.push(self.flag_affected.clone());
+            if !self.flags.is_empty() {
+                result.push("".to_owned());
+                result.append(&mut self.flags_to_table().to_md_lines());
+            }
         }
 
         // 기타 섹션
@@ -228,11 +939,40 @@ impl Instruction {
             result.push("".to_owned());
             result.push("## Exceptions".to_owned());
             result.push("".to_owned());
-            for (kind, exceptions) in &self.exceptions {
+            // HashMap 순서 대신 모드 순서로 출력
+            let mut kinds: Vec<(ExceptionMode, &str)> = self
+                .exceptions
+                .keys()
+                .map(|kind| (ExceptionMode::from_heading(kind), kind.as_str()))
+                .collect();
+            // 예외 클래스 확장으로 생긴 모드 섹션
+            for entry in &self.exception_entries {
+                if !kinds.iter().any(|(mode, _)| *mode == entry.mode) {
+                    kinds.push((entry.mode.clone(), entry.mode.heading()));
+                }
+            }
+            kinds.sort();
+            kinds.dedup_by(|a, b| a.0 == b.0);
+            for (mode, kind) in kinds {
                 result.push(format!("### {kind}"));
                 result.push("".to_owned());
-                for exception in exceptions {
-                    result.push(format!("- {exception}"));
+                let entries: Vec<&ExceptionEntry> = self
+                    .exception_entries
+                    .iter()
+                    .filter(|entry| entry.mode == mode)
+                    .collect();
+                if entries.is_empty() {
+                    for exception in self.exceptions.get(kind).into_iter().flatten() {
+                        result.push(format!("- {exception}"));
+                    }
+                }
+                for entry in entries {
+                    match &entry.vector {
+                        Some(vector) => {
+                            result.push(format!("- `{}` — {}", vector.text(), entry.condition))
+                        }
+                        None => result.push(format!("- {}", entry.condition)),
+                    }
                 }
                 result.push("".to_owned());
             }
@@ -268,7 +1008,7 @@ impl Instruction {
             })
             .collect()
     }
-    fn get_reformed_operation(&self) -> Vec<String> {
+    pub(crate) fn get_reformed_operation(&self) -> Vec<String> {
         self.operation
             .lines()
             .map(|line| postprocess_operation_line(line))
@@ -0,0 +1,378 @@
+use super::{build_tables_from_cells, is_header_footer};
+use crate::intel::result::{
+    ExceptionClass, ExceptionClassEntry, ExceptionEntry, ExceptionMode, ExceptionReference,
+    ExceptionVector, Instruction, MdTable,
+};
+use crate::pdf::v2::*;
+use regex::Regex;
+use std::sync::OnceLock;
+use tracing::debug;
+
+/// Parse a class table caption: "Table 2-21. Type 4 Class Exception Conditions (Contd.)"
+/// Returns (table number, class).
+fn parse_caption(text: &str) -> Option<(String, String)> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(
+            r"Table\s+(\d+-\d+)\.?\s+Type\s+([A-Z]*\d+[A-Za-z]*)\s+Class\s+Exception\s+Conditions",
+        )
+        .unwrap()
+    });
+    let captures = regex.captures(text)?;
+    Some((captures[1].to_owned(), captures[2].to_owned()))
+}
+
+/// Check if the table is a class table ("Exception | Real | Virtual-8086 | ... | Cause of Exception")
+fn is_class_table(table: &MdTable) -> bool {
+    table
+        .headers
+        .first()
+        .is_some_and(|h| h.trim().eq_ignore_ascii_case("Exception"))
+        && table
+            .headers
+            .iter()
+            .any(|h| h.to_lowercase().starts_with("cause"))
+}
+
+/// Modes of a class table mark column
+fn column_modes(header: &str) -> Vec<ExceptionMode> {
+    let lower = header.to_lowercase().replace('-', " ");
+    if lower.starts_with("real") {
+        vec![ExceptionMode::RealAddress]
+    } else if lower.starts_with("virtual") {
+        vec![ExceptionMode::Virtual8086]
+    } else if lower.starts_with("protected") && lower.contains("compatibility") {
+        vec![ExceptionMode::Protected, ExceptionMode::Compatibility]
+    } else if lower.starts_with("protected") {
+        vec![ExceptionMode::Protected]
+    } else if lower.starts_with("compatibility") {
+        vec![ExceptionMode::Compatibility]
+    } else if lower.starts_with("64") {
+        vec![ExceptionMode::Bit64]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Parse the vector of an "Exception" cell ("Invalid Opcode, #UD", "Stack, SS(0)")
+fn parse_cell_vector(cell: &str) -> Option<ExceptionVector> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    static FALLBACK: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"#([A-Z]{1,3})(?:\s*\(([^)]*)\))?").unwrap());
+    // Some cells drop the '#': "Stack, SS(0)"
+    let fallback = FALLBACK.get_or_init(|| Regex::new(r"\b([A-Z]{2})\s*\(([^)]*)\)").unwrap());
+    let captures = regex.captures(cell).or_else(|| fallback.captures(cell))?;
+    Some(ExceptionVector {
+        name: captures[1].to_owned(),
+        error_code: captures.get(2).map(|m| m.as_str().trim().to_owned()),
+    })
+}
+
+/// Parse the rows of a class table.
+/// The "Exception" cell spans every cause row of that exception, so it only lands in
+/// the row holding its center; the other rows take the nearest named row.
+fn parse_class_rows(table: &MdTable) -> Vec<ExceptionClassEntry> {
+    let Some(cause_idx) = table
+        .headers
+        .iter()
+        .position(|h| h.to_lowercase().starts_with("cause"))
+    else {
+        return Vec::new();
+    };
+    let mode_columns: Vec<(usize, Vec<ExceptionMode>)> = table
+        .headers
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, h)| (i, column_modes(h)))
+        .filter(|(_, modes)| !modes.is_empty())
+        .collect();
+
+    let mut rows: Vec<(Option<ExceptionVector>, Vec<ExceptionMode>, String)> = Vec::new();
+    for row in &table.rows {
+        let vector = row.first().and_then(|cell| parse_cell_vector(cell));
+        let condition = row.get(cause_idx).map(|s| s.trim()).unwrap_or("");
+        if condition.is_empty() {
+            continue;
+        }
+        let mut modes: Vec<ExceptionMode> = mode_columns
+            .iter()
+            .filter(|(i, _)| row.get(*i).is_some_and(|cell| !cell.trim().is_empty()))
+            .flat_map(|(_, modes)| modes.iter().cloned())
+            .collect();
+        modes.sort();
+        rows.push((vector, modes, condition.to_owned()));
+    }
+
+    let named: Vec<usize> = (0..rows.len()).filter(|&i| rows[i].0.is_some()).collect();
+    let mut result = Vec::new();
+    for (i, (vector, modes, condition)) in rows.iter().enumerate() {
+        let vector = match vector {
+            Some(vector) => vector.clone(),
+            None => {
+                let before = named.iter().rev().find(|&&n| n < i);
+                let after = named.iter().find(|&&n| n > i);
+                let nearest = match (before, after) {
+                    (Some(&b), Some(&a)) => Some(if i - b <= a - i { b } else { a }),
+                    (b, a) => b.or(a).copied(),
+                };
+                match nearest.and_then(|n| rows[n].0.clone()) {
+                    Some(vector) => vector,
+                    None => continue,
+                }
+            }
+        };
+        result.push(ExceptionClassEntry {
+            vector,
+            modes: modes.clone(),
+            condition: condition.clone(),
+        });
+    }
+    result
+}
+
+/// Extract the exception class tables (Type 1-12, E1-E12 ...) from the Vol.2 chapter 2 pages.
+/// A table without a caption above it continues the previous page's class table.
+pub(crate) fn parse_exception_classes(
+    mut d: Vec<(Vec<PdfString>, PdfBoxes)>,
+) -> Vec<ExceptionClass> {
+    let mut result: Vec<ExceptionClass> = Vec::new();
+    let mut last_caption: Option<(String, String)> = None;
+
+    for (sorted_strings, boxes) in &mut d {
+        boxes.prepare_cells();
+        if sorted_strings.is_empty() {
+            continue;
+        }
+        let page_y_range = sorted_strings
+            .iter()
+            .fold((f32::MAX, f32::MIN), |range, s| {
+                let r = s.rect();
+                (range.0.min(r.min().y), range.1.max(r.max().y))
+            });
+
+        let (mut page_tables, _) = build_tables_from_cells(sorted_strings, boxes);
+        page_tables.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+        let mut previous_top = f32::MAX;
+        for (table_top_y, table) in page_tables {
+            // Closest caption between this table and the previous one
+            let caption = sorted_strings
+                .iter()
+                .filter(|s| !is_header_footer(s, page_y_range))
+                .filter(|s| {
+                    let y = s.rect().center().y;
+                    y > table_top_y && y < previous_top
+                })
+                .min_by(|a, b| {
+                    let da = a.rect().center().y - table_top_y;
+                    let db = b.rect().center().y - table_top_y;
+                    da.partial_cmp(&db).unwrap()
+                })
+                .and_then(|s| parse_caption(&s.get()));
+            previous_top = table_top_y;
+            if caption.is_some() {
+                last_caption = caption;
+            } else if !is_class_table(&table) {
+                // Any other table ends the class table
+                last_caption = None;
+                continue;
+            }
+            let Some((table_number, class)) = last_caption.clone() else {
+                continue;
+            };
+            if !is_class_table(&table) {
+                continue;
+            }
+
+            let entries = parse_class_rows(&table);
+            match result.iter_mut().find(|c| c.table == table_number) {
+                Some(existing) => existing.entries.extend(entries),
+                None => result.push(ExceptionClass {
+                    table: table_number,
+                    class,
+                    entries,
+                }),
+            }
+        }
+    }
+
+    debug!("Parsed {} exception classes", result.len());
+    result
+}
+
+/// Find the class table of a reference: by table number, then by class name
+/// (table numbers shift between SDM revisions, class names don't).
+fn find_class<'a>(
+    classes: &'a [ExceptionClass],
+    table: Option<&str>,
+    class: &str,
+) -> Option<&'a ExceptionClass> {
+    classes
+        .iter()
+        .find(|c| table == Some(c.table.as_str()) && c.class == class)
+        .or_else(|| classes.iter().find(|c| c.class == class))
+}
+
+/// Expand "See Table 2-21, Type 4 Class Exception Conditions" references inline.
+/// The reference entry is kept; the class rows are added for every mode they apply to,
+/// or only for the section's mode when the reference sits in a mode section.
+pub(crate) fn expand_exception_classes(
+    instructions: &mut [Instruction],
+    classes: &[ExceptionClass],
+) {
+    for instruction in instructions.iter_mut() {
+        let mut expanded: Vec<ExceptionEntry> = Vec::new();
+        for entry in &instruction.exception_entries {
+            let Some(ExceptionReference::Class { table, class }) = &entry.reference else {
+                continue;
+            };
+            let Some(exception_class) = find_class(classes, table.as_deref(), class) else {
+                debug!("{}: unknown exception class {class}", instruction.title);
+                continue;
+            };
+            for row in &exception_class.entries {
+                let modes: Vec<&ExceptionMode> = match &entry.mode {
+                    ExceptionMode::Other(_)
+                    | ExceptionMode::FloatingPoint
+                    | ExceptionMode::SimdFloatingPoint => row.modes.iter().collect(),
+                    mode => row.modes.iter().filter(|m| *m == mode).collect(),
+                };
+                for mode in modes {
+                    let entry = ExceptionEntry {
+                        mode: mode.clone(),
+                        vector: Some(row.vector.clone()),
+                        condition: row.condition.clone(),
+                        reference: entry.reference.clone(),
+                    };
+                    if !instruction.exception_entries.contains(&entry) && !expanded.contains(&entry)
+                    {
+                        expanded.push(entry);
+                    }
+                }
+            }
+        }
+        if expanded.is_empty() {
+            continue;
+        }
+        instruction.exception_entries.append(&mut expanded);
+        instruction
+            .exception_entries
+            .sort_by(|a, b| a.mode.cmp(&b.mode));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class_table() -> MdTable {
+        let row = |cells: &[&str]| cells.iter().map(|c| (*c).to_owned()).collect();
+        MdTable {
+            headers: row(&[
+                "Exception",
+                "Real",
+                "Virtual-8086",
+                "Protected and Compatibility",
+                "64-bit",
+                "Cause of Exception",
+            ]),
+            rows: vec![
+                row(&["", "X", "X", "", "", "If CR0.EM[bit 2] = 1."]),
+                row(&[
+                    "Invalid Opcode, #UD",
+                    "X",
+                    "X",
+                    "X",
+                    "X",
+                    "If preceded by a LOCK prefix (F0H).",
+                ]),
+                row(&[
+                    "",
+                    "",
+                    "",
+                    "X",
+                    "X",
+                    "If any REX, F2, F3, or 66 prefixes precede a VEX prefix.",
+                ]),
+                row(&[
+                    "Stack, SS(0)",
+                    "",
+                    "",
+                    "",
+                    "X",
+                    "For an illegal address in the SS segment.",
+                ]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_parse_class_rows() {
+        assert_eq!(
+            parse_caption("Table 2-21. Type 4 Class Exception Conditions (Contd.)"),
+            Some(("2-21".to_owned(), "4".to_owned()))
+        );
+        let entries = parse_class_rows(&class_table());
+        assert_eq!(entries.len(), 4);
+        assert!(entries[..3].iter().all(|e| e.vector.text() == "#UD"));
+        assert_eq!(
+            entries[0].modes,
+            vec![ExceptionMode::RealAddress, ExceptionMode::Virtual8086]
+        );
+        assert_eq!(
+            entries[2].modes,
+            vec![
+                ExceptionMode::Protected,
+                ExceptionMode::Compatibility,
+                ExceptionMode::Bit64
+            ]
+        );
+        assert_eq!(entries[3].vector.text(), "#SS(0)");
+    }
+
+    #[test]
+    fn test_expand_exception_classes() {
+        let classes = vec![ExceptionClass {
+            table: "2-21".to_owned(),
+            class: "4".to_owned(),
+            entries: parse_class_rows(&class_table()),
+        }];
+        let reference = |mode: ExceptionMode| ExceptionEntry {
+            mode,
+            vector: None,
+            condition: "See Table 2-21, “Type 4 Class Exception Conditions”.".to_owned(),
+            reference: Some(ExceptionReference::Class {
+                table: Some("2-21".to_owned()),
+                class: "4".to_owned(),
+            }),
+        };
+
+        let mut instructions = vec![Instruction {
+            exception_entries: vec![reference(ExceptionMode::Other(
+                "Other Exceptions".to_owned(),
+            ))],
+            ..Default::default()
+        }];
+        expand_exception_classes(&mut instructions, &classes);
+        let entries = &instructions[0].exception_entries;
+        assert_eq!(
+            entries
+                .iter()
+                .filter(|e| e.mode == ExceptionMode::Bit64)
+                .count(),
+            3
+        );
+        assert_eq!(
+            entries.last().unwrap().mode,
+            ExceptionMode::Other("Other Exceptions".to_owned())
+        );
+
+        // Inside a mode section only that mode's rows are added
+        let mut instructions = vec![Instruction {
+            exception_entries: vec![reference(ExceptionMode::RealAddress)],
+            ..Default::default()
+        }];
+        expand_exception_classes(&mut instructions, &classes);
+        assert_eq!(instructions[0].exception_entries.len(), 3);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::intel::result::{ExceptionEntry, ExceptionMode, ExceptionReference, ExceptionVector};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Parse a leading exception vector: "#GP(0) If ...", "#UD", "#PF(fault-code) ..."
+/// Returns the vector and the remaining condition text.
+fn parse_vector(line: &str) -> Option<(ExceptionVector, String)> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex =
+        REGEX.get_or_init(|| Regex::new(r"^#([A-Z]{1,3})(?:\s*\(([^)]*)\))?\s*(.*)$").unwrap());
+    let captures = regex.captures(line.trim())?;
+    let vector = ExceptionVector {
+        name: captures[1].to_owned(),
+        error_code: captures.get(2).map(|m| m.as_str().trim().to_owned()),
+    };
+    Some((vector, captures[3].trim().to_owned()))
+}
+
+/// Parse "See Table 2-21, “Type 4 Class Exception Conditions”" style references
+fn parse_class_reference(line: &str) -> Option<ExceptionReference> {
+    static CLASS_REGEX: OnceLock<Regex> = OnceLock::new();
+    static EXCEPTIONS_TYPE_REGEX: OnceLock<Regex> = OnceLock::new();
+    static TABLE_REGEX: OnceLock<Regex> = OnceLock::new();
+    let class_regex =
+        CLASS_REGEX.get_or_init(|| Regex::new(r"Type\s+([A-Z]*\d+[A-Za-z]*)\s+Class").unwrap());
+    let exceptions_type_regex = EXCEPTIONS_TYPE_REGEX
+        .get_or_init(|| Regex::new(r"Exceptions\s+Type\s+([A-Z]*\d+[A-Za-z]*)").unwrap());
+    let table_regex = TABLE_REGEX.get_or_init(|| Regex::new(r"Table\s+(\d+-\d+)").unwrap());
+
+    let class = class_regex
+        .captures(line)
+        .or_else(|| exceptions_type_regex.captures(line))?[1]
+        .to_owned();
+    let table = table_regex.captures(line).map(|c| c[1].to_owned());
+    Some(ExceptionReference::Class { table, class })
+}
+
+/// Parse "Same exceptions as in protected mode." / "Same as for protected mode exceptions."
+fn parse_mode_reference(line: &str) -> Option<ExceptionReference> {
+    if !line.trim_start().to_lowercase().starts_with("same ") {
+        return None;
+    }
+    ExceptionMode::from_text(line).map(ExceptionReference::Mode)
+}
+
+/// Parse the raw exception lines of one mode section.
+/// A line that starts with a vector begins a new entry, following lines without a
+/// vector continue its condition (the PDF wraps long conditions over several lines).
+fn parse_exception_lines(mode: &ExceptionMode, lines: &[String]) -> Vec<ExceptionEntry> {
+    let mut result: Vec<ExceptionEntry> = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some((vector, condition)) = parse_vector(line) {
+            result.push(ExceptionEntry {
+                mode: mode.clone(),
+                vector: Some(vector),
+                condition,
+                reference: None,
+            });
+            continue;
+        }
+        let reference = parse_class_reference(line).or_else(|| parse_mode_reference(line));
+        if reference.is_some() {
+            result.push(ExceptionEntry {
+                mode: mode.clone(),
+                vector: None,
+                condition: line.to_owned(),
+                reference,
+            });
+            continue;
+        }
+        match result.last_mut() {
+            // Continuation of the previous condition / reference sentence
+            Some(last) if last.vector.is_some() || last.reference.is_some() => {
+                if !last.condition.is_empty() {
+                    last.condition.push(' ');
+                }
+                last.condition.push_str(line);
+                // "See Table 2-21," / "“Type 4 Class Exception Conditions”" may be split
+                if last.vector.is_none() {
+                    if let Some(reference) = parse_class_reference(&last.condition) {
+                        last.reference = Some(reference);
+                    }
+                }
+            }
+            _ => result.push(ExceptionEntry {
+                mode: mode.clone(),
+                vector: None,
+                condition: line.to_owned(),
+                reference: None,
+            }),
+        }
+    }
+    result
+}
+
+/// Parse every exception section of an instruction, in stable mode order.
+pub(super) fn parse_exceptions(exceptions: &HashMap<String, Vec<String>>) -> Vec<ExceptionEntry> {
+    let mut sections: Vec<(ExceptionMode, &Vec<String>)> = exceptions
+        .iter()
+        .map(|(kind, lines)| (ExceptionMode::from_heading(kind), lines))
+        .collect();
+    sections.sort_by(|a, b| a.0.cmp(&b.0));
+    sections
+        .into_iter()
+        .flat_map(|(mode, lines)| parse_exception_lines(&mode, lines))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|l| (*l).to_owned()).collect()
+    }
+
+    #[test]
+    fn test_parse_exception_lines() {
+        let entries = parse_exception_lines(
+            &ExceptionMode::Protected,
+            &lines(&[
+                "#GP(0) If a memory operand effective address is outside the CS, DS,",
+                "ES, FS, or GS segment limit.",
+                "#PF(fault-code) If a page fault occurs.",
+                "#UD If the LOCK prefix is used.",
+            ]),
+        );
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].vector.as_ref().unwrap().text(), "#GP(0)");
+        assert_eq!(
+            entries[0].condition,
+            "If a memory operand effective address is outside the CS, DS, ES, FS, or GS segment limit."
+        );
+        assert_eq!(
+            entries[1].vector.as_ref().unwrap().error_code.as_deref(),
+            Some("fault-code")
+        );
+        assert_eq!(entries[2].vector.as_ref().unwrap().error_code, None);
+    }
+
+    #[test]
+    fn test_parse_exception_references() {
+        let entries = parse_exception_lines(
+            &ExceptionMode::Bit64,
+            &lines(&[
+                "See Table 2-21, “Type 4 Class Exception Conditions,” additionally:",
+                "#UD If VEX.L = 1.",
+            ]),
+        );
+        assert_eq!(
+            entries[0].reference,
+            Some(ExceptionReference::Class {
+                table: Some("2-21".to_owned()),
+                class: "4".to_owned(),
+            })
+        );
+        assert_eq!(entries[1].vector.as_ref().unwrap().name, "UD");
+
+        let entries = parse_exception_lines(
+            &ExceptionMode::Compatibility,
+            &lines(&["Same exceptions as in protected mode."]),
+        );
+        assert_eq!(
+            entries[0].reference,
+            Some(ExceptionReference::Mode(ExceptionMode::Protected))
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use crate::intel::result::{Flag, FlagEffect, FlagEffectEntry};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Split "Flags Affected" prose into sentences, keeping the trailing period.
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        current.push(c);
+        if c == '.' && chars.peek().is_none_or(|n| n.is_whitespace()) {
+            let sentence = current.trim().to_owned();
+            if !sentence.is_empty() {
+                result.push(sentence);
+            }
+            current.clear();
+        }
+    }
+    let rest = current.trim();
+    if !rest.is_empty() {
+        result.push(rest.to_owned());
+    }
+    result
+}
+
+/// Classify the effect described by a single clause
+fn classify_clause(clause: &str) -> Option<FlagEffect> {
+    let lower = format!(" {} ", clause.to_lowercase());
+    let has = |patterns: &[&str]| patterns.iter().any(|p| lower.contains(p));
+
+    if has(&[
+        " if ",
+        "otherwise",
+        " only ",
+        "depending",
+        " when ",
+        " unless ",
+    ]) {
+        Some(FlagEffect::Conditional)
+    } else if has(&["undefined"]) {
+        Some(FlagEffect::Undefined)
+    } else if has(&["unaffected", "not affected", "not modified", "unchanged"]) {
+        Some(FlagEffect::Unaffected)
+    } else if has(&[
+        // "set to the value of the bit shifted out" is not a constant 1
+        "set to the value",
+        "set according",
+        "according to",
+        "set or cleared",
+        "contain",
+        "receive",
+        "reflect",
+        "filled",
+        "loaded",
+        "modified",
+        "affected",
+        "updated",
+    ]) {
+        Some(FlagEffect::SetByResult)
+    } else if has(&["cleared", "set to 0", " are 0", " is 0", " reset"]) {
+        Some(FlagEffect::Cleared)
+    } else if has(&["set to 1", " is set", " are set", " set "]) {
+        Some(FlagEffect::Set)
+    } else {
+        None
+    }
+}
+
+fn flag_regex() -> &'static Regex {
+    static FLAG_REGEX: OnceLock<Regex> = OnceLock::new();
+    FLAG_REGEX
+        .get_or_init(|| Regex::new(r"\b(CF|PF|AF|ZF|SF|OF|DF|IF|TF|AC|C0|C1|C2|C3)\b").unwrap())
+}
+
+fn parse_flag(name: &str) -> Option<Flag> {
+    Flag::ALL.iter().find(|flag| flag.as_str() == name).copied()
+}
+
+/// Add an effect for a flag, merging with an earlier mention of the same flag.
+/// Different effects for the same flag mean the effect depends on a condition.
+fn record(entries: &mut Vec<FlagEffectEntry>, flag: Flag, effect: FlagEffect, source: &str) {
+    if let Some(entry) = entries.iter_mut().find(|entry| entry.flag == flag) {
+        if entry.effect != effect {
+            entry.effect = FlagEffect::Conditional;
+        }
+        if !entry.source.contains(source) {
+            entry.source.push(' ');
+            entry.source.push_str(source);
+        }
+    } else {
+        entries.push(FlagEffectEntry {
+            flag,
+            effect,
+            source: source.to_owned(),
+        });
+    }
+}
+
+/// Parse the Intel "Flags Affected" / "FPU Flags Affected" prose into a per-flag effect table.
+/// e.g. "The OF and CF flags are cleared; the SF, ZF, and PF flags are set according to
+/// the result. The state of the AF flag is undefined."
+/// Clauses that name no flag ("otherwise they are set to 0") apply to the flags of the
+/// previous clause.
+pub(super) fn parse_flags_affected(text: &str) -> Vec<FlagEffectEntry> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Vec::new();
+    }
+
+    let mut entries: Vec<FlagEffectEntry> = Vec::new();
+    if text.trim_end_matches('.').eq_ignore_ascii_case("None") {
+        for flag in [Flag::Cf, Flag::Pf, Flag::Af, Flag::Zf, Flag::Sf, Flag::Of] {
+            record(&mut entries, flag, FlagEffect::Unaffected, text);
+        }
+        return entries;
+    }
+
+    let mut last_flags: Vec<Flag> = Vec::new();
+    for sentence in split_sentences(text) {
+        for clause in sentence.split(';') {
+            let flags: Vec<Flag> = flag_regex()
+                .find_iter(clause)
+                .filter_map(|m| parse_flag(m.as_str()))
+                .collect();
+            let Some(effect) = classify_clause(clause) else {
+                continue;
+            };
+            if !flags.is_empty() {
+                last_flags = flags;
+            }
+            for flag in last_flags.iter() {
+                record(&mut entries, *flag, effect, &sentence);
+            }
+        }
+    }
+
+    entries.sort_by_key(|entry| entry.flag);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn effects(text: &str) -> Vec<(&'static str, FlagEffect)> {
+        parse_flags_affected(text)
+            .into_iter()
+            .map(|entry| (entry.flag.as_str(), entry.effect))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_flags_affected() {
+        assert_eq!(
+            effects(
+                "The OF and CF flags are cleared; the SF, ZF, and PF flags are set according \
+                 to the result. The state of the AF flag is undefined."
+            ),
+            vec![
+                ("CF", FlagEffect::Cleared),
+                ("PF", FlagEffect::SetByResult),
+                ("AF", FlagEffect::Undefined),
+                ("ZF", FlagEffect::SetByResult),
+                ("SF", FlagEffect::SetByResult),
+                ("OF", FlagEffect::Cleared),
+            ]
+        );
+        assert_eq!(
+            effects(
+                "The AF and CF flags are set to 1 if the adjustment results in a decimal \
+                 carry; otherwise they are set to 0. The OF, SF, ZF, and PF flags are undefined."
+            ),
+            vec![
+                ("CF", FlagEffect::Conditional),
+                ("PF", FlagEffect::Undefined),
+                ("AF", FlagEffect::Conditional),
+                ("ZF", FlagEffect::Undefined),
+                ("SF", FlagEffect::Undefined),
+                ("OF", FlagEffect::Undefined),
+            ]
+        );
+        assert_eq!(
+            effects(
+                "The CF flag is set to the value of the bit shifted out; the OF flag is \
+                 cleared."
+            ),
+            vec![("CF", FlagEffect::SetByResult), ("OF", FlagEffect::Cleared)]
+        );
+    }
+
+    #[test]
+    fn test_parse_fpu_flags_affected() {
+        assert_eq!(
+            effects("C1 Set to 0 if stack underflow occurred. C0, C2, C3 Undefined."),
+            vec![
+                ("C0", FlagEffect::Undefined),
+                ("C1", FlagEffect::Conditional),
+                ("C2", FlagEffect::Undefined),
+                ("C3", FlagEffect::Undefined),
+            ]
+        );
+        assert_eq!(effects("None.").len(), 6);
+    }
+}
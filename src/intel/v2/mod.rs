@@ -1,4 +1,13 @@
-use crate::intel::result::{Instruction, MdTable};
+mod exception_class;
+mod exceptions;
+mod flags;
+mod mode;
+mod opcode;
+mod operand;
+
+pub(crate) use exception_class::{expand_exception_classes, parse_exception_classes};
+
+use crate::intel::result::{Instruction, InstructionVariant, MdTable};
 use crate::pdf::v2::*;
 use geo::Rect;
 use std::collections::BTreeMap;
@@ -343,11 +352,12 @@ fn detect_section(text: &str) -> CurrentSection {
     CurrentSection::None
 }
 
-/// Extract (instruction_mnemonic, description) pairs from an Instruction table.
+/// Extract (instruction_mnemonic, description) pairs and the per-variant opcode
+/// encodings from an Instruction table.
 /// Handles two column layouts:
 /// 1. Separate "Opcode" and "Instruction" columns (e.g., AAA, PUSH)
 /// 2. Merged "Opcode/ Instruction" column (e.g., ADDPD, MOV)
-fn extract_instruction_variants(table: &MdTable, out: &mut Vec<(String, String)>) {
+fn extract_instruction_variants(table: &MdTable, out: &mut Instruction) {
     if table.headers.is_empty() || table.rows.is_empty() {
         return;
     }
@@ -383,6 +393,17 @@ fn extract_instruction_variants(table: &MdTable, out: &mut Vec<(String, String)>
         let h = table.headers[instr_idx].trim().to_lowercase();
         h.contains("opcode")
     };
+    // Separate layout: the Opcode column is the one whose header starts with "Opcode"
+    let opcode_idx = if is_merged {
+        None
+    } else {
+        table
+            .headers
+            .iter()
+            .position(|h| h.trim().to_lowercase().starts_with("opcode"))
+    };
+    let op_en_idx = operand::find_op_en_column(table);
+    let mode_columns = mode::ModeColumns::find(table);
 
     for row in &table.rows {
         let instr_cell = row.get(instr_idx).map(|s| s.trim()).unwrap_or("");
@@ -391,28 +412,46 @@ fn extract_instruction_variants(table: &MdTable, out: &mut Vec<(String, String)>
             continue;
         }
 
-        let mnemonic = if is_merged {
-            // Extract instruction mnemonic from merged "opcode instruction operands"
+        let (opcode_cell, mnemonic) = if is_merged {
+            // Split merged "opcode instruction operands"
             // e.g., "66 0F 58 /r ADDPD xmm1, xmm2/m128"
-            extract_mnemonic_from_merged(instr_cell)
+            split_merged_opcode_cell(instr_cell)
         } else {
-            instr_cell.to_owned()
+            let opcode_cell = opcode_idx
+                .and_then(|i| row.get(i))
+                .map(|s| s.trim().to_owned())
+                .unwrap_or_default();
+            (opcode_cell, instr_cell.to_owned())
         };
 
         if !mnemonic.is_empty() {
-            out.push((mnemonic, desc_cell.to_owned()));
+            let op_en = op_en_idx
+                .and_then(|i| row.get(i))
+                .map(|s| s.trim().to_owned())
+                .unwrap_or_default();
+            let mode_support = mode_columns.parse_row(row);
+            out.variants.push(InstructionVariant {
+                instruction: mnemonic.clone(),
+                encoding: opcode::parse_opcode(&opcode_cell),
+                op_en,
+                operand_encoding: None,
+                mode_64: mode_support.mode_64,
+                mode_compat_legacy: mode_support.mode_compat_legacy,
+                cpuid_features: mode_support.cpuid_features,
+            });
+            out.instructions.push((mnemonic, desc_cell.to_owned()));
         }
     }
 }
 
-/// Extract the instruction mnemonic + operands from a merged "Opcode/Instruction" cell.
+/// Split a merged "Opcode/Instruction" cell into (opcode, mnemonic + operands).
 /// Input: "66 0F 58 /r ADDPD xmm1, xmm2/m128"
-/// Output: "ADDPD xmm1, xmm2/m128"
+/// Output: ("66 0F 58 /r", "ADDPD xmm1, xmm2/m128")
 ///
 /// Strategy: find the first token that starts with an uppercase letter and is at least
 /// 2 chars long and not a hex byte (like "0F"). Everything from that token onward is
 /// the instruction mnemonic with operands.
-fn extract_mnemonic_from_merged(cell: &str) -> String {
+fn split_merged_opcode_cell(cell: &str) -> (String, String) {
     let tokens: Vec<&str> = cell.split_whitespace().collect();
     for (i, token) in tokens.iter().enumerate() {
         // Skip known opcode prefixes: hex bytes (0F, 66, F2, etc.), /r, /0-/7, ib, iw, id, etc.
@@ -451,11 +490,11 @@ fn extract_mnemonic_from_merged(cell: &str) -> String {
             .next()
             .map_or(false, |c| c.is_ascii_uppercase())
         {
-            return tokens[i..].join(" ");
+            return (tokens[..i].join(" "), tokens[i..].join(" "));
         }
     }
     // Fallback: return the whole cell
-    cell.to_owned()
+    (String::new(), cell.to_owned())
 }
 
 pub(crate) fn parse_instructions(mut d: Vec<(Vec<PdfString>, PdfBoxes)>) -> Vec<Instruction> {
@@ -772,7 +811,7 @@ pub(crate) fn parse_instructions(mut d: Vec<(Vec<PdfString>, PdfBoxes)>) -> Vec<
         if has_tables && has_current {
             for (name, table) in table_sections {
                 if name == "Instruction" && current.instructions.is_empty() {
-                    extract_instruction_variants(&table, &mut current.instructions);
+                    extract_instruction_variants(&table, &mut current);
                 }
                 current.tables.push((name, table));
             }
@@ -789,6 +828,15 @@ pub(crate) fn parse_instructions(mut d: Vec<(Vec<PdfString>, PdfBoxes)>) -> Vec<
         result.push(current);
     }
 
+    // Op/En tables may come after the opcode table (or on the next page),
+    // so link them (and parse the accumulated Flags Affected / Exceptions text) once
+    // every page of the instruction has been collected
+    for instruction in result.iter_mut() {
+        operand::link_operand_encodings(instruction);
+        instruction.flags = flags::parse_flags_affected(&instruction.flag_affected);
+        instruction.exception_entries = exceptions::parse_exceptions(&instruction.exceptions);
+    }
+
     debug!("Parsed {} instructions", result.len());
     result
 }
@@ -0,0 +1,262 @@
+use crate::intel::result::{MdTable, ModeValidity};
+use std::iter::Peekable;
+
+/// Mode/feature columns of an opcode table
+#[derive(Debug, Default)]
+pub(super) struct ModeColumns {
+    /// "64-Bit Mode"
+    mode_64: Option<usize>,
+    /// "Compat/ Leg Mode"
+    compat_legacy: Option<usize>,
+    /// "64/32 bit Mode Support" (VEX/EVEX tables, "V/V" style values)
+    combined: Option<usize>,
+    /// "CPUID Feature Flag"
+    cpuid: Option<usize>,
+}
+
+/// Values parsed from one opcode table row
+pub(super) struct ModeSupport {
+    pub(super) mode_64: Option<ModeValidity>,
+    pub(super) mode_compat_legacy: Option<ModeValidity>,
+    pub(super) cpuid_features: Vec<Vec<String>>,
+}
+
+fn normalize_header(header: &str) -> String {
+    header
+        .split_whitespace()
+        .collect::<String>()
+        .to_lowercase()
+        .replace('-', "")
+}
+
+impl ModeColumns {
+    pub(super) fn find(table: &MdTable) -> Self {
+        let mut columns = ModeColumns::default();
+        for (i, header) in table.headers.iter().enumerate() {
+            let header = normalize_header(header);
+            if header.starts_with("64/32") {
+                columns.combined = Some(i);
+                // Some tables merge the two headers into one cell
+                if header.contains("cpuid") {
+                    columns.cpuid = Some(i);
+                }
+            } else if header.starts_with("64bitmode") {
+                columns.mode_64 = Some(i);
+            } else if header.starts_with("compat") {
+                columns.compat_legacy = Some(i);
+            } else if header.contains("cpuid") {
+                columns.cpuid = Some(i);
+            }
+        }
+        columns
+    }
+
+    pub(super) fn parse_row(&self, row: &[String]) -> ModeSupport {
+        let cell = |idx: Option<usize>| idx.and_then(|i| row.get(i)).map(|s| s.trim());
+
+        let mut mode_64 = cell(self.mode_64).and_then(parse_validity);
+        let mut mode_compat_legacy = cell(self.compat_legacy).and_then(parse_validity);
+        if let Some(combined) = cell(self.combined) {
+            // "V/V", "V/N.E." - combined header may also carry the CPUID flags after the pair
+            let pair = combined.split_whitespace().next().unwrap_or("");
+            if let Some((left, right)) = pair.split_once('/') {
+                mode_64 = mode_64.or_else(|| parse_validity(left));
+                mode_compat_legacy = mode_compat_legacy.or_else(|| parse_validity(right));
+            }
+        }
+
+        let cpuid_features = match cell(self.cpuid) {
+            Some(text) if self.cpuid == self.combined => {
+                // Skip the leading "V/V" pair
+                parse_cpuid_features(text.split_once(char::is_whitespace).map_or("", |x| x.1))
+            }
+            Some(text) => parse_cpuid_features(text),
+            None => Vec::new(),
+        };
+
+        ModeSupport {
+            mode_64,
+            mode_compat_legacy,
+            cpuid_features,
+        }
+    }
+}
+
+/// Parse a single validity value ("V", "Valid", "I", "Invalid", "N.E.", "N.S.", ...)
+fn parse_validity(text: &str) -> Option<ModeValidity> {
+    // Drop footnote markers ("Valid1", "N.E.²") and spacing ("N. E.")
+    let normalized: String = text
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic() || *c == '.')
+        .collect::<String>()
+        .to_uppercase();
+    if normalized.is_empty() {
+        return None;
+    }
+    let validity = match normalized.trim_end_matches('.') {
+        "V" | "VALID" => ModeValidity::Valid,
+        "I" | "INVALID" | "INV" => ModeValidity::Invalid,
+        "N.E" | "NE" => ModeValidity::NotEncodable,
+        "N.S" | "NS" => ModeValidity::NotSupported,
+        "N.P" => ModeValidity::NotPrefixed,
+        "N.I" | "NI" => ModeValidity::NewInstruction,
+        _ => ModeValidity::Other(text.trim().to_owned()),
+    };
+    Some(validity)
+}
+
+/// Parse a "CPUID Feature Flag" cell into groups that are all required, each satisfied by
+/// any one of its flags: "AVX512VL AVX512F" is `[[AVX512VL], [AVX512F]]`, while
+/// "AVX512VL OR AVX10.1" is `[[AVX512VL, AVX10.1]]`. AND (or juxtaposition) binds tighter
+/// than OR, so "(AVX512_FP16 AND AVX512VL) OR AVX10.1" distributes into two groups.
+fn parse_cpuid_features(text: &str) -> Vec<Vec<String>> {
+    let spaced = text.replace('(', " ( ").replace(')', " ) ");
+    let mut tokens = spaced
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|token| match token {
+            "(" | ")" => token,
+            _ => token.trim_end_matches(|c: char| !c.is_ascii_alphanumeric()),
+        })
+        .filter(|token| !token.is_empty())
+        .peekable();
+    let mut groups = Vec::new();
+    while tokens.peek().is_some() {
+        groups.extend(parse_any_of(&mut tokens));
+        // Unbalanced ")"
+        tokens.next_if_eq(&")");
+    }
+    groups
+}
+
+fn is_or(token: &&str) -> bool {
+    matches!(*token, "OR" | "or")
+}
+
+/// `all_of (OR all_of)*`
+fn parse_any_of<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Vec<Vec<String>> {
+    let mut groups = parse_all_of(tokens);
+    while tokens.next_if(is_or).is_some() {
+        let other = parse_all_of(tokens);
+        if groups.is_empty() || other.is_empty() {
+            groups.extend(other);
+            continue;
+        }
+        // (a AND b) OR c = (a OR c) AND (b OR c)
+        groups = groups
+            .iter()
+            .flat_map(|group| {
+                other.iter().map(move |alternatives| {
+                    let mut group = group.clone();
+                    for flag in alternatives {
+                        if !group.contains(flag) {
+                            group.push(flag.clone());
+                        }
+                    }
+                    group
+                })
+            })
+            .collect();
+    }
+    groups
+}
+
+/// Flags and parenthesized expressions up to the next OR or ")"
+fn parse_all_of<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Vec<Vec<String>> {
+    let mut groups = Vec::new();
+    while let Some(token) = tokens.next_if(|token| *token != ")" && !is_or(token)) {
+        match token {
+            "(" => {
+                groups.extend(parse_any_of(tokens));
+                tokens.next_if_eq(&")");
+            }
+            "AND" | "and" => {}
+            flag => groups.push(vec![flag.to_owned()]),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(headers: &[&str], row: &[&str]) -> MdTable {
+        MdTable {
+            headers: headers.iter().map(|h| (*h).to_owned()).collect(),
+            rows: vec![row.iter().map(|c| (*c).to_owned()).collect()],
+        }
+    }
+
+    #[test]
+    fn test_separate_mode_columns() {
+        let table = table(
+            &[
+                "Opcode",
+                "Instruction",
+                "Op/En",
+                "64-Bit Mode",
+                "Compat/ Leg Mode",
+                "Description",
+            ],
+            &[
+                "37",
+                "AAA",
+                "ZO",
+                "Invalid",
+                "Valid",
+                "ASCII adjust AL after addition.",
+            ],
+        );
+        let support = ModeColumns::find(&table).parse_row(&table.rows[0]);
+        assert_eq!(support.mode_64, Some(ModeValidity::Invalid));
+        assert_eq!(support.mode_compat_legacy, Some(ModeValidity::Valid));
+        assert!(support.cpuid_features.is_empty());
+    }
+
+    #[test]
+    fn test_combined_mode_column() {
+        let table = table(
+            &[
+                "Opcode/ Instruction",
+                "Op / En",
+                "64/32 bit Mode Support",
+                "CPUID Feature Flag",
+                "Description",
+            ],
+            &[
+                "EVEX.128.66.0F.W1 58 /r VADDPD xmm1 {k1}{z}, xmm2, xmm3/m128/m64bcst",
+                "A",
+                "V/N.E.",
+                "AVX512VL AVX512F",
+                "Add packed double precision floating-point values.",
+            ],
+        );
+        let support = ModeColumns::find(&table).parse_row(&table.rows[0]);
+        assert_eq!(support.mode_64, Some(ModeValidity::Valid));
+        assert_eq!(support.mode_compat_legacy, Some(ModeValidity::NotEncodable));
+        assert_eq!(
+            support.cpuid_features,
+            vec![vec!["AVX512VL"], vec!["AVX512F"]]
+        );
+    }
+
+    #[test]
+    fn test_parse_cpuid_features_alternatives() {
+        assert_eq!(
+            parse_cpuid_features("AVX512VL OR AVX10.1"),
+            vec![vec!["AVX512VL", "AVX10.1"]]
+        );
+        assert_eq!(
+            parse_cpuid_features("AVX512VL AND AVX512F"),
+            vec![vec!["AVX512VL"], vec!["AVX512F"]]
+        );
+        assert_eq!(
+            parse_cpuid_features("(AVX512_FP16 AND AVX512VL) OR AVX10.1"),
+            vec![vec!["AVX512_FP16", "AVX10.1"], vec!["AVX512VL", "AVX10.1"]]
+        );
+        assert_eq!(
+            parse_cpuid_features("AVX512VL AND (AVX512_BF16 OR AVX10.2)"),
+            vec![vec!["AVX512VL"], vec!["AVX512_BF16", "AVX10.2"]]
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use crate::intel::result::{
+    CodeOffset, ImmediateSize, MandatoryPrefix, ModRm, OpcodeMap, OpcodeRegister, RexPrefix,
+    VectorLength, VectorPrefix, VectorPrefixKind, VvvvRole, WBit, X86Encoding,
+};
+
+/// Legacy prefix bytes that may appear in front of an opcode
+const LEGACY_PREFIX_BYTES: &[u8] = &[
+    0xF0, 0xF2, 0xF3, 0x2E, 0x36, 0x3E, 0x26, 0x64, 0x65, 0x66, 0x67,
+];
+
+/// Parse an Opcode column cell like "66 0F 58 /r", "REX.W + 81 /0 id" or
+/// "EVEX.512.66.0F.W1 58 /r" into a typed encoding.
+/// Unknown tokens are kept in `unparsed` instead of failing the whole cell.
+pub(super) fn parse_opcode(text: &str) -> X86Encoding {
+    let mut encoding = X86Encoding::default();
+    let mut bytes: Vec<u8> = Vec::new();
+
+    let tokens = tokenize(text);
+    for token in tokens.iter() {
+        let token = token.as_str();
+        match token {
+            "REX" => encoding.rex = Some(RexPrefix { w: false }),
+            "REX.W" => encoding.rex = Some(RexPrefix { w: true }),
+            "NP" => encoding.mandatory_prefix = Some(MandatoryPrefix::Np),
+            "/r" => encoding.modrm = Some(ModRm::Reg),
+            "/vsib" => {
+                encoding.modrm = Some(ModRm::Reg);
+                encoding.vsib = true;
+            }
+            "/is4" => encoding.immediates.push(ImmediateSize::Is4),
+            "ib" => encoding.immediates.push(ImmediateSize::Byte),
+            "iw" => encoding.immediates.push(ImmediateSize::Word),
+            "id" => encoding.immediates.push(ImmediateSize::Dword),
+            "io" => encoding.immediates.push(ImmediateSize::Qword),
+            "cb" => encoding.code_offset = Some(CodeOffset::Byte),
+            "cw" => encoding.code_offset = Some(CodeOffset::Word),
+            "cd" => encoding.code_offset = Some(CodeOffset::Dword),
+            "cp" => encoding.code_offset = Some(CodeOffset::Pointer),
+            "co" => encoding.code_offset = Some(CodeOffset::Qword),
+            "ct" => encoding.code_offset = Some(CodeOffset::Tbyte),
+            "+rb" => encoding.opcode_register = Some(OpcodeRegister::Byte),
+            "+rw" => encoding.opcode_register = Some(OpcodeRegister::Word),
+            "+rd" => encoding.opcode_register = Some(OpcodeRegister::Dword),
+            "+ro" => encoding.opcode_register = Some(OpcodeRegister::Qword),
+            "+i" => encoding.opcode_register = Some(OpcodeRegister::FpuStack),
+            _ if token.starts_with("VEX.") => {
+                encoding.vector = Some(parse_vector_prefix(
+                    VectorPrefixKind::Vex,
+                    &token["VEX.".len()..],
+                    &mut encoding.unparsed,
+                ));
+            }
+            _ if token.starts_with("EVEX.") => {
+                encoding.vector = Some(parse_vector_prefix(
+                    VectorPrefixKind::Evex,
+                    &token["EVEX.".len()..],
+                    &mut encoding.unparsed,
+                ));
+            }
+            _ if token.len() == 2 && token.starts_with('/') => match token[1..].parse::<u8>() {
+                Ok(digit) if digit < 8 => encoding.modrm = Some(ModRm::Digit(digit)),
+                _ => encoding.unparsed.push(token.to_owned()),
+            },
+            _ if token.len() == 2 && token.chars().all(|c| c.is_ascii_hexdigit()) => {
+                bytes.push(u8::from_str_radix(token, 16).unwrap());
+            }
+            _ => encoding.unparsed.push(token.to_owned()),
+        }
+    }
+
+    split_prefix_bytes(&mut encoding, bytes);
+    encoding
+}
+
+/// Split a cell into tokens, normalizing the "+" spellings used by the SDM
+/// ("REX.W + 81", "B8+ rd", "B8 +rd", "B8+rd", "D8 C0+i") so that register
+/// suffixes always come out as a separate "+rd" token.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    let raw: Vec<&str> = text.split_whitespace().collect();
+    let mut i = 0;
+    while i < raw.len() {
+        let token = raw[i];
+        i += 1;
+        if token == "+" {
+            continue;
+        }
+        if let Some(base) = token.strip_suffix('+') {
+            // "B8+ rd" → "B8", "+rd" / "REX.W+" → "REX.W"
+            if !base.is_empty() {
+                result.push(base.to_owned());
+            }
+            if let Some(next) = raw.get(i).filter(|n| is_register_suffix(n)) {
+                result.push(format!("+{next}"));
+                i += 1;
+            }
+            continue;
+        }
+        if let Some((base, suffix)) = token.split_once('+') {
+            // "B8+rd" → "B8", "+rd" / "C0+i" → "C0", "+i"
+            if !base.is_empty() {
+                result.push(base.to_owned());
+            }
+            result.push(format!("+{suffix}"));
+            continue;
+        }
+        result.push(token.to_owned());
+    }
+    result
+}
+
+fn is_register_suffix(token: &str) -> bool {
+    matches!(token, "rb" | "rw" | "rd" | "ro" | "i")
+}
+
+/// Parse the dotted fields after "VEX." / "EVEX." (e.g. "NDS.128.66.0F38.W0")
+fn parse_vector_prefix(
+    kind: VectorPrefixKind,
+    fields: &str,
+    unparsed: &mut Vec<String>,
+) -> VectorPrefix {
+    let mut prefix = VectorPrefix {
+        kind,
+        length: None,
+        pp: MandatoryPrefix::Np,
+        map: None,
+        w: None,
+        vvvv: None,
+    };
+    for field in fields.split('.') {
+        match field {
+            "128" | "L0" => prefix.length = Some(VectorLength::L128),
+            "256" | "L1" => prefix.length = Some(VectorLength::L256),
+            "512" => prefix.length = Some(VectorLength::L512),
+            "LZ" | "LLZ" => prefix.length = Some(VectorLength::Zero),
+            "LIG" | "LLIG" => prefix.length = Some(VectorLength::Ignored),
+            "NP" => prefix.pp = MandatoryPrefix::Np,
+            "66" => prefix.pp = MandatoryPrefix::P66,
+            "F2" => prefix.pp = MandatoryPrefix::PF2,
+            "F3" => prefix.pp = MandatoryPrefix::PF3,
+            "0F" => prefix.map = Some(OpcodeMap::Map0F),
+            "0F38" => prefix.map = Some(OpcodeMap::Map0F38),
+            "0F3A" => prefix.map = Some(OpcodeMap::Map0F3A),
+            "W0" => prefix.w = Some(WBit::W0),
+            "W1" => prefix.w = Some(WBit::W1),
+            "WIG" => prefix.w = Some(WBit::Ignored),
+            "NDS" => prefix.vvvv = Some(VvvvRole::Nds),
+            "NDD" => prefix.vvvv = Some(VvvvRole::Ndd),
+            "DDS" => prefix.vvvv = Some(VvvvRole::Dds),
+            _ => match field.strip_prefix("MAP").map(str::parse::<u8>) {
+                Some(Ok(map)) => prefix.map = Some(OpcodeMap::Map(map)),
+                _ => unparsed.push(field.to_owned()),
+            },
+        }
+    }
+    prefix
+}
+
+/// Separate leading prefix bytes from the opcode bytes.
+/// A 66/F2/F3 directly in front of the 0F escape is the mandatory prefix,
+/// any other leading prefix byte is kept as a legacy prefix (e.g. "F3 A4" REP MOVS).
+fn split_prefix_bytes(encoding: &mut X86Encoding, bytes: Vec<u8>) {
+    let mut prefix_len = 0;
+    while prefix_len + 1 < bytes.len() && LEGACY_PREFIX_BYTES.contains(&bytes[prefix_len]) {
+        prefix_len += 1;
+    }
+    let (prefixes, opcode) = bytes.split_at(prefix_len);
+    let mut prefixes = prefixes.to_vec();
+
+    if encoding.vector.is_none() && opcode.first() == Some(&0x0F) {
+        let mandatory = match prefixes.last() {
+            Some(0x66) => Some(MandatoryPrefix::P66),
+            Some(0xF2) => Some(MandatoryPrefix::PF2),
+            Some(0xF3) => Some(MandatoryPrefix::PF3),
+            _ => None,
+        };
+        if let Some(mandatory) = mandatory {
+            prefixes.pop();
+            encoding.mandatory_prefix = Some(mandatory);
+        }
+    }
+
+    encoding.legacy_prefixes = prefixes;
+    encoding.opcode = opcode.to_vec();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_opcode_legacy() {
+        let encoding = parse_opcode("66 0F 58 /r");
+        assert_eq!(encoding.mandatory_prefix, Some(MandatoryPrefix::P66));
+        assert_eq!(encoding.opcode, vec![0x0F, 0x58]);
+        assert_eq!(encoding.modrm, Some(ModRm::Reg));
+        assert!(encoding.unparsed.is_empty());
+
+        let encoding = parse_opcode("REX.W + 81 /0 id");
+        assert_eq!(encoding.rex, Some(RexPrefix { w: true }));
+        assert_eq!(encoding.opcode, vec![0x81]);
+        assert_eq!(encoding.modrm, Some(ModRm::Digit(0)));
+        assert_eq!(encoding.immediates, vec![ImmediateSize::Dword]);
+
+        let encoding = parse_opcode("F3 A4");
+        assert_eq!(encoding.legacy_prefixes, vec![0xF3]);
+        assert_eq!(encoding.mandatory_prefix, None);
+        assert_eq!(encoding.opcode, vec![0xA4]);
+    }
+
+    #[test]
+    fn test_parse_opcode_register_suffix() {
+        for text in ["B8+ rd id", "B8 +rd id", "B8+rd id"] {
+            let encoding = parse_opcode(text);
+            assert_eq!(encoding.opcode, vec![0xB8], "{text}");
+            assert_eq!(
+                encoding.opcode_register,
+                Some(OpcodeRegister::Dword),
+                "{text}"
+            );
+            assert_eq!(encoding.immediates, vec![ImmediateSize::Dword], "{text}");
+        }
+        let encoding = parse_opcode("D8 C0+i");
+        assert_eq!(encoding.opcode, vec![0xD8, 0xC0]);
+        assert_eq!(encoding.opcode_register, Some(OpcodeRegister::FpuStack));
+    }
+
+    #[test]
+    fn test_parse_opcode_vector() {
+        let encoding = parse_opcode("EVEX.512.66.0F.W1 58 /r");
+        let vector = encoding.vector.unwrap();
+        assert_eq!(vector.kind, VectorPrefixKind::Evex);
+        assert_eq!(vector.length, Some(VectorLength::L512));
+        assert_eq!(vector.pp, MandatoryPrefix::P66);
+        assert_eq!(vector.map, Some(OpcodeMap::Map0F));
+        assert_eq!(vector.w, Some(WBit::W1));
+        assert_eq!(encoding.opcode, vec![0x58]);
+
+        let encoding = parse_opcode("VEX.NDS.128.66.0F3A.W0 4B /r /is4");
+        let vector = encoding.vector.unwrap();
+        assert_eq!(vector.vvvv, Some(VvvvRole::Nds));
+        assert_eq!(vector.map, Some(OpcodeMap::Map0F3A));
+        assert_eq!(encoding.immediates, vec![ImmediateSize::Is4]);
+
+        let encoding = parse_opcode("EVEX.128.NP.MAP5.W0 58 /r");
+        assert_eq!(encoding.vector.unwrap().map, Some(OpcodeMap::Map(5)));
+
+        // no pp: NP is not spelled out for vector prefixes
+        let encoding = parse_opcode("VEX.128.0F.WIG 28 /r");
+        assert_eq!(encoding.prefix_text(), "VEX.128.0F.WIG");
+        let encoding = parse_opcode("EVEX.512.66.0F.W1 58 /r");
+        assert_eq!(encoding.prefix_text(), "EVEX.512.66.0F.W1");
+    }
+}
@@ -0,0 +1,220 @@
+use crate::intel::result::{Instruction, MdTable, OperandEncoding, OperandSource, OperandSpec};
+
+/// Normalize an "Op/En" header or cell ("Op/ En", "Op / En") for comparison
+fn normalize_op_en(text: &str) -> String {
+    text.split_whitespace().collect::<String>().to_uppercase()
+}
+
+/// Check if the table is an "Instruction Operand Encoding" table (first column "Op/En")
+pub(super) fn is_operand_encoding_table(table: &MdTable) -> bool {
+    table
+        .headers
+        .first()
+        .is_some_and(|h| normalize_op_en(h) == "OP/EN")
+}
+
+/// Find the "Op/En" column of an opcode table
+pub(super) fn find_op_en_column(table: &MdTable) -> Option<usize> {
+    table
+        .headers
+        .iter()
+        .position(|h| normalize_op_en(h) == "OP/EN")
+}
+
+/// Parse every row of an "Instruction Operand Encoding" table.
+/// Layouts: "Op/En | Operand 1 | ... | Operand 4" and, for EVEX instructions,
+/// "Op/En | Tuple Type | Operand 1 | ... | Operand 4".
+fn parse_operand_encoding_table(table: &MdTable) -> Vec<OperandEncoding> {
+    let tuple_idx = table
+        .headers
+        .iter()
+        .position(|h| h.trim().eq_ignore_ascii_case("Tuple Type"));
+    let operand_idxs: Vec<usize> = table
+        .headers
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| h.trim().to_lowercase().starts_with("operand"))
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut result = Vec::new();
+    for row in &table.rows {
+        let op_en = row.first().map(|s| s.trim()).unwrap_or("");
+        if op_en.is_empty() {
+            continue;
+        }
+        let tuple_type = tuple_idx
+            .and_then(|i| row.get(i))
+            .map(|s| s.trim())
+            .filter(|s| !is_not_applicable(s))
+            .map(str::to_owned);
+        let operands = operand_idxs
+            .iter()
+            .map(|&i| parse_operand_spec(row.get(i).map(|s| s.as_str()).unwrap_or("")))
+            .collect();
+        result.push(OperandEncoding {
+            op_en: op_en.to_owned(),
+            tuple_type,
+            operands,
+        });
+    }
+    result
+}
+
+fn is_not_applicable(text: &str) -> bool {
+    let text = text.trim();
+    text.is_empty() || text.eq_ignore_ascii_case("NA") || text.eq_ignore_ascii_case("N/A")
+}
+
+/// Parse an operand cell like "ModRM:reg (r, w)", "VEX.vvvv (r)" or "imm8".
+/// Returns None for "NA"/"N/A" (operand not present).
+fn parse_operand_spec(cell: &str) -> Option<OperandSpec> {
+    let cell = cell.trim();
+    if is_not_applicable(cell) {
+        return None;
+    }
+
+    // Access is the trailing parenthesized group: "(r)", "(w)", "(r, w)", "(r,w)"
+    let (source_text, read, write) = match cell.rsplit_once('(') {
+        Some((source, access)) if access.trim_end().ends_with(')') => {
+            let flags: Vec<String> = access
+                .trim_end()
+                .trim_end_matches(')')
+                .split(',')
+                .map(|f| f.trim().to_lowercase())
+                .collect();
+            if flags.iter().all(|f| f == "r" || f == "w") {
+                (
+                    source.trim(),
+                    flags.iter().any(|f| f == "r"),
+                    flags.iter().any(|f| f == "w"),
+                )
+            } else {
+                (cell, false, false)
+            }
+        }
+        _ => (cell, false, false),
+    };
+
+    let lower = source_text.to_lowercase().replace(' ', "");
+    let source = if lower.starts_with("modrm:reg") {
+        OperandSource::ModRmReg
+    } else if lower.starts_with("modrm:r/m") || lower.starts_with("modrm:rm") {
+        OperandSource::ModRmRm
+    } else if lower.ends_with(".vvvv") {
+        OperandSource::Vvvv
+    } else if lower.starts_with("imm8[7:4]") || lower.starts_with("is4") {
+        OperandSource::ImmediateHigh
+    } else if lower.starts_with("imm") || matches!(lower.as_str(), "ib" | "iw" | "id" | "io") {
+        OperandSource::Immediate
+    } else if lower.starts_with("opcode+r") {
+        OperandSource::OpcodeRegister
+    } else {
+        OperandSource::Implicit(source_text.to_owned())
+    };
+
+    Some(OperandSpec {
+        source,
+        read,
+        write,
+    })
+}
+
+/// Parse the instruction's "Instruction Operand Encoding" tables and attach the
+/// matching row to every opcode variant through its Op/En value.
+pub(super) fn link_operand_encodings(instruction: &mut Instruction) {
+    let encodings: Vec<OperandEncoding> = instruction
+        .tables
+        .iter()
+        .filter(|(_, table)| is_operand_encoding_table(table))
+        .flat_map(|(_, table)| parse_operand_encoding_table(table))
+        .collect();
+    if encodings.is_empty() {
+        return;
+    }
+
+    for variant in instruction.variants.iter_mut() {
+        let op_en = normalize_op_en(&variant.op_en);
+        if op_en.is_empty() {
+            continue;
+        }
+        variant.operand_encoding = encodings
+            .iter()
+            .find(|encoding| normalize_op_en(&encoding.op_en) == op_en)
+            .cloned();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intel::result::VectorPrefixKind;
+
+    #[test]
+    fn test_parse_operand_spec() {
+        assert_eq!(
+            parse_operand_spec("ModRM:reg (r, w)"),
+            Some(OperandSpec {
+                source: OperandSource::ModRmReg,
+                read: true,
+                write: true,
+            })
+        );
+        assert_eq!(
+            parse_operand_spec("EVEX.vvvv (r)"),
+            Some(OperandSpec {
+                source: OperandSource::Vvvv,
+                read: true,
+                write: false,
+            })
+        );
+        assert_eq!(
+            parse_operand_spec("imm8[7:4]").map(|spec| spec.source),
+            Some(OperandSource::ImmediateHigh)
+        );
+        assert_eq!(
+            parse_operand_spec("AL/AX/EAX/RAX").map(|spec| spec.source),
+            Some(OperandSource::Implicit("AL/AX/EAX/RAX".to_owned()))
+        );
+        assert_eq!(parse_operand_spec("N/A"), None);
+
+        let vvvv = parse_operand_spec("EVEX.vvvv (r)").unwrap();
+        assert_eq!(vvvv.text(Some(VectorPrefixKind::Evex)), "EVEX.vvvv (r)");
+        assert_eq!(vvvv.text(Some(VectorPrefixKind::Vex)), "VEX.vvvv (r)");
+        assert_eq!(vvvv.text(None), "vvvv (r)");
+    }
+
+    #[test]
+    fn test_parse_operand_encoding_table_with_tuple_type() {
+        let table = MdTable {
+            headers: [
+                "Op/En",
+                "Tuple Type",
+                "Operand 1",
+                "Operand 2",
+                "Operand 3",
+                "Operand 4",
+            ]
+            .iter()
+            .map(|h| (*h).to_owned())
+            .collect(),
+            rows: vec![[
+                "C",
+                "Full",
+                "ModRM:reg (w)",
+                "EVEX.vvvv (r)",
+                "ModRM:r/m (r)",
+                "N/A",
+            ]
+            .iter()
+            .map(|c| (*c).to_owned())
+            .collect()],
+        };
+        let encodings = parse_operand_encoding_table(&table);
+        assert_eq!(encodings.len(), 1);
+        assert_eq!(encodings[0].op_en, "C");
+        assert_eq!(encodings[0].tuple_type.as_deref(), Some("Full"));
+        assert_eq!(encodings[0].operands.len(), 4);
+        assert!(encodings[0].operands[3].is_none());
+    }
+}
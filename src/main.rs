@@ -5,6 +5,7 @@ pub(crate) mod arm;
 #[path = "intel/intel.rs"]
 pub(crate) mod intel;
 use architecture_doc_extractor::pdf;
+use architecture_doc_extractor::pseudocode;
 #[cfg(feature = "pdf_inspector")]
 mod pdf_inspector;
 #[cfg(test)]
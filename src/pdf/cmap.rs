@@ -0,0 +1,288 @@
+//! ToUnicode CMaps (PDF 32000-1:2008 §9.10.3, Adobe Technical Note #5411).
+
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct ToUnicode {
+    origin: String,
+    /// `begincodespacerange` entries: byte-wise low and high bounds of equal length
+    codespace: Vec<(Vec<u8>, Vec<u8>)>,
+    mapping: HashMap<Vec<u8>, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+    Hex(Vec<u8>),
+    Name(&'a str),
+    ArrayStart,
+    ArrayEnd,
+    /// Keywords, numbers and anything else we skip
+    Word(&'a str),
+}
+
+fn tokenize(origin: &str) -> Vec<Token<'_>> {
+    let bytes = origin.as_bytes();
+    let is_delimiter = |b: u8| b.is_ascii_whitespace() || b"()<>[]{}/%".contains(&b);
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b if b.is_ascii_whitespace() => i += 1,
+            b'%' => {
+                while i < bytes.len() && bytes[i] != b'\n' && bytes[i] != b'\r' {
+                    i += 1;
+                }
+            }
+            // literal strings only appear in CIDSystemInfo
+            b'(' => {
+                let mut depth = 0;
+                while i < bytes.len() {
+                    match bytes[i] {
+                        b'\\' => i += 1,
+                        b'(' => depth += 1,
+                        b')' => depth -= 1,
+                        _ => {}
+                    }
+                    i += 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+            }
+            b'<' if bytes.get(i + 1) == Some(&b'<') => i += 2,
+            b'>' if bytes.get(i + 1) == Some(&b'>') => i += 2,
+            b'<' => {
+                let end = origin[i..].find('>').map_or(bytes.len(), |end| i + end);
+                let digits: Vec<u8> = origin[i + 1..end]
+                    .chars()
+                    .filter_map(|c| c.to_digit(16).map(|d| d as u8))
+                    .collect();
+                // an odd final digit is followed by an implicit 0 (§7.3.4.3)
+                let hex = digits
+                    .chunks(2)
+                    .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0))
+                    .collect();
+                tokens.push(Token::Hex(hex));
+                i = end + 1;
+            }
+            b'[' => {
+                tokens.push(Token::ArrayStart);
+                i += 1;
+            }
+            b']' => {
+                tokens.push(Token::ArrayEnd);
+                i += 1;
+            }
+            b'/' => {
+                let start = i + 1;
+                i = start;
+                while i < bytes.len() && !is_delimiter(bytes[i]) {
+                    i += 1;
+                }
+                tokens.push(Token::Name(&origin[start..i]));
+            }
+            _ => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && !is_delimiter(bytes[i]) {
+                    i += 1;
+                }
+                tokens.push(Token::Word(&origin[start..i]));
+            }
+        }
+    }
+    tokens
+}
+
+/// Destination strings are UTF-16BE; a glyph name is allowed in `bfchar`.
+fn destination(token: &Token) -> Option<String> {
+    match token {
+        Token::Hex(bytes) => Some(utf16be(bytes)),
+        Token::Name(name) => crate::pdf::encoding::glyph_name_to_unicode(name),
+        _ => None,
+    }
+}
+
+fn utf16be(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks(2)
+        .map(|pair| match pair {
+            [high, low] => u16::from_be_bytes([*high, *low]),
+            [single] => *single as u16,
+            _ => unreachable!(),
+        })
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+fn code_to_int(code: &[u8]) -> u32 {
+    code.iter().fold(0, |acc, &b| acc << 8 | b as u32)
+}
+
+fn int_to_code(value: u32, len: usize) -> Vec<u8> {
+    value.to_be_bytes()[4 - len..].to_vec()
+}
+
+pub fn parse_tounicode(origin: String) -> ToUnicode {
+    let mut codespace = Vec::new();
+    let mut mapping = HashMap::new();
+    let tokens = tokenize(&origin);
+    let mut tokens = tokens.iter().peekable();
+    let is_hex = |token: &&Token| matches!(token, Token::Hex(_));
+    // each section runs until the first token that does not start an entry
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Word("begincodespacerange") => {
+                while let Some(Token::Hex(low)) = tokens.next_if(is_hex) {
+                    let Some(Token::Hex(high)) = tokens.next() else {
+                        break;
+                    };
+                    codespace.push((low.clone(), high.clone()));
+                }
+            }
+            Token::Word("beginbfchar") => {
+                while let Some(Token::Hex(code)) = tokens.next_if(is_hex) {
+                    let Some(dst) = tokens.next() else {
+                        break;
+                    };
+                    match destination(dst) {
+                        Some(text) => {
+                            mapping.insert(code.clone(), text);
+                        }
+                        None => tracing::warn!(?code, ?dst, "unsupported bfchar destination"),
+                    }
+                }
+            }
+            Token::Word("beginbfrange") => {
+                while let Some(Token::Hex(low)) = tokens.next_if(is_hex) {
+                    let Some(Token::Hex(high)) = tokens.next() else {
+                        break;
+                    };
+                    let (start, end) = (code_to_int(low), code_to_int(high));
+                    let valid = low.len() == high.len()
+                        && (1..=4).contains(&low.len())
+                        && end.checked_sub(start).is_some_and(|len| len <= 0xffff);
+                    if !valid {
+                        tracing::warn!(?low, ?high, "malformed bfrange, skipping");
+                    }
+                    let mut codes = (start..=end)
+                        .filter(|_| valid)
+                        .map(|code| int_to_code(code, low.len()));
+                    match tokens.next() {
+                        // Scalar: the last code unit is incremented along the range
+                        Some(Token::Hex(dst)) if dst.len() >= 2 => {
+                            let last = dst.len() - 2;
+                            let first = u16::from_be_bytes([dst[last], dst[last + 1]]);
+                            for (offset, code) in codes.enumerate() {
+                                let mut dst = dst.clone();
+                                let unit = first.wrapping_add(offset as u16).to_be_bytes();
+                                dst[last..].copy_from_slice(&unit);
+                                mapping.insert(code, utf16be(&dst));
+                            }
+                        }
+                        // Array: one destination per code
+                        Some(Token::ArrayStart) => {
+                            for dst in tokens.by_ref() {
+                                if *dst == Token::ArrayEnd {
+                                    break;
+                                }
+                                let (Some(code), Some(text)) = (codes.next(), destination(dst))
+                                else {
+                                    continue;
+                                };
+                                mapping.insert(code, text);
+                            }
+                        }
+                        dst => tracing::warn!(?low, ?dst, "unsupported bfrange destination"),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    ToUnicode {
+        origin,
+        codespace,
+        mapping,
+    }
+}
+
+impl ToUnicode {
+    pub fn origin(&self) -> &str {
+        &self.origin
+    }
+    pub fn mapping_raw(&self) -> &HashMap<Vec<u8>, String> {
+        &self.mapping
+    }
+    pub fn mapping(&self, code: &[u8]) -> Option<&str> {
+        self.mapping.get(code).map(String::as_str)
+    }
+
+    /// Length of the code at the start of `bytes` (§9.7.6.2): the shortest codespace range
+    /// that matches, else the shortest range length. None without codespace ranges.
+    pub fn code_length(&self, bytes: &[u8]) -> Option<usize> {
+        let matches = |(low, high): &(Vec<u8>, Vec<u8>)| {
+            bytes.len() >= low.len()
+                && std::iter::zip(low, high)
+                    .zip(bytes)
+                    .all(|((low, high), b)| (low..=high).contains(&b))
+        };
+        let matched = self.codespace.iter().filter(|range| matches(range));
+        matched
+            .map(|(low, _)| low.len())
+            .min()
+            .or_else(|| self.codespace.iter().map(|(low, _)| low.len()).min())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CMAP: &str = "/CIDInit /ProcSet findresource begin 12 dict begin begincmap
+/CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def
+/CMapName /Adobe-Identity-UCS def /CMapType 2 def
+2 begincodespacerange
+<00> <7F>
+<8000> <FFFF>
+endcodespacerange
+3 beginbfchar
+<41> <0041>
+<8001> <00660069>
+<8002> <D835DC00>
+endbfchar
+1 beginbfchar
+<42> /fi
+endbfchar
+2 beginbfrange
+<8010> <8012> <03B1>
+<8020> <8021> [<2264> <0066006C>]
+endbfrange
+endcmap CMapName currentdict /CMap defineresource pop end end";
+
+    #[test]
+    fn test_parse_tounicode() {
+        let to_unicode = parse_tounicode(CMAP.to_owned());
+        assert_eq!(to_unicode.mapping(b"A"), Some("A"));
+        // ligatures and surrogate pairs
+        assert_eq!(to_unicode.mapping(&[0x80, 0x01]), Some("fi"));
+        assert_eq!(to_unicode.mapping(&[0x80, 0x02]), Some("𝐀"));
+        // every bfchar section, including glyph name destinations
+        assert_eq!(to_unicode.mapping(b"B"), Some("ﬁ"));
+        assert_eq!(to_unicode.mapping(&[0x80, 0x12]), Some("γ"));
+        assert_eq!(to_unicode.mapping(&[0x80, 0x20]), Some("≤"));
+        assert_eq!(to_unicode.mapping(&[0x80, 0x21]), Some("fl"));
+        assert_eq!(to_unicode.mapping(&[0x00, 0x41]), None);
+        assert_eq!(to_unicode.mapping_raw().len(), 9);
+    }
+
+    #[test]
+    fn test_code_length() {
+        let to_unicode = parse_tounicode(CMAP.to_owned());
+        assert_eq!(to_unicode.code_length(&[0x41, 0x80]), Some(1));
+        assert_eq!(to_unicode.code_length(&[0x80, 0x01, 0x41]), Some(2));
+        // a partial code takes the shortest length
+        assert_eq!(to_unicode.code_length(&[0x80]), Some(1));
+        assert_eq!(parse_tounicode(String::new()).code_length(&[0x41]), None);
+    }
+}
@@ -0,0 +1,304 @@
+//! Simple font encodings (PDF 32000-1:2008 §9.6.6, Annex D) and glyph name to Unicode mapping.
+
+use lopdf::{Dictionary, Document, Object};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Base encodings of Annex D, as rows of 16 codes from 0x20 to 0xff. `\0` is undefined.
+const STANDARD: [&str; 14] = [
+    " !\"#$%&’()*+,-./",
+    "0123456789:;<=>?",
+    "@ABCDEFGHIJKLMNO",
+    "PQRSTUVWXYZ[\\]^_",
+    "‘abcdefghijklmno",
+    "pqrstuvwxyz{|}~\0",
+    "\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
+    "\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
+    "\0¡¢£⁄¥ƒ§¤'“«‹›ﬁﬂ",
+    "\0–†‡·\0¶•‚„”»…‰\0¿",
+    "\0`´ˆ˜¯˘˙¨\0˚¸\0˝˛ˇ",
+    "—\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
+    "\0Æ\0ª\0\0\0\0ŁØŒº\0\0\0\0",
+    "\0æ\0\0\0ı\0\0łøœß\0\0\0\0",
+];
+
+const WIN_ANSI: [&str; 14] = [
+    " !\"#$%&'()*+,-./",
+    "0123456789:;<=>?",
+    "@ABCDEFGHIJKLMNO",
+    "PQRSTUVWXYZ[\\]^_",
+    "`abcdefghijklmno",
+    "pqrstuvwxyz{|}~\0",
+    "€\0‚ƒ„…†‡ˆ‰Š‹Œ\0Ž\0",
+    "\0‘’“”•–—˜™š›œ\0žŸ",
+    " ¡¢£¤¥¦§¨©ª«¬-®¯",
+    "°±²³´µ¶·¸¹º»¼½¾¿",
+    "ÀÁÂÃÄÅÆÇÈÉÊËÌÍÎÏ",
+    "ÐÑÒÓÔÕÖ×ØÙÚÛÜÝÞß",
+    "àáâãäåæçèéêëìíîï",
+    "ðñòóôõö÷øùúûüýþÿ",
+];
+
+const MAC_ROMAN: [&str; 14] = [
+    " !\"#$%&'()*+,-./",
+    "0123456789:;<=>?",
+    "@ABCDEFGHIJKLMNO",
+    "PQRSTUVWXYZ[\\]^_",
+    "`abcdefghijklmno",
+    "pqrstuvwxyz{|}~\0",
+    "ÄÅÇÉÑÖÜáàâäãåçéè",
+    "êëíìîïñóòôöõúùûü",
+    "†°¢£§•¶ß®©™´¨≠ÆØ",
+    "∞±≤≥¥µ∂∑∏π∫ªºΩæø",
+    "¿¡¬√ƒ≈∆«»… ÀÃÕŒœ",
+    "–—“”‘’÷◊ÿŸ⁄¤‹›ﬁﬂ",
+    "‡·‚„‰ÂÊÁËÈÍÎÏÌÓÔ",
+    "\u{f8ff}ÒÚÛÙıˆ˜¯˘˙˚¸˝˛ˇ",
+];
+
+/// Built-in encoding of the Symbol font, as its glyph names map in the glyph list;
+/// extension pieces are in the private use area.
+const SYMBOL: [&str; 14] = [
+    " !∀#∃%&∋()∗+,−./",
+    "0123456789:;<=>?",
+    "≅ΑΒΧ∆ΕΦΓΗΙϑΚΛΜΝΟ",
+    "ΠΘΡΣΤΥς\u{2126}ΞΨΖ[∴]⊥_",
+    "\u{f8e5}αβχδεφγηιϕκλµνο",
+    "πθρστυϖωξψζ{|}∼\0",
+    "\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
+    "\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
+    "\0ϒ′≤⁄∞ƒ♣♦♥♠↔←↑→↓",
+    "°±″≥×∝∂•÷≠≡≈…\u{f8e6}\u{f8e7}↵",
+    "ℵℑℜ℘⊗⊕∅∩∪⊃⊇⊄⊂⊆∈∉",
+    "∠∇\u{f6da}\u{f6d9}\u{f6db}∏√⋅¬∧∨⇔⇐⇑⇒⇓",
+    "◊\u{2329}\u{f8e8}\u{f8e9}\u{f8ea}∑\u{f8eb}\u{f8ec}\u{f8ed}\u{f8ee}\u{f8ef}\u{f8f0}\u{f8f1}\u{f8f2}\u{f8f3}\u{f8f4}",
+    "\0\u{232a}∫⌠\u{f8f5}⌡\u{f8f6}\u{f8f7}\u{f8f8}\u{f8f9}\u{f8fa}\u{f8fb}\u{f8fc}\u{f8fd}\u{f8fe}\0",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseEncoding {
+    Standard,
+    WinAnsi,
+    MacRoman,
+    Symbol,
+}
+
+impl BaseEncoding {
+    /// `/Encoding` or `/BaseEncoding` name
+    pub fn from_name(name: &[u8]) -> Option<Self> {
+        match name {
+            b"StandardEncoding" => Some(Self::Standard),
+            b"WinAnsiEncoding" => Some(Self::WinAnsi),
+            b"MacRomanEncoding" => Some(Self::MacRoman),
+            _ => None,
+        }
+    }
+
+    fn rows(self) -> &'static [&'static str; 14] {
+        match self {
+            Self::Standard => &STANDARD,
+            Self::WinAnsi => &WIN_ANSI,
+            Self::MacRoman => &MAC_ROMAN,
+            Self::Symbol => &SYMBOL,
+        }
+    }
+}
+
+/// Code to text table of a simple font. Codes left undefined fall back to the caller.
+#[derive(Debug, Clone)]
+pub struct SimpleEncoding {
+    codes: Box<[Option<String>]>,
+}
+
+impl SimpleEncoding {
+    pub fn new(base: Option<BaseEncoding>) -> Self {
+        let mut codes = vec![None; 256].into_boxed_slice();
+        if let Some(base) = base {
+            let chars = base.rows().iter().flat_map(|row| row.chars());
+            for (code, c) in (0x20..).zip(chars) {
+                if c != '\0' {
+                    codes[code] = Some(c.to_string());
+                }
+            }
+        }
+        Self { codes }
+    }
+
+    /// Encoding of a Type1 or TrueType font dictionary.
+    /// None when the font relies on a built-in encoding we cannot read.
+    pub fn from_font(doc: &Document, font: &Dictionary) -> Option<Self> {
+        // Symbol's built-in encoding is known; others live in the font program
+        let builtin = font
+            .get(b"BaseFont")
+            .and_then(Object::as_name)
+            .is_ok_and(|name| name.windows(6).any(|part| part == b"Symbol"))
+            .then_some(BaseEncoding::Symbol);
+        match font.get_deref(b"Encoding", doc) {
+            Ok(Object::Name(name)) => match BaseEncoding::from_name(name) {
+                Some(base) => Some(Self::new(Some(base))),
+                None => {
+                    let encoding = String::from_utf8_lossy(name);
+                    tracing::warn!(%encoding, "unknown simple font encoding");
+                    builtin.map(|base| Self::new(Some(base)))
+                }
+            },
+            Ok(Object::Dictionary(dict)) => {
+                let base = dict
+                    .get(b"BaseEncoding")
+                    .and_then(Object::as_name)
+                    .ok()
+                    .and_then(BaseEncoding::from_name)
+                    .or(builtin);
+                let mut encoding = Self::new(base);
+                if let Ok(differences) = dict
+                    .get_deref(b"Differences", doc)
+                    .and_then(Object::as_array)
+                {
+                    encoding.apply_differences(differences);
+                }
+                Some(encoding)
+            }
+            _ => builtin.map(|base| Self::new(Some(base))),
+        }
+    }
+
+    /// `[code name name … code name …]`: each name takes the code after the previous one.
+    pub fn apply_differences(&mut self, differences: &[Object]) {
+        let mut code = 0usize;
+        for item in differences {
+            match item {
+                Object::Integer(start) => code = *start as usize,
+                Object::Name(name) => {
+                    if let Some(slot) = self.codes.get_mut(code) {
+                        *slot = glyph_name_to_unicode(&String::from_utf8_lossy(name));
+                    }
+                    code += 1;
+                }
+                _ => tracing::warn!(?item, "unexpected item in Differences, skipping"),
+            }
+        }
+    }
+
+    pub fn get(&self, code: u8) -> Option<&str> {
+        self.codes[code as usize].as_deref()
+    }
+}
+
+fn glyph_list() -> &'static HashMap<&'static str, char> {
+    static GLYPH_LIST: OnceLock<HashMap<&'static str, char>> = OnceLock::new();
+    GLYPH_LIST.get_or_init(|| {
+        include_str!("glyphlist.txt")
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| {
+                let (name, code) = line.split_once(';')?;
+                let code = u32::from_str_radix(code, 16).ok()?;
+                Some((name, char::from_u32(code)?))
+            })
+            .collect()
+    })
+}
+
+/// Maps a glyph name to Unicode following the Adobe Glyph List Specification:
+/// the suffix after `.` is dropped, `_` joins ligature components, and each component is
+/// looked up in the glyph list or read as `uniXXXX…` / `uXXXX[XX]`.
+pub fn glyph_name_to_unicode(name: &str) -> Option<String> {
+    let name = name.split('.').next().unwrap_or(name);
+    if name.is_empty() {
+        return None;
+    }
+    name.split('_').map(component_to_unicode).collect()
+}
+
+fn component_to_unicode(component: &str) -> Option<String> {
+    if let Some(&c) = glyph_list().get(component) {
+        return Some(c.to_string());
+    }
+    let is_upper_hex = |s: &str| s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'A'..=b'F'));
+    // from_u32 rejects surrogates, which the specification excludes
+    let scalar = |hex: &str| u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+    if let Some(hex) = component.strip_prefix("uni") {
+        if !hex.is_empty() && hex.len() % 4 == 0 && is_upper_hex(hex) {
+            return (0..hex.len())
+                .step_by(4)
+                .map(|i| scalar(&hex[i..i + 4]))
+                .collect();
+        }
+    }
+    if let Some(hex) = component.strip_prefix('u') {
+        if (4..=6).contains(&hex.len()) && is_upper_hex(hex) {
+            return scalar(hex).map(String::from);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::dictionary;
+
+    #[test]
+    fn test_glyph_name_to_unicode() {
+        assert_eq!(glyph_name_to_unicode("A").as_deref(), Some("A"));
+        assert_eq!(glyph_name_to_unicode("notequal").as_deref(), Some("≠"));
+        assert_eq!(glyph_name_to_unicode("union").as_deref(), Some("∪"));
+        assert_eq!(glyph_name_to_unicode("uni2264").as_deref(), Some("≤"));
+        assert_eq!(glyph_name_to_unicode("uni00660069").as_deref(), Some("fi"));
+        assert_eq!(glyph_name_to_unicode("u1D400").as_deref(), Some("𝐀"));
+        assert_eq!(glyph_name_to_unicode("f_f_i").as_deref(), Some("ffi"));
+        assert_eq!(glyph_name_to_unicode("a.sc").as_deref(), Some("a"));
+        assert_eq!(glyph_name_to_unicode("uni20ac"), None);
+        assert_eq!(glyph_name_to_unicode("g123"), None);
+        assert_eq!(glyph_name_to_unicode(".notdef"), None);
+    }
+
+    #[test]
+    fn test_simple_encoding_from_font() {
+        let doc = Document::with_version("1.5");
+        let encoding = |font: Dictionary| SimpleEncoding::from_font(&doc, &font);
+
+        let win_ansi = encoding(dictionary! { "Encoding" => "WinAnsiEncoding" }).unwrap();
+        assert_eq!(win_ansi.get(b'A'), Some("A"));
+        assert_eq!(win_ansi.get(0x93), Some("“"));
+        assert_eq!(win_ansi.get(0x1f), None);
+        assert_eq!(win_ansi.get(0x8f), None);
+
+        let differences = encoding(dictionary! {
+            "Encoding" => dictionary! {
+                "BaseEncoding" => "MacRomanEncoding",
+                "Differences" => vec![
+                    2.into(), "notequal".into(), "greaterequal".into(),
+                    0xa5.into(), "uni2264".into(),
+                ],
+            },
+        })
+        .unwrap();
+        assert_eq!(differences.get(2), Some("≠"));
+        assert_eq!(differences.get(3), Some("≥"));
+        assert_eq!(differences.get(0xa5), Some("≤"));
+        assert_eq!(differences.get(0xa7), Some("ß"));
+
+        // Differences on top of an unknown built-in encoding
+        let partial = encoding(dictionary! {
+            "Encoding" => dictionary! { "Differences" => vec![0x8a.into(), "endash".into()] },
+        })
+        .unwrap();
+        assert_eq!(partial.get(0x8a), Some("–"));
+        assert_eq!(partial.get(b'A'), None);
+
+        assert!(encoding(dictionary! { "BaseFont" => "Helvetica" }).is_none());
+        let symbol = encoding(dictionary! { "BaseFont" => "ABCDEF+SymbolMT" }).unwrap();
+        assert_eq!(symbol.get(b'a'), Some("α"));
+        assert_eq!(symbol.get(0xb9), Some("≠"));
+        // the built-in table agrees with the glyph list
+        for (code, name) in [
+            (0xa8, "diamond"),
+            (0xbd, "arrowvertex"),
+            (0xe7, "parenleftex"),
+        ] {
+            assert_eq!(symbol.get(code), glyph_name_to_unicode(name).as_deref());
+        }
+        assert_eq!(symbol.get(0xa9), Some("♥"));
+    }
+}
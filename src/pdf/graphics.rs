@@ -0,0 +1,326 @@
+//! Graphics state tracking shared by the v2 extractors (PDF 32000-1:2008 §8.4, §9.3, §9.4.2).
+
+use crate::pdf::v1::extract_num;
+use geo::Rect;
+use lopdf::content::Operation;
+use lopdf::Object;
+
+/// Affine transform `[a b c d e f]`: maps `(x, y)` to `(a·x + c·y + e, b·x + d·y + f)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Matrix {
+    pub const IDENTITY: Matrix = Matrix::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+    pub const fn new(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    pub const fn translate(tx: f32, ty: f32) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, tx, ty)
+    }
+
+    /// The six operands of `cm` / `Tm`.
+    pub fn from_operands(operands: &[Object]) -> Option<Self> {
+        let [a, b, c, d, e, f] = operands else {
+            return None;
+        };
+        Some(Self::new(
+            extract_num(a),
+            extract_num(b),
+            extract_num(c),
+            extract_num(d),
+            extract_num(e),
+            extract_num(f),
+        ))
+    }
+
+    /// `self × other`: apply `self` first, then `other`.
+    pub fn concat(&self, other: &Matrix) -> Matrix {
+        Matrix::new(
+            self.a * other.a + self.b * other.c,
+            self.a * other.b + self.b * other.d,
+            self.c * other.a + self.d * other.c,
+            self.c * other.b + self.d * other.d,
+            self.e * other.a + self.f * other.c + other.e,
+            self.e * other.b + self.f * other.d + other.f,
+        )
+    }
+
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+
+    /// Axis-aligned bounding box of `rect` after the transform.
+    pub fn transform_rect(&self, rect: Rect<f32>) -> Rect<f32> {
+        let (min, max) = (rect.min(), rect.max());
+        let corners = [
+            self.apply(min.x, min.y),
+            self.apply(max.x, min.y),
+            self.apply(min.x, max.y),
+            self.apply(max.x, max.y),
+        ];
+        let (mut low, mut high) = (corners[0], corners[0]);
+        for (x, y) in &corners[1..] {
+            low = (low.0.min(*x), low.1.min(*y));
+            high = (high.0.max(*x), high.1.max(*y));
+        }
+        Rect::new([low.0, low.1], [high.0, high.1])
+    }
+
+    /// Length of the transformed unit y vector.
+    pub fn y_scale(&self) -> f32 {
+        self.c.hypot(self.d)
+    }
+}
+
+/// Text state parameters, saved and restored with the rest of the graphics state (§9.3).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextState {
+    /// `Tf` font resource name
+    pub font: Option<String>,
+    /// `Tf` size
+    pub font_size: f32,
+    /// `Tc`, in unscaled text space units
+    pub char_space: f32,
+    /// `Tw`, applied to single-byte code 32
+    pub word_space: f32,
+    /// `Tz / 100`
+    pub horizontal_scaling: f32,
+    /// `TL`, used by `T*`, `'` and `"`
+    pub leading: f32,
+    /// `Ts`, in unscaled text space units
+    pub rise: f32,
+    /// `Tr`
+    pub render_mode: i64,
+}
+
+impl Default for TextState {
+    fn default() -> Self {
+        Self {
+            font: None,
+            font_size: 0.0,
+            char_space: 0.0,
+            word_space: 0.0,
+            horizontal_scaling: 1.0,
+            leading: 0.0,
+            rise: 0.0,
+            render_mode: 0,
+        }
+    }
+}
+
+impl TextState {
+    /// Render modes 3 (neither fill nor stroke) and 7 (clip only) paint nothing.
+    pub fn is_invisible(&self) -> bool {
+        matches!(self.render_mode, 3 | 7)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GraphicsState {
+    pub ctm: Matrix,
+    pub text: TextState,
+}
+
+/// Current graphics state, the `q`/`Q` stack, and the text object matrices.
+#[derive(Debug, Clone, Default)]
+pub struct GraphicsStack {
+    current: GraphicsState,
+    saved: Vec<GraphicsState>,
+    /// Tm
+    text_matrix: Matrix,
+    /// Tlm
+    text_line_matrix: Matrix,
+}
+
+impl GraphicsStack {
+    pub fn state(&self) -> &GraphicsState {
+        &self.current
+    }
+
+    pub fn text_matrix(&self) -> Matrix {
+        self.text_matrix
+    }
+
+    /// Maps text space to user space: `Tm × CTM`.
+    pub fn text_to_user(&self) -> Matrix {
+        self.text_matrix.concat(&self.current.ctm)
+    }
+
+    /// Maps glyph space (scaled by 1000) to user space: `[Tfs·Th 0 0 Tfs 0 Trise] × Tm × CTM`.
+    pub fn text_rendering_matrix(&self) -> Matrix {
+        let text = &self.current.text;
+        Matrix::new(
+            text.font_size * text.horizontal_scaling,
+            0.0,
+            0.0,
+            text.font_size,
+            0.0,
+            text.rise,
+        )
+        .concat(&self.text_to_user())
+    }
+
+    /// Applies a graphics or text state operator. Returns false for anything else.
+    pub fn apply(&mut self, op: &Operation) -> bool {
+        let num = |i: usize| op.operands.get(i).map(extract_num).unwrap_or(0.0);
+        match op.operator.as_str() {
+            "q" => self.saved.push(self.current.clone()),
+            "Q" => match self.saved.pop() {
+                Some(state) => self.current = state,
+                None => tracing::warn!("Q without matching q, ignoring"),
+            },
+            "cm" => match Matrix::from_operands(&op.operands) {
+                Some(matrix) => self.current.ctm = matrix.concat(&self.current.ctm),
+                None => tracing::warn!(?op.operands, "malformed cm, ignoring"),
+            },
+            "BT" => {
+                self.text_matrix = Matrix::IDENTITY;
+                self.text_line_matrix = Matrix::IDENTITY;
+            }
+            "ET" => {}
+            "Tf" => {
+                self.current.text.font = op
+                    .operands
+                    .first()
+                    .and_then(|name| name.as_name_str().ok())
+                    .map(str::to_owned);
+                self.current.text.font_size = num(1);
+            }
+            "Tc" => self.current.text.char_space = num(0),
+            "Tw" => self.current.text.word_space = num(0),
+            "Tz" => self.current.text.horizontal_scaling = num(0) / 100.0,
+            "TL" => self.current.text.leading = num(0),
+            "Ts" => self.current.text.rise = num(0),
+            "Tr" => self.current.text.render_mode = num(0) as i64,
+            "Td" => self.next_line(num(0), num(1)),
+            "TD" => {
+                self.current.text.leading = -num(1);
+                self.next_line(num(0), num(1));
+            }
+            "T*" => self.next_line_by_leading(),
+            "Tm" => match Matrix::from_operands(&op.operands) {
+                Some(matrix) => {
+                    self.text_matrix = matrix;
+                    self.text_line_matrix = matrix;
+                }
+                None => tracing::warn!(?op.operands, "malformed Tm, ignoring"),
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    /// Starts the next line at an offset from the start of the current one.
+    pub fn next_line(&mut self, tx: f32, ty: f32) {
+        self.text_line_matrix = Matrix::translate(tx, ty).concat(&self.text_line_matrix);
+        self.text_matrix = self.text_line_matrix;
+    }
+
+    /// `T*`: starts the next line, `TL` below the current one.
+    pub fn next_line_by_leading(&mut self) {
+        self.next_line(0.0, -self.current.text.leading);
+    }
+
+    /// Text state that the `"` operator sets before showing its string.
+    pub fn text_mut(&mut self) -> &mut TextState {
+        &mut self.current.text
+    }
+
+    /// Moves Tm along the baseline by `tx` unscaled text space units.
+    pub fn advance(&mut self, tx: f32) {
+        self.text_matrix = Matrix::translate(tx, 0.0).concat(&self.text_matrix);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(operator: &str, operands: &[f32]) -> Operation {
+        Operation::new(
+            operator,
+            operands.iter().map(|&n| Object::Real(n)).collect(),
+        )
+    }
+
+    #[test]
+    fn test_matrix_concat() {
+        let scale = Matrix::new(2.0, 0.0, 0.0, 3.0, 0.0, 0.0);
+        let shift = Matrix::translate(10.0, 20.0);
+        // scale first, then shift
+        assert_eq!(scale.concat(&shift).apply(1.0, 1.0), (12.0, 23.0));
+        assert_eq!(shift.concat(&scale).apply(1.0, 1.0), (22.0, 63.0));
+        let rotate = Matrix::new(0.0, 1.0, -1.0, 0.0, 0.0, 0.0);
+        let rect = rotate.transform_rect(Rect::new([0.0, 0.0], [4.0, 1.0]));
+        assert_eq!(rect, Rect::new([-1.0, 0.0], [0.0, 4.0]));
+        assert_eq!(rotate.y_scale(), 1.0);
+    }
+
+    #[test]
+    fn test_graphics_stack() {
+        let mut stack = GraphicsStack::default();
+        assert!(stack.apply(&op("q", &[])));
+        assert!(stack.apply(&op("cm", &[1.0, 0.0, 0.0, 1.0, 100.0, 0.0])));
+        assert!(stack.apply(&op("cm", &[2.0, 0.0, 0.0, 2.0, 0.0, 0.0])));
+        assert!(stack.apply(&op("Tc", &[0.5])));
+        assert!(stack.apply(&op("BT", &[])));
+        assert!(stack.apply(&op("Tm", &[9.0, 0.0, 0.0, 9.0, 10.0, 50.0])));
+        assert!(stack.apply(&op("Td", &[1.0, -2.0])));
+        // Td is in text space: scaled by Tm, then by the CTM
+        assert_eq!(stack.text_matrix().apply(0.0, 0.0), (19.0, 32.0));
+        assert_eq!(stack.text_to_user().apply(0.0, 0.0), (138.0, 64.0));
+        stack.advance(2.0);
+        assert_eq!(stack.text_matrix().apply(0.0, 0.0), (37.0, 32.0));
+        // the line matrix is not moved by glyph advances
+        assert!(stack.apply(&op("Td", &[0.0, -1.0])));
+        assert_eq!(stack.text_matrix().apply(0.0, 0.0), (19.0, 23.0));
+        assert!(stack.apply(&op("ET", &[])));
+        assert!(stack.apply(&op("Q", &[])));
+        assert_eq!(stack.state(), &GraphicsState::default());
+        assert!(!stack.apply(&op("re", &[0.0, 0.0, 1.0, 1.0])));
+    }
+
+    #[test]
+    fn test_text_state_operators() {
+        let mut stack = GraphicsStack::default();
+        assert_eq!(stack.state().text.horizontal_scaling, 1.0);
+        assert!(stack.apply(&op("BT", &[])));
+        // TD also sets the leading used by T*
+        assert!(stack.apply(&op("TD", &[5.0, -14.0])));
+        assert_eq!(stack.state().text.leading, 14.0);
+        assert!(stack.apply(&op("T*", &[])));
+        assert_eq!(stack.text_matrix().apply(0.0, 0.0), (5.0, -28.0));
+        assert!(stack.apply(&op("TL", &[10.0])));
+        stack.next_line_by_leading();
+        assert_eq!(stack.text_matrix().apply(0.0, 0.0), (5.0, -38.0));
+        let font = vec![Object::Name(b"F1".to_vec()), Object::Real(10.0)];
+        assert!(stack.apply(&Operation::new("Tf", font)));
+        assert!(stack.apply(&op("Tz", &[50.0])));
+        assert!(stack.apply(&op("Ts", &[2.0])));
+        assert!(stack.apply(&op("Tr", &[7.0])));
+        let text = &stack.state().text;
+        assert_eq!((text.horizontal_scaling, text.rise), (0.5, 2.0));
+        assert!(text.is_invisible());
+        // glyph space (1, 1): scaled by Tfs·Th horizontally, raised by Ts
+        let rendering = stack.text_rendering_matrix();
+        assert_eq!(rendering.apply(1.0, 1.0), (10.0, -26.0));
+    }
+}
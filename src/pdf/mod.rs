@@ -1,3 +1,6 @@
+pub mod cmap;
+pub mod encoding;
+pub mod graphics;
 mod page;
 pub mod v1;
 pub mod v2;
@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+use crate::pdf::cmap::{parse_tounicode, ToUnicode};
+use crate::pdf::encoding::SimpleEncoding;
 use ab_glyph::Font;
 use lopdf::{content::Content, Document};
 use std::io::Read;
@@ -25,29 +27,144 @@ pub fn page_to_boxes_v2(doc: &Document, page: u32) -> crate::pdf::v2::PdfBoxes {
     crate::pdf::v2::operator_to_boxes(get_page_contents(doc, page).operations)
 }
 
+/// Page content with the `Contents` streams joined and every Form XObject painted by `Do`
+/// expanded in place, wrapped in `q`/`cm Matrix`/`Q`.
+/// Fonts of a form with its own resources are renamed to `Form/Font`, as in [`get_pdf_fonts`].
 pub fn get_page_contents(doc: &Document, page: u32) -> Content {
-    let binding = doc.get_pages();
-    let page = binding.get(&page).unwrap();
-    let page = doc.get_object(*page).unwrap();
-    let page_items = page.as_dict().unwrap();
-    let page_items = doc
-        .get_object(page_items.get(b"Contents").unwrap().as_reference().unwrap())
-        .unwrap()
-        .as_stream()
-        .unwrap();
-    Content::decode(&page_items.decompressed_content().unwrap()).unwrap()
+    let mut operations = Vec::new();
+    expand_forms(
+        doc,
+        get_page_resources(doc, page),
+        "",
+        get_page_contents2(doc, page).operations,
+        &mut Vec::new(),
+        &mut operations,
+    );
+    Content { operations }
 }
 
+/// Page content with the `Contents` streams joined, without expanding `Do`.
 pub fn get_page_contents2(doc: &Document, page: u32) -> Content {
     let pages = doc.get_pages();
     let page = pages.get(&page).unwrap();
-    let page_contents = doc.get_page_contents(*page);
-    let page_contents = doc
-        .get_object(page_contents[0])
-        .unwrap()
-        .as_stream()
-        .unwrap();
-    Content::decode(&page_contents.decompressed_content().unwrap()).unwrap()
+    // A Contents array is one stream split at token boundaries (§7.8.2)
+    let mut data = Vec::new();
+    for id in doc.get_page_contents(*page) {
+        data.extend(stream_content(
+            doc.get_object(id).unwrap().as_stream().unwrap(),
+        ));
+        data.push(b'\n');
+    }
+    Content::decode(&data).unwrap()
+}
+
+fn stream_content(stream: &lopdf::Stream) -> Vec<u8> {
+    stream
+        .decompressed_content()
+        .unwrap_or_else(|_| stream.content.clone())
+}
+
+/// A Form XObject reachable from a resource dictionary
+struct FormXObject<'pdf> {
+    id: lopdf::ObjectId,
+    stream: &'pdf lopdf::Stream,
+    /// the form's own resources, or the ones it inherits
+    resources: &'pdf lopdf::Dictionary,
+    /// font name prefix inside the form
+    prefix: String,
+}
+
+impl<'pdf> FormXObject<'pdf> {
+    fn get(
+        doc: &'pdf Document,
+        resources: &'pdf lopdf::Dictionary,
+        prefix: &str,
+        name: &[u8],
+    ) -> Option<Self> {
+        let xobjects = resources.get_deref(b"XObject", doc).ok()?.as_dict().ok()?;
+        let id = xobjects.get(name).ok()?.as_reference().ok()?;
+        let stream = doc.get_object(id).ok()?.as_stream().ok()?;
+        if stream.dict.get(b"Subtype").and_then(|s| s.as_name()).ok() != Some(b"Form") {
+            return None;
+        }
+        let (resources, prefix) = match stream.dict.get_deref(b"Resources", doc) {
+            Ok(own) => (
+                own.as_dict().ok()?,
+                format!("{prefix}{}/", String::from_utf8_lossy(name)),
+            ),
+            Err(_) => (resources, prefix.to_owned()),
+        };
+        Some(Self {
+            id,
+            stream,
+            resources,
+            prefix,
+        })
+    }
+
+    fn xobject_names(resources: &lopdf::Dictionary, doc: &Document) -> Vec<Vec<u8>> {
+        resources
+            .get_deref(b"XObject", doc)
+            .and_then(lopdf::Object::as_dict)
+            .map(|xobjects| xobjects.iter().map(|(name, _)| name.clone()).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// `visiting` holds the forms being expanded, so a self-referencing form is painted once.
+fn expand_forms(
+    doc: &Document,
+    resources: &lopdf::Dictionary,
+    prefix: &str,
+    operations: Vec<lopdf::content::Operation>,
+    visiting: &mut Vec<lopdf::ObjectId>,
+    result: &mut Vec<lopdf::content::Operation>,
+) {
+    use lopdf::content::Operation;
+    for mut op in operations {
+        match op.operator.as_str() {
+            "Tf" if !prefix.is_empty() => {
+                if let Some(lopdf::Object::Name(name)) = op.operands.first_mut() {
+                    name.splice(0..0, prefix.bytes());
+                }
+            }
+            "Do" => {
+                let form = op
+                    .operands
+                    .first()
+                    .and_then(|name| name.as_name().ok())
+                    .and_then(|name| FormXObject::get(doc, resources, prefix, name));
+                if let Some(form) = form.filter(|form| !visiting.contains(&form.id)) {
+                    let content = match Content::decode(&stream_content(form.stream)) {
+                        Ok(content) => content,
+                        Err(error) => {
+                            tracing::warn!(?form.id, %error, "undecodable form content, keeping Do");
+                            result.push(op);
+                            continue;
+                        }
+                    };
+                    result.push(Operation::new("q", vec![]));
+                    if let Ok(matrix) = form.stream.dict.get(b"Matrix").and_then(|m| m.as_array()) {
+                        result.push(Operation::new("cm", matrix.clone()));
+                    }
+                    visiting.push(form.id);
+                    expand_forms(
+                        doc,
+                        form.resources,
+                        &form.prefix,
+                        content.operations,
+                        visiting,
+                        result,
+                    );
+                    visiting.pop();
+                    result.push(Operation::new("Q", vec![]));
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        result.push(op);
+    }
 }
 
 pub fn get_page_resources(doc: &Document, page: u32) -> &lopdf::Dictionary {
@@ -75,13 +192,51 @@ pub fn get_char_width(doc: &Document, page: u32, font_name: impl AsRef<str>, c:
         / 1000.0
 }
 
+/// Fonts of the page, plus those of its Form XObjects under `Form/Font` names.
 pub fn get_pdf_fonts(doc: &Document, page: u32) -> PdfFonts {
-    let resources = get_page_resources(doc, page);
-    let fonts = resources.get(b"Font").unwrap().as_dict().unwrap();
+    let mut fonts = std::collections::BTreeMap::new();
+    collect_fonts(
+        doc,
+        get_page_resources(doc, page),
+        "",
+        &mut Vec::new(),
+        &mut fonts,
+    );
     PdfFonts(doc, fonts)
 }
+
+fn collect_fonts<'pdf>(
+    doc: &'pdf Document,
+    resources: &'pdf lopdf::Dictionary,
+    prefix: &str,
+    visiting: &mut Vec<lopdf::ObjectId>,
+    fonts: &mut std::collections::BTreeMap<Vec<u8>, &'pdf lopdf::Object>,
+) {
+    if let Ok(dict) = resources
+        .get_deref(b"Font", doc)
+        .and_then(lopdf::Object::as_dict)
+    {
+        for (name, font) in dict.iter() {
+            fonts.insert([prefix.as_bytes(), name].concat(), font);
+        }
+    }
+    for name in FormXObject::xobject_names(resources, doc) {
+        let Some(form) = FormXObject::get(doc, resources, prefix, &name) else {
+            continue;
+        };
+        if form.prefix == prefix || visiting.contains(&form.id) {
+            continue;
+        }
+        visiting.push(form.id);
+        collect_fonts(doc, form.resources, &form.prefix, visiting, fonts);
+        visiting.pop();
+    }
+}
 #[derive(Debug)]
-pub struct PdfFonts<'pdf>(&'pdf Document, &'pdf lopdf::Dictionary);
+pub struct PdfFonts<'pdf>(
+    &'pdf Document,
+    std::collections::BTreeMap<Vec<u8>, &'pdf lopdf::Object>,
+);
 #[derive(Debug)]
 pub enum PdfFont<'pdf> {
     Regular {
@@ -89,6 +244,8 @@ pub enum PdfFont<'pdf> {
         widths: Box<[f32]>,
         doc: &'pdf Document,
         font_descripter: &'pdf lopdf::Dictionary,
+        encoding: Option<SimpleEncoding>,
+        to_unicode: Option<ToUnicode>,
     },
     CidFont {
         doc: &'pdf Document,
@@ -97,17 +254,11 @@ pub enum PdfFont<'pdf> {
         font_arc: ab_glyph::FontArc,
     },
 }
-#[derive(Debug)]
-pub struct ToUnicode {
-    origin: String,
-    mapping: std::collections::HashMap<[u8; 2], [u8; 2]>,
-}
 
 impl<'pdf> PdfFonts<'pdf> {
     pub fn get(&self, font_name: impl AsRef<str>) -> PdfFont {
         let font = self.1.get(font_name.as_ref().as_bytes()).unwrap();
         let font = self.0.dereference(font).unwrap().1.as_dict().unwrap();
-        // TODO Custom Encoding not covered
         if font.get(b"Subtype").unwrap().as_name_str().unwrap() != "Type0" {
             self.get_regular(&font)
         } else {
@@ -132,6 +283,8 @@ impl<'pdf> PdfFonts<'pdf> {
                 .collect(),
             doc: self.0,
             font_descripter,
+            encoding: SimpleEncoding::from_font(self.0, font),
+            to_unicode: get_to_unicode(self.0, font),
         }
     }
     fn get_cidfont(&self, font: &'pdf lopdf::Dictionary) -> PdfFont<'pdf> {
@@ -141,17 +294,7 @@ impl<'pdf> PdfFonts<'pdf> {
         // ToUnicode (Decompressed by zlib)
         // /CIDInit /ProcSet findresource begin 12 dict begin begincmap /CIDSystemInfo <<\n/Registry (HKLMCJ+TT35+0) /Ordering (T42UV) /Supplement 0 >> def\n/CMapName /HKLMCJ+TT35+0 def\n/CMapType 2 def\n1 begincodespacerange <03ab> <03bb> endcodespacerange\n2 beginbfchar\n<03ab> <2212>\n<03bb> <221E>\nendbfchar\n1 beginbfrange\n<03b6> <03b7> <2264>\nendbfrange\nendcmap CMapName currentdict /CMap defineresource pop end end\n
 
-        let to_unicode = self
-            .0
-            .dereference(font.get(b"ToUnicode").unwrap())
-            .unwrap()
-            .1
-            .as_stream()
-            .unwrap();
-        let mut reader = flate2::bufread::ZlibDecoder::new(&*to_unicode.content);
-        let mut to_unicode = String::new();
-        reader.read_to_string(&mut to_unicode).unwrap();
-        let to_unicode = parse_tounicode(to_unicode);
+        let to_unicode = get_to_unicode(self.0, font).unwrap();
 
         let descendant_font = &font.get(b"DescendantFonts").unwrap().as_array().unwrap()[0];
         let descendant_font = self
@@ -173,13 +316,42 @@ impl<'pdf> PdfFonts<'pdf> {
     }
 }
 impl<'pdf> PdfFont<'pdf> {
+    /// Text of a simple font code from its ToUnicode CMap, then its encoding.
+    /// None leaves the code to the caller's fallback.
+    pub fn get_char_text(&self, c: u8) -> Option<String> {
+        let PdfFont::Regular {
+            encoding,
+            to_unicode,
+            ..
+        } = self
+        else {
+            unreachable!()
+        };
+        to_unicode
+            .as_ref()
+            .and_then(|to_unicode| to_unicode.mapping(&[c]))
+            .or_else(|| encoding.as_ref()?.get(c))
+            .map(str::to_owned)
+    }
     pub fn get_char_width(&self, c: u8) -> f32 {
         match self {
             Self::Regular {
-                first_char, widths, ..
+                first_char,
+                widths,
+                font_descripter,
+                ..
             } => {
-                let index = c as usize - first_char;
-                widths[index]
+                let width = (c as usize)
+                    .checked_sub(*first_char)
+                    .and_then(|index| widths.get(index));
+                // codes outside FirstChar..=LastChar use MissingWidth
+                width.copied().unwrap_or_else(|| {
+                    font_descripter
+                        .get(b"MissingWidth")
+                        .and_then(lopdf::Object::as_float)
+                        .unwrap_or(0.0)
+                        / 1000.0
+                })
             }
             PdfFont::CidFont { .. } => {
                 // CidFont字 uses get_cid_width; fallback to default width
@@ -191,18 +363,33 @@ impl<'pdf> PdfFont<'pdf> {
             }
         }
     }
-    pub fn get_cid_width(&self, hex: [u8; 2]) -> f32 {
+    /// Splits a composite font string into codes by the ToUnicode codespace ranges,
+    /// two bytes each (Identity-H) when there are none.
+    pub fn split_codes<'a>(&self, mut bytes: &'a [u8]) -> Vec<&'a [u8]> {
+        let PdfFont::CidFont { to_unicode, .. } = self else {
+            unreachable!()
+        };
+        let mut codes = Vec::new();
+        while !bytes.is_empty() {
+            let len = to_unicode.code_length(bytes).unwrap_or(2).min(bytes.len());
+            let (code, rest) = bytes.split_at(len);
+            codes.push(code);
+            bytes = rest;
+        }
+        codes
+    }
+    pub fn get_cid_width(&self, code: &[u8]) -> f32 {
         let PdfFont::CidFont {
             font_arc: font_ref, ..
         } = self
         else {
             unreachable!()
         };
-        let c = self.get_cid_char(hex);
+        let c = self.get_cid_char(code).chars().next().unwrap_or('\u{FFFD}');
         let id = font_ref.glyph_id(c);
         font_ref.h_advance_unscaled(id) / 1000.0
     }
-    pub fn get_cid_char(&self, hex: [u8; 2]) -> char {
+    pub fn get_cid_char(&self, code: &[u8]) -> String {
         let PdfFont::CidFont {
             to_unicode,
             font_arc,
@@ -211,10 +398,11 @@ impl<'pdf> PdfFont<'pdf> {
         else {
             unreachable!()
         };
-        match to_unicode.mapping(hex) {
-            Some(c) => std::char::from_u32(u16::from_be_bytes(c) as u32).unwrap_or('\u{FFFD}'),
+        match to_unicode.mapping(code) {
+            Some(text) => text.to_owned(),
             None => {
-                let glyph_id = u16::from_be_bytes(hex);
+                // Identity CIDToGIDMap: the code is the glyph id
+                let glyph_id = code.iter().fold(0u16, |id, &b| id << 8 | b as u16);
                 let c = Self::cid_glyph_fallback(font_arc, glyph_id);
                 if c == '\u{FFFD}' {
                     tracing::debug!(
@@ -223,7 +411,7 @@ impl<'pdf> PdfFont<'pdf> {
                         "ToUnicode CMap for unmapped glyph"
                     );
                 }
-                c
+                c.into()
             }
         }
     }
@@ -283,6 +471,12 @@ impl<'pdf> PdfFont<'pdf> {
     }
 }
 
+fn get_to_unicode(doc: &Document, font: &lopdf::Dictionary) -> Option<ToUnicode> {
+    let stream = font.get_deref(b"ToUnicode", doc).ok()?.as_stream().ok()?;
+    let origin = String::from_utf8_lossy(&stream_content(stream)).into_owned();
+    Some(parse_tounicode(origin))
+}
+
 fn get_font_descripter<'pdf>(
     doc: &'pdf Document,
     font: &'pdf lopdf::Dictionary,
@@ -313,120 +507,130 @@ fn get_font_file<'pdf>(doc: &'pdf Document, font_descripter: &'pdf lopdf::Dictio
     buf
 }
 
-/// Parse a single `<hex>` token from a character iterator, returning the u16 value.
-fn parse_hex_token(
-    chars: &mut std::iter::Peekable<std::str::Chars>,
-    extract: &dyn Fn(&str) -> u16,
-) -> Option<u16> {
-    // Skip whitespace
-    while chars.peek().map_or(false, |c| c.is_whitespace()) {
-        chars.next();
-    }
-    if chars.peek() != Some(&'<') {
-        return None;
-    }
-    let mut token = String::new();
-    for c in chars.by_ref() {
-        token.push(c);
-        if c == '>' {
-            break;
-        }
-    }
-    Some(extract(&token))
-}
-
-fn parse_tounicode(origin: String) -> ToUnicode {
-    let mut mapping = std::collections::HashMap::new();
-    use itertools::Itertools;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{dictionary, Object, Stream};
 
-    let extract_hex_data = |s: &str| {
-        let d = s.trim_matches(|c| c == '<' || c == '>');
-        u16::from_str_radix(d, 16).unwrap()
-    };
-
-    // bfchar section (optional)
-    if let (Some(begin), Some(end)) = (origin.find("beginbfchar"), origin.find("endbfchar")) {
-        let bfchar = &origin[begin..end];
-        let mut bfchar = bfchar.split_whitespace();
-        bfchar.next();
-        for mut bfchar in &bfchar.chunks(2) {
-            let Some(f) = bfchar.next() else { break };
-            let Some(t) = bfchar.next() else { break };
-            let f = extract_hex_data(f).to_be_bytes();
-            let t = extract_hex_data(t).to_be_bytes();
-            mapping.insert(f, t);
-        }
+    fn font(doc: &mut Document, width: i64) -> lopdf::ObjectId {
+        let descriptor = doc.add_object(dictionary! { "Type" => "FontDescriptor" });
+        doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "FirstChar" => 65,
+            "Widths" => vec![Object::Integer(width), Object::Integer(width)],
+            "FontDescriptor" => descriptor,
+        })
     }
 
-    // bfrange section (optional)
-    // Two forms per entry:
-    //   Scalar: <srcStart> <srcEnd> <dstStart>  → sequential mapping
-    //   Array:  <srcStart> <srcEnd> [<d1> <d2> ...]  → per-CID mapping
-    if let (Some(begin), Some(end)) = (origin.find("beginbfrange"), origin.find("endbfrange")) {
-        let section = &origin[begin..end];
-        // Skip "beginbfrange"
-        let body = &section["beginbfrange".len()..];
-
-        let mut chars = body.chars().peekable();
-        loop {
-            // Skip whitespace
-            while chars.peek().map_or(false, |c| c.is_whitespace()) {
-                chars.next();
-            }
-            if chars.peek().is_none() {
-                break;
-            }
-
-            // Parse <srcStart>
-            let Some(src_start) = parse_hex_token(&mut chars, &extract_hex_data) else {
-                break;
-            };
-            // Parse <srcEnd>
-            let Some(src_end) = parse_hex_token(&mut chars, &extract_hex_data) else {
-                break;
-            };
-
-            // Skip whitespace
-            while chars.peek().map_or(false, |c| c.is_whitespace()) {
-                chars.next();
-            }
-
-            if chars.peek() == Some(&'[') {
-                // Array form: [<d1> <d2> ...]
-                chars.next(); // skip '['
-                for cid in src_start..=src_end {
-                    if let Some(dst) = parse_hex_token(&mut chars, &extract_hex_data) {
-                        mapping.insert(cid.to_be_bytes(), dst.to_be_bytes());
-                    }
-                }
-                // Skip to closing ']'
-                while chars.peek().map_or(false, |&c| c != ']') {
-                    chars.next();
-                }
-                chars.next(); // skip ']'
-            } else {
-                // Scalar form: <dstStart>
-                let Some(dst_start) = parse_hex_token(&mut chars, &extract_hex_data) else {
-                    break;
-                };
-                for (i, cid) in (src_start..=src_end).enumerate() {
-                    mapping.insert(cid.to_be_bytes(), (dst_start + i as u16).to_be_bytes());
-                }
-            }
-        }
+    /// Contents split over two streams; `Fm0` is moved by its Matrix, has its own `F1`,
+    /// and paints `Fm1`, which inherits those resources and paints itself.
+    fn test_document() -> Document {
+        let mut doc = Document::with_version("1.5");
+        let page_font = font(&mut doc, 500);
+        let form_font = font(&mut doc, 1000);
+        let fm1 = doc.new_object_id();
+        doc.objects.insert(
+            fm1,
+            Object::Stream(Stream::new(
+                dictionary! { "Type" => "XObject", "Subtype" => "Form" },
+                b"BT /F1 10 Tf (B) Tj ET /Fm1 Do".to_vec(),
+            )),
+        );
+        let fm0 = doc.add_object(Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Form",
+                "Matrix" => vec![1.into(), 0.into(), 0.into(), 1.into(), 100.into(), 0.into()],
+                "Resources" => dictionary! {
+                    "Font" => dictionary! { "F1" => form_font },
+                    "XObject" => dictionary! { "Fm1" => fm1 },
+                },
+            },
+            b"/Fm1 Do".to_vec(),
+        ));
+        let contents = [
+            b"BT /F1 10 Tf 10 20 Td (A) Tj".to_vec(),
+            b"ET /Fm0 Do".to_vec(),
+        ]
+        .map(|content| Object::Reference(doc.add_object(Stream::new(dictionary! {}, content))));
+        let pages = doc.new_object_id();
+        let page = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages,
+            "Contents" => contents.to_vec(),
+            "Resources" => dictionary! {
+                "Font" => dictionary! { "F1" => page_font },
+                "XObject" => dictionary! { "Fm0" => fm0 },
+            },
+        });
+        doc.objects.insert(
+            pages,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![page.into()],
+                "Count" => 1,
+            }),
+        );
+        let catalog = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages });
+        doc.trailer.set("Root", catalog);
+        doc
     }
 
-    ToUnicode { origin, mapping }
-}
+    #[test]
+    fn test_get_page_contents_forms() {
+        let doc = test_document();
+        let operators = |content: Content| -> Vec<String> {
+            content
+                .operations
+                .into_iter()
+                .map(|op| op.operator)
+                .collect()
+        };
+        assert_eq!(
+            operators(get_page_contents2(&doc, 1)),
+            ["BT", "Tf", "Td", "Tj", "ET", "Do"]
+        );
+        let contents = get_page_contents(&doc, 1);
+        assert_eq!(
+            operators(contents.clone()),
+            // the nested self-reference is left as a plain Do
+            [
+                "BT", "Tf", "Td", "Tj", "ET", "q", "cm", "q", "BT", "Tf", "Tj", "ET", "Do", "Q",
+                "Q"
+            ]
+        );
+        assert_eq!(
+            contents.operations[9].operands[0],
+            Object::Name(b"Fm0/F1".to_vec())
+        );
 
-impl ToUnicode {
-    pub fn origin(&self) -> &str {
-        &self.origin
+        let fonts = get_pdf_fonts(&doc, 1);
+        let names: Vec<&[u8]> = fonts.1.keys().map(Vec::as_slice).collect();
+        assert_eq!(names, [b"F1".as_slice(), b"Fm0/F1"]);
     }
-    pub fn mapping_raw(&self) -> &std::collections::HashMap<[u8; 2], [u8; 2]> {
-        &self.mapping
-    }
-    pub fn mapping(&self, hex: [u8; 2]) -> Option<[u8; 2]> {
-        self.mapping.get(&hex).copied()
+
+    #[test]
+    fn test_page_to_texts_v2_forms() {
+        let doc = test_document();
+        let mut strings: Vec<(String, [f32; 4])> = page_to_texts_v2(&doc, 1)
+            .iter()
+            .map(|s| {
+                let rect = s.rect();
+                (
+                    s.get(),
+                    [rect.min().x, rect.min().y, rect.max().x, rect.max().y],
+                )
+            })
+            .collect();
+        strings.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            strings,
+            [
+                ("A".to_owned(), [10.0, 20.0, 15.0, 30.0]),
+                // form font, 1000 wide, shifted by the form Matrix
+                ("B".to_owned(), [100.0, 0.0, 110.0, 10.0]),
+            ]
+        );
     }
 }
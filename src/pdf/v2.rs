@@ -1,182 +1,148 @@
-use crate::pdf::v1::{extract_num, PDF_TEXT_HEIGHT_FACTOR};
+use crate::pdf::graphics::GraphicsStack;
+use crate::pdf::v1::extract_num;
 use either::Either;
 use geo::{BoundingRect, MultiPolygon, Rect};
-use lopdf::{content::Operation, Object, StringFormat};
+use lopdf::{content::Operation, Object};
 use std::cmp::Ordering;
-use tracing::debug;
+use std::collections::HashMap;
 
 pub fn operator_to_chars(
     fonts: crate::pdf::PdfFonts,
     data: impl IntoIterator<Item = Operation>,
 ) -> Vec<PdfChar> {
     let mut result = Vec::new();
-    let mut font = None;
-    let mut font_scale = 1.0;
-    let mut word_space = 0.0;
-    let mut char_space = 0.0;
-    let mut pointer = (0.0, 0.0);
-    let mut width_factor = 0.0;
-    let mut height_factor = 0.0;
+    let mut loaded = HashMap::new();
+    let mut state = GraphicsStack::default();
     for op in data.into_iter() {
+        if state.apply(&op) {
+            continue;
+        }
         match op.operator.as_str() {
-            "Tfs" => {
-                tracing::warn!("Tfs operator not supported, skipping");
+            "Tj" | "TJ" => {
+                show_operands(&mut state, &fonts, &mut loaded, &op.operands, &mut result)
             }
-            "Tf" => {
-                font = Some(fonts.get(op.operands[0].as_name_str().unwrap()));
-                font_scale = extract_num(&op.operands[1]);
+            "'" => {
+                state.next_line_by_leading();
+                show_operands(&mut state, &fonts, &mut loaded, &op.operands, &mut result);
             }
-            "Tc" => char_space = extract_num(&op.operands[0]),
-            "Tw" => word_space = extract_num(&op.operands[0]),
-            "T*" => {
-                pointer.1 -= height_factor * PDF_TEXT_HEIGHT_FACTOR;
+            "\"" => {
+                let [word_space, char_space, operands @ ..] = &op.operands[..] else {
+                    tracing::warn!(?op.operands, "malformed \" operator, skipping");
+                    continue;
+                };
+                let text = state.text_mut();
+                text.word_space = extract_num(word_space);
+                text.char_space = extract_num(char_space);
+                state.next_line_by_leading();
+                show_operands(&mut state, &fonts, &mut loaded, operands, &mut result);
             }
-            "Td" | "TD" => {
-                pointer.0 += extract_num(&op.operands[0]) * width_factor;
-                pointer.1 += extract_num(&op.operands[1]) * height_factor;
+            _ => {}
+        }
+    }
+    result
+}
+
+/// Shows the string operands of Tj/TJ/'/" in the current font.
+fn show_operands<'a>(
+    state: &mut GraphicsStack,
+    fonts: &'a crate::pdf::PdfFonts,
+    loaded: &mut HashMap<String, crate::pdf::PdfFont<'a>>,
+    operands: &[Object],
+    result: &mut Vec<PdfChar>,
+) {
+    let Some(name) = state.state().text.font.clone() else {
+        tracing::warn!("text shown before Tf, skipping");
+        return;
+    };
+    let font = loaded
+        .entry(name)
+        .or_insert_with_key(|name| fonts.get(name));
+    for operand in operands {
+        show_text(state, font, operand, result);
+    }
+}
+
+/// Emits the glyphs of one Tj/TJ operand and advances the text matrix.
+fn show_text(
+    state: &mut GraphicsStack,
+    font: &crate::pdf::PdfFont,
+    operand: &Object,
+    result: &mut Vec<PdfChar>,
+) {
+    match operand {
+        Object::String(s, _) if matches!(font, crate::pdf::PdfFont::CidFont { .. }) => {
+            for code in font.split_codes(s) {
+                let text = font.get_cid_char(code);
+                let width = font.get_cid_width(code);
+                show_glyph(
+                    state,
+                    Either::Right((code.into(), text)),
+                    None,
+                    width,
+                    false,
+                    result,
+                );
             }
-            "Tm" | "Tlm" => {
-                if extract_num(&op.operands[0]) == extract_num(&op.operands[3])
-                    && extract_num(&op.operands[1]) == 0.0
-                    && extract_num(&op.operands[2]) == 0.0
-                {
-                    pointer.0 = extract_num(&op.operands[4]);
-                    pointer.1 = extract_num(&op.operands[5]);
-                }
-                width_factor = extract_num(&op.operands[0]);
-                height_factor = extract_num(&op.operands[3]);
+        }
+        Object::String(s, _) => {
+            for &c in s {
+                let width = font.get_char_width(c);
+                let decoded = font.get_char_text(c);
+                show_glyph(state, Either::Left(c), decoded, width, c == b' ', result);
             }
-            "Tj" | "TJ" => {
-                let mut last_x = pointer.0;
-                for operand in op.operands {
-                    match operand {
-                        Object::String(s, StringFormat::Literal) => {
-                            for c in s {
-                                let width = font.as_ref().unwrap().get_char_width(c)
-                                    * width_factor
-                                    * font_scale;
-                                let height = height_factor;
-                                let _left_bottom = (last_x, pointer.1);
-                                let rect = Rect::new(
-                                    [last_x, pointer.1],
-                                    [last_x + width, pointer.1 + height],
-                                );
-                                let pdf_char = PdfChar {
-                                    raw: Either::Left(c),
-                                    rect,
-                                    font_scale: width_factor,
-                                    is_superscript: false,
-                                    is_subscript: false,
-                                    represent_as: None,
-                                };
-                                last_x += rect.width() + char_space;
-                                result.push(pdf_char);
-                            }
-                            last_x += word_space;
-                        }
-                        Object::String(s, StringFormat::Hexadecimal) => {
-                            debug!(?s, "Hex in Tj");
-                            for c_hex in s.chunks(2) {
-                                let c_hex = [c_hex[0], c_hex[1]];
-                                let c = font.as_ref().unwrap().get_cid_char(c_hex);
-                                let width = font.as_ref().unwrap().get_cid_width(c_hex)
-                                    * width_factor
-                                    * font_scale;
-                                let height = height_factor;
-                                let rect = Rect::new(
-                                    [last_x, pointer.1],
-                                    [last_x + width, pointer.1 + height],
-                                );
-                                let pdf_char = PdfChar {
-                                    raw: Either::Right((c_hex.into(), c)),
-                                    rect,
-                                    font_scale: width_factor,
-                                    is_superscript: false,
-                                    is_subscript: false,
-                                    represent_as: None,
-                                };
-                                last_x += rect.width() + char_space;
-                                result.push(pdf_char);
-                            }
-                            last_x += word_space;
-                        }
-                        Object::Array(operands) => {
-                            for operand in operands {
-                                match operand {
-                                    Object::Integer(i) => {
-                                        last_x -= i as f32 / 1000.0 * width_factor
-                                    }
-                                    Object::Real(i) => last_x -= i / 1000.0 * width_factor,
-                                    Object::String(s, StringFormat::Literal) => {
-                                        for c in s {
-                                            let width = font.as_ref().unwrap().get_char_width(c)
-                                                * width_factor
-                                                * font_scale;
-                                            let height = height_factor;
-                                            let _left_bottom = (last_x, pointer.1);
-                                            let rect = Rect::new(
-                                                [last_x, pointer.1],
-                                                [last_x + width, pointer.1 + height],
-                                            );
-                                            let pdf_char = PdfChar {
-                                                raw: Either::Left(c),
-                                                rect,
-                                                font_scale: width_factor,
-                                                is_superscript: false,
-                                                is_subscript: false,
-                                                represent_as: None,
-                                            };
-                                            last_x += rect.width() + char_space;
-                                            result.push(pdf_char);
-                                        }
-                                        last_x += word_space;
-                                    }
-                                    Object::String(s, StringFormat::Hexadecimal) => {
-                                        debug!(?s, "Hex in Tj");
-                                        for c_hex in s.chunks(2) {
-                                            let c_hex = [c_hex[0], c_hex[1]];
-                                            let c = font.as_ref().unwrap().get_cid_char(c_hex);
-                                            let width = font.as_ref().unwrap().get_cid_width(c_hex)
-                                                * width_factor
-                                                * font_scale;
-                                            let height = height_factor;
-                                            let rect = Rect::new(
-                                                [last_x, pointer.1],
-                                                [last_x + width, pointer.1 + height],
-                                            );
-                                            let pdf_char = PdfChar {
-                                                raw: Either::Right((c_hex.into(), c)),
-                                                rect,
-                                                font_scale: width_factor,
-                                                is_superscript: false,
-                                                is_subscript: false,
-                                                represent_as: None,
-                                            };
-                                            last_x += rect.width() + char_space;
-                                            result.push(pdf_char);
-                                        }
-                                        last_x += word_space;
-                                    }
-                                    _ => {
-                                        tracing::warn!(
-                                            ?operand,
-                                            "unexpected operand in TJ array, skipping"
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                        _ => {
-                            tracing::warn!(?operand, "unexpected operand in Tj/TJ, skipping");
-                        }
+        }
+        Object::Array(operands) => {
+            for operand in operands {
+                match operand {
+                    Object::Integer(_) | Object::Real(_) => {
+                        let text = &state.state().text;
+                        let scale = text.font_size * text.horizontal_scaling;
+                        state.advance(-extract_num(operand) / 1000.0 * scale);
+                    }
+                    Object::String(..) => show_text(state, font, operand, result),
+                    _ => {
+                        tracing::warn!(?operand, "unexpected operand in TJ array, skipping");
                     }
                 }
             }
-            _ => {}
+        }
+        _ => {
+            tracing::warn!(?operand, "unexpected operand in Tj/TJ, skipping");
         }
     }
-    result
 }
+
+/// Places one glyph of `width` (glyph space / 1000) at the current text position.
+/// The box spans the advance width and the font size above the rise, mapped through
+/// the text rendering matrix. Invisible text (`Tr` 3 or 7) only advances.
+fn show_glyph(
+    state: &mut GraphicsStack,
+    raw: Either<u8, (Box<[u8]>, String)>,
+    decoded: Option<String>,
+    width: f32,
+    is_space: bool,
+    result: &mut Vec<PdfChar>,
+) {
+    let text = &state.state().text;
+    if !text.is_invisible() {
+        let rendering = state.text_rendering_matrix();
+        result.push(PdfChar {
+            raw,
+            decoded,
+            rect: rendering.transform_rect(Rect::new([0.0, 0.0], [width, 1.0])),
+            font_scale: rendering.y_scale(),
+            is_superscript: false,
+            is_subscript: false,
+            represent_as: None,
+        });
+    }
+    let mut advance = width * text.font_size + text.char_space;
+    if is_space {
+        advance += text.word_space;
+    }
+    state.advance(advance * text.horizontal_scaling);
+}
+
 pub fn detect_strings(mut cs: Vec<PdfChar>) -> Vec<PdfString> {
     cs.iter_mut().for_each(PdfChar::make_ready);
     let nearby = |s: &PdfString, c: &PdfChar| {
@@ -325,7 +291,9 @@ impl PdfString {
     }
 }
 pub struct PdfChar {
-    raw: Either<u8, (Box<[u8; 2]>, char)>,
+    raw: Either<u8, (Box<[u8]>, String)>,
+    /// Text of a simple font code per the font's ToUnicode or encoding
+    decoded: Option<String>,
     // x, height
     pub rect: Rect<f32>,
     font_scale: f32,
@@ -338,8 +306,10 @@ impl PdfChar {
         if self.represent_as.is_some() {
             return;
         }
-        let data = match &self.raw {
-            Either::Left(raw) => match raw {
+        let data = match (&self.raw, &self.decoded) {
+            (Either::Left(_), Some(decoded)) => Self::to_plain_punctuation(decoded),
+            // no usable encoding: byte table tuned to the Intel SDM fonts
+            (Either::Left(raw), None) => match raw {
                 0x20..=0x7e => String::from_utf8([*raw].into()).unwrap(),
                 0x2 => '≠'.into(),
                 0x3 => '≥'.into(),
@@ -383,7 +353,7 @@ impl PdfChar {
                     '\u{FFFD}'.into()
                 }
             },
-            Either::Right(raw) => raw.1.into(),
+            (Either::Right(raw), _) => raw.1.clone(),
         };
         self.represent_as = Some(data);
     }
@@ -412,6 +382,27 @@ impl PdfChar {
     pub fn get(&self) -> &str {
         self.represent_as.as_ref().expect("make_ready not called")
     }
+    /// Typographic quotes, dashes and ligatures as the plain characters used in the output
+    fn to_plain_punctuation(text: &str) -> String {
+        let mut result = String::new();
+        for c in text.chars() {
+            match c {
+                '‘' | '’' => result.push('\''),
+                '“' | '”' => result.push('"'),
+                '‚' => result.push(','),
+                '•' | '–' | '—' => result.push('-'),
+                '«' => result.push_str("<<"),
+                '»' => result.push_str(">>"),
+                'ﬀ' => result.push_str("ff"),
+                'ﬁ' => result.push_str("fi"),
+                'ﬂ' => result.push_str("fl"),
+                'ﬃ' => result.push_str("ffi"),
+                'ﬄ' => result.push_str("ffl"),
+                _ => result.push(c),
+            }
+        }
+        result
+    }
     /// Convert a single ASCII char to its Unicode superscript equivalent if possible
     fn to_superscript(c: char) -> Option<char> {
         match c {
@@ -462,8 +453,12 @@ impl PdfChar {
 pub fn operator_to_boxes(data: impl IntoIterator<Item = Operation>) -> PdfBoxes {
     let mut result = Vec::new();
 
+    let mut state = GraphicsStack::default();
     let mut rect = Rect::new([0.0, 0.0], [0.0, 0.0]);
     for op in data.into_iter() {
+        if state.apply(&op) {
+            continue;
+        }
         match op.operator.as_str() {
             "f" | "F" => {
                 result.push(PdfBox {
@@ -479,7 +474,11 @@ pub fn operator_to_boxes(data: impl IntoIterator<Item = Operation>) -> PdfBoxes
                     extract_num(&op.operands[2]),
                     extract_num(&op.operands[3]),
                 ];
-                rect = Rect::new([x, y], [x + w, y + h]);
+                // path coordinates are in user space at the time of construction
+                rect = state
+                    .state()
+                    .ctm
+                    .transform_rect(Rect::new([x, y], [x + w, y + h]));
             }
             _ => {}
         }
@@ -731,6 +730,304 @@ mod tests {
         .into()
     }
 
+    /// One page with a Type1 font `F1`: space is 250 wide, `!` to `B` are 500.
+    /// `F2` is WinAnsi with `A` replaced by `alpha` and a ToUnicode mapping `B` to `≠`
+    /// and `C` to `fi`.
+    fn test_document() -> lopdf::Document {
+        use lopdf::dictionary;
+        let mut doc = lopdf::Document::with_version("1.5");
+        let descriptor = doc.add_object(dictionary! { "Type" => "FontDescriptor" });
+        let widths: Vec<Object> = (32..=66)
+            .map(|c| Object::Integer(if c == 32 { 250 } else { 500 }))
+            .collect();
+        let font = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "FirstChar" => 32,
+            "Widths" => widths,
+            "FontDescriptor" => descriptor,
+        });
+        let to_unicode = doc.add_object(lopdf::Stream::new(
+            dictionary! {},
+            b"2 beginbfchar\n<42> <2260>\n<43> <00660069>\nendbfchar".to_vec(),
+        ));
+        let encoded = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "TrueType",
+            "FirstChar" => 32,
+            "Widths" => vec![Object::Integer(500); 224],
+            "FontDescriptor" => descriptor,
+            "Encoding" => dictionary! {
+                "BaseEncoding" => "WinAnsiEncoding",
+                "Differences" => vec![0x41.into(), "alpha".into()],
+            },
+            "ToUnicode" => to_unicode,
+        });
+        let pages = doc.new_object_id();
+        let page = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages,
+            "Resources" => dictionary! {
+                "Font" => dictionary! { "F1" => font, "F2" => encoded },
+            },
+        });
+        doc.objects.insert(
+            pages,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![page.into()],
+                "Count" => 1,
+            }),
+        );
+        let catalog = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages });
+        doc.trailer.set("Root", catalog);
+        doc
+    }
+
+    fn chars(doc: &lopdf::Document, ops: Vec<Operation>) -> Vec<(String, Rect<f32>, f32)> {
+        let mut chars = operator_to_chars(crate::pdf::get_pdf_fonts(doc, 1), ops);
+        chars
+            .iter_mut()
+            .map(|c| {
+                c.make_ready();
+                (c.get().to_owned(), c.rect, c.font_scale)
+            })
+            .collect()
+    }
+
+    fn nums(operator: &str, operands: &[f32]) -> Operation {
+        Operation::new(
+            operator,
+            operands.iter().map(|&n| Object::Real(n)).collect(),
+        )
+    }
+
+    fn text(operator: &str, operands: Vec<Object>) -> Operation {
+        Operation::new(operator, operands)
+    }
+
+    #[test]
+    fn test_operator_to_chars_text_matrix() {
+        let doc = test_document();
+        let chars = chars(
+            &doc,
+            vec![
+                nums("BT", &[]),
+                text("Tf", vec![Object::Name(b"F1".to_vec()), Object::Integer(1)]),
+                nums("Tm", &[10.0, 0.0, 0.0, 10.0, 72.0, 700.0]),
+                nums("Tw", &[0.5]),
+                text("Tj", vec![Object::string_literal("A B")]),
+                nums("Td", &[0.0, -2.0]),
+                text(
+                    "TJ",
+                    vec![Object::Array(vec![
+                        Object::string_literal("A"),
+                        Object::Integer(-500),
+                        Object::string_literal("B"),
+                    ])],
+                ),
+                nums("ET", &[]),
+            ],
+        );
+        let rects: Vec<(&str, [f32; 4])> = chars
+            .iter()
+            .map(|(c, r, _)| (c.as_str(), [r.min().x, r.min().y, r.max().x, r.max().y]))
+            .collect();
+        assert_eq!(
+            rects,
+            vec![
+                ("A", [72.0, 700.0, 77.0, 710.0]),
+                // space advances by its width plus Tw, both scaled by Tm
+                (" ", [77.0, 700.0, 79.5, 710.0]),
+                ("B", [84.5, 700.0, 89.5, 710.0]),
+                // Td starts from the line start, not the last glyph
+                ("A", [72.0, 680.0, 77.0, 690.0]),
+                ("B", [82.0, 680.0, 87.0, 690.0]),
+            ]
+        );
+        assert!(chars.iter().all(|(_, _, scale)| *scale == 10.0));
+    }
+
+    #[test]
+    fn test_operator_to_chars_graphics_state() {
+        let doc = test_document();
+        let chars = chars(
+            &doc,
+            vec![
+                nums("q", &[]),
+                // rotate 90° counterclockwise around (100, 100)
+                nums("cm", &[0.0, 1.0, -1.0, 0.0, 100.0, 100.0]),
+                nums("BT", &[]),
+                text(
+                    "Tf",
+                    vec![Object::Name(b"F1".to_vec()), Object::Integer(10)],
+                ),
+                text("Tj", vec![Object::string_literal("AB")]),
+                nums("ET", &[]),
+                nums("Q", &[]),
+                nums("BT", &[]),
+                text(
+                    "Tf",
+                    vec![Object::Name(b"F1".to_vec()), Object::Integer(10)],
+                ),
+                nums("Td", &[20.0, 30.0]),
+                text("Tj", vec![Object::string_literal("A")]),
+                nums("ET", &[]),
+            ],
+        );
+        let rects: Vec<[f32; 4]> = chars
+            .iter()
+            .map(|(_, r, _)| [r.min().x, r.min().y, r.max().x, r.max().y])
+            .collect();
+        assert_eq!(
+            rects,
+            vec![
+                [90.0, 100.0, 100.0, 105.0],
+                [90.0, 105.0, 100.0, 110.0],
+                // CTM restored by Q
+                [20.0, 30.0, 25.0, 40.0],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_operator_to_chars_text_state() {
+        let doc = test_document();
+        let chars = chars(
+            &doc,
+            vec![
+                nums("BT", &[]),
+                text(
+                    "Tf",
+                    vec![Object::Name(b"F1".to_vec()), Object::Integer(10)],
+                ),
+                nums("Td", &[100.0, 700.0]),
+                nums("Tz", &[50.0]),
+                text("Tj", vec![Object::string_literal("AB")]),
+                nums("Tz", &[100.0]),
+                nums("TL", &[12.0]),
+                nums("T*", &[]),
+                // invisible glyphs still advance
+                nums("Tr", &[3.0]),
+                text("Tj", vec![Object::string_literal("A")]),
+                nums("Tr", &[0.0]),
+                text("Tj", vec![Object::string_literal("B")]),
+                text("'", vec![Object::string_literal("A")]),
+                text(
+                    "\"",
+                    vec![
+                        Object::Real(2.0),
+                        Object::Real(1.0),
+                        Object::string_literal("A A"),
+                    ],
+                ),
+                nums("Ts", &[3.0]),
+                text("Tj", vec![Object::string_literal("B")]),
+                nums("ET", &[]),
+            ],
+        );
+        let rects: Vec<(&str, [f32; 4])> = chars
+            .iter()
+            .map(|(c, r, _)| (c.as_str(), [r.min().x, r.min().y, r.max().x, r.max().y]))
+            .collect();
+        assert_eq!(
+            rects,
+            vec![
+                // Tz narrows both the glyph and its advance
+                ("A", [100.0, 700.0, 102.5, 710.0]),
+                ("B", [102.5, 700.0, 105.0, 710.0]),
+                ("B", [105.0, 688.0, 110.0, 698.0]),
+                ("A", [100.0, 676.0, 105.0, 686.0]),
+                // " sets Tw and Tc before moving to the next line
+                ("A", [100.0, 664.0, 105.0, 674.0]),
+                (" ", [106.0, 664.0, 108.5, 674.0]),
+                ("A", [111.5, 664.0, 116.5, 674.0]),
+                // Ts raises the glyph above the baseline
+                ("B", [117.5, 667.0, 122.5, 677.0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_strings_text_rise() {
+        let doc = test_document();
+        let strings = |rise: f32| {
+            let ops = vec![
+                nums("BT", &[]),
+                text(
+                    "Tf",
+                    vec![Object::Name(b"F1".to_vec()), Object::Integer(10)],
+                ),
+                nums("Td", &[100.0, 700.0]),
+                text("Tj", vec![Object::string_literal("A")]),
+                text("Tf", vec![Object::Name(b"F1".to_vec()), Object::Integer(6)]),
+                nums("Ts", &[rise]),
+                text("Tj", vec![Object::string_literal("2")]),
+                text(
+                    "Tf",
+                    vec![Object::Name(b"F1".to_vec()), Object::Integer(10)],
+                ),
+                nums("Ts", &[0.0]),
+                text("Tj", vec![Object::string_literal("A")]),
+                nums("ET", &[]),
+            ];
+            let chars = operator_to_chars(crate::pdf::get_pdf_fonts(&doc, 1), ops);
+            detect_strings(chars)
+                .iter()
+                .map(PdfString::get)
+                .collect::<Vec<_>>()
+        };
+        // a smaller glyph on the baseline stays as is
+        assert_eq!(strings(0.0), vec!["A2A"]);
+        assert_eq!(strings(5.0), vec!["A²A"]);
+        assert_eq!(strings(-1.0), vec!["A₂A"]);
+    }
+
+    #[test]
+    fn test_operator_to_chars_encoding() {
+        let doc = test_document();
+        let text = |font: &str, string: &[u8]| -> String {
+            let ops = vec![
+                nums("BT", &[]),
+                text(
+                    "Tf",
+                    vec![Object::Name(font.as_bytes().to_vec()), Object::Integer(10)],
+                ),
+                text("Tj", vec![Object::string_literal(string)]),
+                nums("ET", &[]),
+            ];
+            chars(&doc, ops).into_iter().map(|(c, _, _)| c).collect()
+        };
+        // ToUnicode, then Differences, then WinAnsi with quotes and dashes made plain
+        assert_eq!(text("F2", b"AB\x93C\x94\x96\xae\xe9"), "α≠\"fi\"-®é");
+        // codes WinAnsi leaves undefined still reach the byte table
+        assert_eq!(text("F2", b"1\x8f2"), "1≠2");
+        // a font without an encoding keeps the byte table
+        assert_eq!(text("F1", b"AB\x02"), "AB≠");
+    }
+
+    #[test]
+    fn test_operator_to_boxes_ctm() {
+        let ops = vec![
+            nums("q", &[]),
+            nums("cm", &[2.0, 0.0, 0.0, 2.0, 10.0, 20.0]),
+            nums("re", &[0.0, 0.0, 5.0, 1.0]),
+            nums("f", &[]),
+            nums("Q", &[]),
+            nums("re", &[0.0, 0.0, 5.0, 1.0]),
+            nums("f", &[]),
+        ];
+        let boxes = operator_to_boxes(ops);
+        let rects: Vec<Rect<f32>> = boxes.get_lines().iter().map(|b| b.rect).collect();
+        assert_eq!(
+            rects,
+            vec![
+                Rect::new([10.0, 20.0], [20.0, 22.0]),
+                Rect::new([0.0, 0.0], [5.0, 1.0]),
+            ]
+        );
+    }
+
     #[test]
     fn test_prepare_cells_fit() {
         let cells = generate_cells(&[
@@ -0,0 +1,229 @@
+pub use crate::pseudocode::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    /// `-x`
+    Neg,
+    /// `!x`
+    Not,
+    /// `NOT x`
+    BitNot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    /// `||`
+    LogicalOr,
+    /// `&&`
+    LogicalAnd,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// `x IN {'0x', '11'}`
+    In,
+    /// `OR`
+    Or,
+    /// `EOR`
+    Eor,
+    /// `AND`
+    And,
+    /// `a : b` bit concatenation, also `<a, b>` as an assignment target
+    Concat,
+    Add,
+    Sub,
+    Mul,
+    /// `/`
+    Div,
+    /// `DIV`
+    IntDiv,
+    Mod,
+    Rem,
+    Shl,
+    Shr,
+    /// `^`
+    Pow,
+}
+
+/// Declared type: `bits(datasize)`, `integer`, `boolean`, `AccType`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Bits(Box<Expr>),
+    Named(String),
+    /// `array [0..3] of bits(VL)`
+    Array {
+        low: Box<Expr>,
+        high: Box<Expr>,
+        element: Box<Type>,
+    },
+}
+
+/// One range inside `x<...>`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SliceRange {
+    /// `x<3>`
+    Bit(Expr),
+    /// `x<7:0>`
+    Range { high: Expr, low: Expr },
+    /// `x<lsb+:width>`
+    Width { low: Expr, width: Expr },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+impl Expr {
+    /// `AArch64.CheckAlignment` from an `Ident`/`Field` chain
+    pub fn dotted_name(&self) -> Option<String> {
+        match &self.kind {
+            ExprKind::Ident(name) => Some(name.clone()),
+            ExprKind::Field { base, name } => Some(format!("{}.{name}", base.dotted_name()?)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind {
+    /// `64`, `0x1F` - value is None when the literal doesn't fit or is not an integer
+    Number {
+        text: String,
+        value: Option<u128>,
+    },
+    /// `'0101'`, `'1x'` (quotes and spaces removed)
+    Bits(String),
+    /// `"ADD (immediate)"`
+    Str(String),
+    /// `datasize`, `TRUE`, `Constraint_UNDEF`
+    Ident(String),
+    /// `PSTATE.N`
+    Field {
+        base: Box<Expr>,
+        name: String,
+    },
+    /// `PSTATE.<N,Z,C,V>`
+    Fields {
+        base: Box<Expr>,
+        names: Vec<String>,
+    },
+    /// `X[d]`, `SP[]`, `Elem[operand, e, esize]`
+    Index {
+        base: Box<Expr>,
+        args: Vec<Expr>,
+    },
+    /// `imm<7:0>`, `X[n]<63:32>`, `op<2,0>`
+    Slice {
+        base: Box<Expr>,
+        ranges: Vec<SliceRange>,
+    },
+    /// `UInt(Rd)`
+    Call {
+        function: String,
+        args: Vec<Expr>,
+    },
+    Unary {
+        op: UnaryOp,
+        operand: Box<Expr>,
+    },
+    Binary {
+        op: BinaryOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+    /// `(result, nzcv)`
+    Tuple(Vec<Expr>),
+    /// `{'0x', '11'}` on the right of `IN`
+    Set(Vec<Expr>),
+    /// `if sf == '1' then 64 else 32` - `elsif` nests in `else_value`
+    Conditional {
+        condition: Box<Expr>,
+        then_value: Box<Expr>,
+        else_value: Box<Expr>,
+    },
+    /// `bits(64) UNKNOWN`
+    Unknown(Type),
+    /// `boolean IMPLEMENTATION_DEFINED "Has SHA1 Crypto instructions"`
+    ImplementationDefined {
+        ty: Type,
+        description: Option<String>,
+    },
+    /// `-` as a tuple assignment target: `(-, carry) = ...`
+    Discard,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stmt {
+    pub kind: StmtKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StmtKind {
+    /// `bits(datasize) imm;`, `constant integer esize = 8;`, `integer a, b;`
+    Decl {
+        ty: Type,
+        names: Vec<String>,
+        value: Option<Expr>,
+        constant: bool,
+    },
+    /// `X[d] = result;`, `(result, nzcv) = AddWithCarry(...);`
+    Assign {
+        target: Expr,
+        value: Expr,
+    },
+    /// Call used as a statement: `CheckFPAdvSIMDEnabled64();`
+    Expr(Expr),
+    /// `if ... then ... elsif ... else ...` - block or single-line form
+    If {
+        condition: Expr,
+        then_branch: Vec<Stmt>,
+        elsif_branches: Vec<(Expr, Vec<Stmt>)>,
+        else_branch: Vec<Stmt>,
+    },
+    /// `case sh of when '0' ... otherwise ...`
+    Case {
+        subject: Expr,
+        arms: Vec<WhenArm>,
+        otherwise: Option<Vec<Stmt>>,
+    },
+    /// `for e = 0 to elements-1` (also `downto`)
+    For {
+        variable: String,
+        start: Expr,
+        end: Expr,
+        descending: bool,
+        body: Vec<Stmt>,
+    },
+    /// `while cond do`
+    While {
+        condition: Expr,
+        body: Vec<Stmt>,
+    },
+    /// `repeat ... until cond;`
+    Repeat {
+        body: Vec<Stmt>,
+        condition: Expr,
+    },
+    Return(Option<Expr>),
+    Assert(Expr),
+    Undefined,
+    Unpredictable,
+    /// `SEE "ADD (extended register)";`
+    See(String),
+    /// `IMPLEMENTATION_DEFINED "signal slave-generated error";`
+    ImplementationDefined(Option<String>),
+    /// `// ...` or `/* ... */` comment
+    Comment(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhenArm {
+    pub patterns: Vec<Expr>,
+    pub body: Vec<Stmt>,
+    pub span: Span,
+}
@@ -0,0 +1,396 @@
+use super::ast::*;
+
+/// Result of evaluating an ASL expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// A bitvector, `width` <= 64
+    Bits {
+        value: u64,
+        width: u32,
+    },
+    Integer(i128),
+    Boolean(bool),
+    /// Enumeration constant such as `Sys_AT`
+    Enum(String),
+}
+
+impl Value {
+    /// Bitvector of `width` bits: `Value::bits(0b011, 3)`
+    pub fn bits(value: u64, width: u32) -> Value {
+        Value::Bits {
+            value: value & ones(width),
+            width,
+        }
+    }
+
+    fn as_bool(&self) -> Result<bool, String> {
+        match self {
+            Value::Boolean(value) => Ok(*value),
+            other => Err(format!("expected boolean, found {other:?}")),
+        }
+    }
+
+    fn as_integer(&self) -> Result<i128, String> {
+        match self {
+            Value::Integer(value) => Ok(*value),
+            other => Err(format!("expected integer, found {other:?}")),
+        }
+    }
+
+    fn as_bits(&self) -> Result<(u64, u32), String> {
+        match self {
+            Value::Bits { value, width } => Ok((*value, *width)),
+            other => Err(format!("expected bits, found {other:?}")),
+        }
+    }
+}
+
+fn ones(width: u32) -> u64 {
+    if width >= 64 {
+        u64::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+/// `'1x0'` as (mask, value, width) - `x` bits are left out of the mask
+fn bit_pattern(text: &str) -> (u64, u64, u32) {
+    let (mut mask, mut value) = (0, 0);
+    for c in text.chars() {
+        mask <<= 1;
+        value <<= 1;
+        match c {
+            '0' => mask |= 1,
+            '1' => {
+                mask |= 1;
+                value |= 1;
+            }
+            _ => {}
+        }
+    }
+    (mask, value, text.len() as u32)
+}
+
+/// Evaluate `expr` with encoding fields looked up through `fields`.
+/// Unknown identifiers other than `TRUE`/`FALSE` evaluate to enumeration constants.
+pub fn eval(expr: &Expr, fields: &dyn Fn(&str) -> Option<Value>) -> Result<Value, String> {
+    match &expr.kind {
+        ExprKind::Number { text, value } => value
+            .and_then(|value| i128::try_from(value).ok())
+            .map(Value::Integer)
+            .ok_or_else(|| format!("unsupported number `{text}`")),
+        ExprKind::Bits(text) => {
+            let (mask, value, width) = bit_pattern(text);
+            if mask != ones(width) {
+                return Err(format!("bit pattern '{text}' outside a comparison"));
+            }
+            Ok(Value::bits(value, width))
+        }
+        ExprKind::Ident(name) => Ok(match name.as_str() {
+            "TRUE" => Value::Boolean(true),
+            "FALSE" => Value::Boolean(false),
+            _ => fields(name).unwrap_or_else(|| Value::Enum(name.clone())),
+        }),
+        ExprKind::Slice { base, ranges } => {
+            let (value, _) = eval(base, fields)?.as_bits()?;
+            let (mut result, mut result_width) = (0, 0);
+            for range in ranges {
+                let (high, low) = match range {
+                    SliceRange::Bit(bit) => {
+                        let bit = eval(bit, fields)?.as_integer()?;
+                        (bit, bit)
+                    }
+                    SliceRange::Range { high, low } => (
+                        eval(high, fields)?.as_integer()?,
+                        eval(low, fields)?.as_integer()?,
+                    ),
+                    SliceRange::Width { low, width } => {
+                        let low = eval(low, fields)?.as_integer()?;
+                        (low + eval(width, fields)?.as_integer()? - 1, low)
+                    }
+                };
+                if !(0..64).contains(&low) || !(low..64).contains(&high) {
+                    return Err(format!("slice <{high}:{low}> out of range"));
+                }
+                let width = (high - low + 1) as u32;
+                result = (result << width) | ((value >> low) & ones(width));
+                result_width += width;
+            }
+            Ok(Value::bits(result, result_width))
+        }
+        ExprKind::Call { function, args } => {
+            let args = args
+                .iter()
+                .map(|arg| eval(arg, fields))
+                .collect::<Result<Vec<_>, _>>()?;
+            call(function, &args)
+        }
+        ExprKind::Unary { op, operand } => {
+            let operand = eval(operand, fields)?;
+            match op {
+                UnaryOp::Not => Ok(Value::Boolean(!operand.as_bool()?)),
+                UnaryOp::Neg => Ok(Value::Integer(-operand.as_integer()?)),
+                UnaryOp::BitNot => {
+                    let (value, width) = operand.as_bits()?;
+                    Ok(Value::bits(!value, width))
+                }
+            }
+        }
+        ExprKind::Binary { op, lhs, rhs } => eval_binary(*op, lhs, rhs, fields),
+        ExprKind::Conditional {
+            condition,
+            then_value,
+            else_value,
+        } => {
+            if eval(condition, fields)?.as_bool()? {
+                eval(then_value, fields)
+            } else {
+                eval(else_value, fields)
+            }
+        }
+        _ => Err("unsupported expression".to_owned()),
+    }
+}
+
+/// Evaluate a boolean condition
+pub fn eval_condition(expr: &Expr, fields: &dyn Fn(&str) -> Option<Value>) -> Result<bool, String> {
+    eval(expr, fields)?.as_bool()
+}
+
+/// `lhs == 'x1x'` style comparison against a literal that may contain `x`
+fn matches_pattern(value: &Value, pattern: &str) -> Result<bool, String> {
+    let (value, width) = value.as_bits()?;
+    let (mask, expected, pattern_width) = bit_pattern(pattern);
+    if width != pattern_width {
+        return Err(format!("comparing bits({width}) with '{pattern}'"));
+    }
+    Ok(value & mask == expected)
+}
+
+fn eval_binary(
+    op: BinaryOp,
+    lhs: &Expr,
+    rhs: &Expr,
+    fields: &dyn Fn(&str) -> Option<Value>,
+) -> Result<Value, String> {
+    match op {
+        BinaryOp::LogicalAnd => {
+            return Ok(Value::Boolean(
+                eval_condition(lhs, fields)? && eval_condition(rhs, fields)?,
+            ))
+        }
+        BinaryOp::LogicalOr => {
+            return Ok(Value::Boolean(
+                eval_condition(lhs, fields)? || eval_condition(rhs, fields)?,
+            ))
+        }
+        BinaryOp::Eq | BinaryOp::Ne => {
+            if let ExprKind::Bits(pattern) = &rhs.kind {
+                let equal = matches_pattern(&eval(lhs, fields)?, pattern)?;
+                return Ok(Value::Boolean(equal == (op == BinaryOp::Eq)));
+            }
+        }
+        BinaryOp::In => {
+            let ExprKind::Set(patterns) = &rhs.kind else {
+                return Err("expected `{...}` after IN".to_owned());
+            };
+            let lhs = eval(lhs, fields)?;
+            for pattern in patterns {
+                let ExprKind::Bits(pattern) = &pattern.kind else {
+                    return Err("expected bit pattern in IN set".to_owned());
+                };
+                if matches_pattern(&lhs, pattern)? {
+                    return Ok(Value::Boolean(true));
+                }
+            }
+            return Ok(Value::Boolean(false));
+        }
+        _ => {}
+    }
+
+    let (lhs, rhs) = (eval(lhs, fields)?, eval(rhs, fields)?);
+    let result = match (op, &lhs, &rhs) {
+        (BinaryOp::Eq, _, _) => Value::Boolean(lhs == rhs),
+        (BinaryOp::Ne, _, _) => Value::Boolean(lhs != rhs),
+        (BinaryOp::Lt, Value::Integer(a), Value::Integer(b)) => Value::Boolean(a < b),
+        (BinaryOp::Le, Value::Integer(a), Value::Integer(b)) => Value::Boolean(a <= b),
+        (BinaryOp::Gt, Value::Integer(a), Value::Integer(b)) => Value::Boolean(a > b),
+        (BinaryOp::Ge, Value::Integer(a), Value::Integer(b)) => Value::Boolean(a >= b),
+        (BinaryOp::Add, Value::Integer(a), Value::Integer(b)) => Value::Integer(a + b),
+        (BinaryOp::Sub, Value::Integer(a), Value::Integer(b)) => Value::Integer(a - b),
+        (BinaryOp::Mul, Value::Integer(a), Value::Integer(b)) => Value::Integer(a * b),
+        (BinaryOp::Mod, Value::Integer(a), Value::Integer(b)) if *b != 0 => {
+            Value::Integer(a.rem_euclid(*b))
+        }
+        (BinaryOp::IntDiv, Value::Integer(a), Value::Integer(b)) if *b != 0 => {
+            Value::Integer(a.div_euclid(*b))
+        }
+        // bits(N) +/- integer wraps to N bits
+        (BinaryOp::Add, Value::Bits { value, width }, Value::Integer(b)) => {
+            Value::bits((*value as i128 + b) as u64, *width)
+        }
+        (BinaryOp::Sub, Value::Bits { value, width }, Value::Integer(b)) => {
+            Value::bits((*value as i128 - b) as u64, *width)
+        }
+        (BinaryOp::Concat, Value::Bits { .. }, Value::Bits { .. }) => {
+            let ((high, high_width), (low, low_width)) = (lhs.as_bits()?, rhs.as_bits()?);
+            if high_width + low_width > 64 {
+                return Err("concatenation wider than 64 bits".to_owned());
+            }
+            Value::bits((high << low_width) | low, high_width + low_width)
+        }
+        (BinaryOp::And | BinaryOp::Or | BinaryOp::Eor, Value::Bits { .. }, Value::Bits { .. }) => {
+            let ((a, width), (b, _)) = (lhs.as_bits()?, rhs.as_bits()?);
+            let value = match op {
+                BinaryOp::And => a & b,
+                BinaryOp::Or => a | b,
+                _ => a ^ b,
+            };
+            Value::bits(value, width)
+        }
+        _ => return Err(format!("unsupported operands for {op:?}: {lhs:?}, {rhs:?}")),
+    };
+    Ok(result)
+}
+
+/// Library functions used by alias preference conditions
+fn call(function: &str, args: &[Value]) -> Result<Value, String> {
+    let bits = |i: usize| -> Result<(u64, u32), String> {
+        args.get(i)
+            .ok_or_else(|| format!("{function}: missing argument {i}"))?
+            .as_bits()
+    };
+    let result = match function {
+        "UInt" => Value::Integer(bits(0)?.0 as i128),
+        "SInt" => {
+            let (value, width) = bits(0)?;
+            let shift = 128 - width;
+            Value::Integer(((value as i128) << shift) >> shift)
+        }
+        "IsZero" => Value::Boolean(bits(0)?.0 == 0),
+        "IsOnes" => {
+            let (value, width) = bits(0)?;
+            Value::Boolean(value == ones(width))
+        }
+        "BitCount" => Value::Integer(bits(0)?.0.count_ones() as i128),
+        "BFXPreferred" => Value::Boolean(bfx_preferred(
+            bits(0)?.0,
+            bits(1)?.0,
+            bits(2)?.0,
+            bits(3)?.0,
+        )),
+        "MoveWidePreferred" => Value::Boolean(move_wide_preferred(
+            bits(0)?.0,
+            bits(1)?.0,
+            bits(2)?.0,
+            bits(3)?.0,
+        )),
+        _ => return Err(format!("unsupported function {function}")),
+    };
+    Ok(result)
+}
+
+/// `BFXPreferred(bit sf, bit uns, bits(6) imms, bits(6) immr)` from the shared pseudocode
+fn bfx_preferred(sf: u64, uns: u64, imms: u64, immr: u64) -> bool {
+    // must not match UBFIZ/SBFIX alias
+    if imms < immr {
+        return false;
+    }
+    // must not match LSR/ASR/LSL alias (imms == 31 or 63)
+    if imms == (sf << 5) | 0b11111 {
+        return false;
+    }
+    // must not match UXTx/SXTx alias
+    if immr == 0 {
+        if sf == 0 && matches!(imms, 0b000111 | 0b001111) {
+            return false;
+        }
+        if sf == 1 && uns == 0 && matches!(imms, 0b000111 | 0b001111 | 0b011111) {
+            return false;
+        }
+    }
+    true
+}
+
+/// `MoveWidePreferred(bit sf, bit immN, bits(6) imms, bits(6) immr)` from the shared pseudocode
+fn move_wide_preferred(sf: u64, imm_n: u64, imms: u64, immr: u64) -> bool {
+    let (s, r) = (imms as i128, immr as i128);
+    let width = if sf == 1 { 64 } else { 32 };
+    // element size must equal total immediate size
+    if sf == 1 && imm_n != 1 {
+        return false;
+    }
+    if sf == 0 && (imm_n != 0 || imms & 0b100000 != 0) {
+        return false;
+    }
+    // for MOVZ must contain no more than 16 ones
+    if s < 16 {
+        return (-r).rem_euclid(16) <= 15 - s;
+    }
+    // for MOVN must contain no more than 16 zeros
+    if s >= width - 15 {
+        return r.rem_euclid(16) <= s - (width - 15);
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse_expr;
+    use super::*;
+
+    fn condition(text: &str, fields: &[(&str, u64, u32)]) -> Result<bool, String> {
+        let expr = parse_expr(text).map_err(|failure| failure.message)?;
+        eval_condition(&expr, &|name| {
+            fields
+                .iter()
+                .find(|(field, _, _)| *field == name)
+                .map(|&(_, value, width)| Value::bits(value, width))
+        })
+    }
+
+    #[test]
+    fn test_eval_condition() {
+        let fields = [
+            ("sf", 1, 1),
+            ("N", 1, 1),
+            ("imms", 0b111111, 6),
+            ("immr", 0b000011, 6),
+            ("Rn", 0b11111, 5),
+            ("Rm", 0b11111, 5),
+            ("cond", 0b1110, 4),
+            ("opc", 0b10, 2),
+        ];
+        assert_eq!(
+            condition("sf == '1' && N == '1' && imms == '111111'", &fields),
+            Ok(true)
+        );
+        assert_eq!(condition("cond != '111x' && Rn == Rm", &fields), Ok(false));
+        assert_eq!(
+            condition("Rn == '11111' && UInt(imms) < UInt(immr)", &fields),
+            Ok(false)
+        );
+        assert_eq!(
+            condition("imms + 1 == immr || opc<1> == '1'", &fields),
+            Ok(true)
+        );
+        assert_eq!(condition("BitCount(sf:N) > 1", &fields), Ok(true));
+        assert_eq!(
+            condition("BFXPreferred(sf, opc<1>, imms, immr)", &fields),
+            Ok(false)
+        );
+        assert!(condition("SysOp(sf, N, imms, immr) == Sys_AT", &fields).is_err());
+    }
+
+    #[test]
+    fn test_preferred_helpers() {
+        // UBFX x0, x1, #4, #8 → UBFM x0, x1, #4, #11
+        assert!(bfx_preferred(1, 1, 11, 4));
+        // UXTB w0, w1 → UBFM w0, w1, #0, #7
+        assert!(!bfx_preferred(0, 1, 7, 0));
+        // ORR w0, wzr, #0xffff: imms = 15, immr = 0
+        assert!(move_wide_preferred(0, 0, 15, 0));
+        // ORR w0, wzr, #0x0000ffff0000ffff-style repeating mask is not a MOVZ
+        assert!(!move_wide_preferred(0, 0, 0b100111, 0));
+    }
+}
@@ -0,0 +1,466 @@
+use super::ast::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Keyword {
+    If,
+    Then,
+    Elsif,
+    Else,
+    Case,
+    Of,
+    When,
+    Otherwise,
+    For,
+    To,
+    Downto,
+    While,
+    Do,
+    Repeat,
+    Until,
+    Return,
+    Assert,
+    Constant,
+    Undefined,
+    Unpredictable,
+    See,
+    Unknown,
+    ImplementationDefined,
+    In,
+    Div,
+    Mod,
+    Rem,
+    And,
+    Or,
+    Eor,
+    Not,
+}
+
+impl Keyword {
+    fn from_ident(ident: &str) -> Option<Self> {
+        let keyword = match ident {
+            "if" => Keyword::If,
+            "then" => Keyword::Then,
+            "elsif" => Keyword::Elsif,
+            "else" => Keyword::Else,
+            "case" => Keyword::Case,
+            "of" => Keyword::Of,
+            "when" => Keyword::When,
+            "otherwise" => Keyword::Otherwise,
+            "for" => Keyword::For,
+            "to" => Keyword::To,
+            "downto" => Keyword::Downto,
+            "while" => Keyword::While,
+            "do" => Keyword::Do,
+            "repeat" => Keyword::Repeat,
+            "until" => Keyword::Until,
+            "return" => Keyword::Return,
+            "assert" => Keyword::Assert,
+            "constant" => Keyword::Constant,
+            "UNDEFINED" => Keyword::Undefined,
+            "UNPREDICTABLE" => Keyword::Unpredictable,
+            "SEE" => Keyword::See,
+            "UNKNOWN" => Keyword::Unknown,
+            "IMPLEMENTATION_DEFINED" => Keyword::ImplementationDefined,
+            "IN" => Keyword::In,
+            "DIV" => Keyword::Div,
+            "MOD" => Keyword::Mod,
+            "REM" => Keyword::Rem,
+            "AND" => Keyword::And,
+            "OR" => Keyword::Or,
+            "EOR" => Keyword::Eor,
+            "NOT" => Keyword::Not,
+            _ => return None,
+        };
+        Some(keyword)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum TokenKind {
+    Ident(String),
+    Keyword(Keyword),
+    Number(String),
+    /// `'0101'` without the quotes
+    Bits(String),
+    /// `"text"` without the quotes
+    Str(String),
+    /// `=`
+    Assign,
+    Colon,
+    /// `+:` in `x<lsb+:width>`
+    PlusColon,
+    Semicolon,
+    Comma,
+    Dot,
+    /// `..` in `array [0..3]`
+    DotDot,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Shl,
+    Shr,
+    AndAnd,
+    OrOr,
+    Bang,
+    /// `// text` or `/* text */`
+    Comment(String),
+    /// End of a logical line
+    Newline,
+    /// The following lines are indented deeper than the enclosing block
+    Indent,
+    /// Closes one `Indent`
+    Dedent,
+    Unknown(char),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Token {
+    pub(super) kind: TokenKind,
+    pub(super) span: Span,
+    /// Whitespace precedes the token - `x<3>` is a slice, `x < 3` a comparison
+    pub(super) spaced: bool,
+}
+
+impl Token {
+    /// Tokens after which a line break continues the expression instead of ending the line
+    fn continues_line(&self) -> bool {
+        matches!(
+            self.kind,
+            TokenKind::Assign
+                | TokenKind::Colon
+                | TokenKind::PlusColon
+                | TokenKind::Comma
+                | TokenKind::Plus
+                | TokenKind::Minus
+                | TokenKind::Star
+                | TokenKind::Slash
+                | TokenKind::Caret
+                | TokenKind::Eq
+                | TokenKind::Ne
+                | TokenKind::Lt
+                | TokenKind::Le
+                | TokenKind::Gt
+                | TokenKind::Ge
+                | TokenKind::Shl
+                | TokenKind::Shr
+                | TokenKind::AndAnd
+                | TokenKind::OrOr
+                | TokenKind::Bang
+                | TokenKind::Keyword(
+                    Keyword::In
+                        | Keyword::Div
+                        | Keyword::Mod
+                        | Keyword::Rem
+                        | Keyword::And
+                        | Keyword::Or
+                        | Keyword::Eor
+                        | Keyword::Not
+                )
+        )
+    }
+}
+
+/// Whether the line after `rest` (which starts at a line break) continues the previous one:
+/// a condition split before `&&`, `||` or `then`
+fn next_line_continues(rest: &str) -> bool {
+    let rest = rest.trim_start();
+    rest.starts_with("&&")
+        || rest.starts_with("||")
+        || rest.strip_prefix("then").is_some_and(|after| {
+            !after.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+/// Split ASL into tokens. Indentation is turned into `Indent`/`Dedent` tokens and line
+/// ends into `Newline`, except inside brackets or after an operator (continued lines).
+pub(super) fn tokenize(source: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let offset = |i: usize| chars.get(i).map_or(source.len(), |c| c.0);
+    let mut line = 1;
+    let mut i = 0;
+    let mut column = 0;
+    let mut depth = 0usize;
+    let mut indents = vec![0];
+    let mut at_line_start = true;
+    let mut line_has_code = false;
+    let mut spaced = true;
+
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        if c == '\n' {
+            let continued = depth > 0
+                || tokens
+                    .iter()
+                    .rev()
+                    .find(|t| !matches!(t.kind, TokenKind::Comment(_)))
+                    .is_some_and(Token::continues_line)
+                || next_line_continues(&source[start..]);
+            if line_has_code && !continued {
+                tokens.push(Token {
+                    kind: TokenKind::Newline,
+                    span: Span {
+                        line,
+                        start,
+                        end: start,
+                    },
+                    spaced: false,
+                });
+                at_line_start = true;
+                line_has_code = false;
+            }
+            line += 1;
+            column = 0;
+            spaced = true;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            column += if c == '\t' { 4 } else { 1 };
+            spaced = true;
+            i += 1;
+            continue;
+        }
+        let token_line = line;
+        let next = chars.get(i + 1).map(|c| c.1);
+        let is_comment = c == '/' && matches!(next, Some('/' | '*'));
+
+        // Comment-only lines don't take part in the layout
+        if at_line_start && !is_comment {
+            let span = Span {
+                line,
+                start,
+                end: start,
+            };
+            let layout = |kind| Token {
+                kind,
+                span,
+                spaced: false,
+            };
+            if column > *indents.last().unwrap() {
+                indents.push(column);
+                tokens.push(layout(TokenKind::Indent));
+            }
+            while column < *indents.last().unwrap() {
+                indents.pop();
+                tokens.push(layout(TokenKind::Dedent));
+            }
+            at_line_start = false;
+        }
+
+        let (kind, len) = if c.is_ascii_alphabetic() || c == '_' {
+            let len = chars[i..]
+                .iter()
+                .take_while(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+                .count();
+            let ident: String = chars[i..i + len].iter().map(|c| c.1).collect();
+            let kind = match Keyword::from_ident(&ident) {
+                Some(keyword) => TokenKind::Keyword(keyword),
+                None => TokenKind::Ident(ident),
+            };
+            (kind, len)
+        } else if c.is_ascii_digit() {
+            let mut len = chars[i..]
+                .iter()
+                .take_while(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+                .count();
+            // Real literal: `0.5`
+            if chars.get(i + len).is_some_and(|c| c.1 == '.')
+                && chars.get(i + len + 1).is_some_and(|c| c.1.is_ascii_digit())
+            {
+                len += 1 + chars[i + len + 1..]
+                    .iter()
+                    .take_while(|(_, c)| c.is_ascii_digit())
+                    .count();
+            }
+            let number: String = chars[i..i + len].iter().map(|c| c.1).collect();
+            (TokenKind::Number(number), len)
+        } else if c == '\'' || c == '"' {
+            let close = chars[i + 1..]
+                .iter()
+                .take_while(|(_, ch)| *ch != '\n')
+                .position(|(_, ch)| *ch == c);
+            match close {
+                Some(close) => {
+                    let text: String = chars[i + 1..i + 1 + close].iter().map(|c| c.1).collect();
+                    let kind = if c == '\'' {
+                        TokenKind::Bits(text.replace(' ', ""))
+                    } else {
+                        TokenKind::Str(text)
+                    };
+                    (kind, close + 2)
+                }
+                None => (TokenKind::Unknown(c), 1),
+            }
+        } else if is_comment && next == Some('/') {
+            let len = chars[i..].iter().take_while(|(_, c)| *c != '\n').count();
+            let text: String = chars[i + 2..i + len].iter().map(|c| c.1).collect();
+            (TokenKind::Comment(text.trim().to_owned()), len)
+        } else if is_comment {
+            let body_start = i + 2;
+            let close = (body_start..chars.len().saturating_sub(1))
+                .find(|&j| chars[j].1 == '*' && chars[j + 1].1 == '/');
+            let body_end = close.unwrap_or(chars.len());
+            let text: String = chars[body_start..body_end].iter().map(|c| c.1).collect();
+            line += text.matches('\n').count();
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            let len = close.map_or(chars.len(), |close| close + 2) - i;
+            (TokenKind::Comment(text), len)
+        } else {
+            match (c, next) {
+                ('+', Some(':')) => (TokenKind::PlusColon, 2),
+                ('=', Some('=')) => (TokenKind::Eq, 2),
+                ('!', Some('=')) => (TokenKind::Ne, 2),
+                ('<', Some('=')) => (TokenKind::Le, 2),
+                ('>', Some('=')) => (TokenKind::Ge, 2),
+                ('<', Some('<')) => (TokenKind::Shl, 2),
+                ('>', Some('>')) => (TokenKind::Shr, 2),
+                ('&', Some('&')) => (TokenKind::AndAnd, 2),
+                ('|', Some('|')) => (TokenKind::OrOr, 2),
+                ('.', Some('.')) => (TokenKind::DotDot, 2),
+                ('=', _) => (TokenKind::Assign, 1),
+                (':', _) => (TokenKind::Colon, 1),
+                (';', _) => (TokenKind::Semicolon, 1),
+                (',', _) => (TokenKind::Comma, 1),
+                ('.', _) => (TokenKind::Dot, 1),
+                ('(', _) => (TokenKind::LParen, 1),
+                (')', _) => (TokenKind::RParen, 1),
+                ('[', _) => (TokenKind::LBracket, 1),
+                (']', _) => (TokenKind::RBracket, 1),
+                ('{', _) => (TokenKind::LBrace, 1),
+                ('}', _) => (TokenKind::RBrace, 1),
+                ('+', _) => (TokenKind::Plus, 1),
+                ('-', _) => (TokenKind::Minus, 1),
+                ('*', _) => (TokenKind::Star, 1),
+                ('/', _) => (TokenKind::Slash, 1),
+                ('^', _) => (TokenKind::Caret, 1),
+                ('<', _) => (TokenKind::Lt, 1),
+                ('>', _) => (TokenKind::Gt, 1),
+                ('!', _) => (TokenKind::Bang, 1),
+                _ => (TokenKind::Unknown(c), 1),
+            }
+        };
+
+        match kind {
+            TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => depth += 1,
+            TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace => {
+                depth = depth.saturating_sub(1)
+            }
+            TokenKind::Comment(_) => {}
+            _ => line_has_code = true,
+        }
+        tokens.push(Token {
+            kind,
+            span: Span {
+                line: token_line,
+                start,
+                end: offset(i + len),
+            },
+            spaced,
+        });
+        spaced = false;
+        column += len;
+        i += len;
+    }
+
+    let end = Span {
+        line,
+        start: source.len(),
+        end: source.len(),
+    };
+    if line_has_code {
+        tokens.push(Token {
+            kind: TokenKind::Newline,
+            span: end,
+            spaced: false,
+        });
+    }
+    for _ in 1..indents.len() {
+        tokens.push(Token {
+            kind: TokenKind::Dedent,
+            span: end,
+            spaced: false,
+        });
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source).into_iter().map(|t| t.kind).collect()
+    }
+
+    #[test]
+    fn test_tokenize_layout() {
+        let ident = |name: &str| TokenKind::Ident(name.to_owned());
+        assert_eq!(
+            kinds("if n == 31 then\n    // stack\n    a = SP[];\nelse\n    a = X[n,\n          64];\n"),
+            vec![
+                TokenKind::Keyword(Keyword::If),
+                ident("n"),
+                TokenKind::Eq,
+                TokenKind::Number("31".to_owned()),
+                TokenKind::Keyword(Keyword::Then),
+                TokenKind::Newline,
+                TokenKind::Comment("stack".to_owned()),
+                TokenKind::Indent,
+                ident("a"),
+                TokenKind::Assign,
+                ident("SP"),
+                TokenKind::LBracket,
+                TokenKind::RBracket,
+                TokenKind::Semicolon,
+                TokenKind::Newline,
+                TokenKind::Dedent,
+                TokenKind::Keyword(Keyword::Else),
+                TokenKind::Newline,
+                TokenKind::Indent,
+                ident("a"),
+                TokenKind::Assign,
+                ident("X"),
+                TokenKind::LBracket,
+                ident("n"),
+                TokenKind::Comma,
+                TokenKind::Number("64".to_owned()),
+                TokenKind::RBracket,
+                TokenKind::Semicolon,
+                TokenKind::Newline,
+                TokenKind::Dedent,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_slices_and_literals() {
+        let tokens = tokenize("imm<7:0> = '0000 1x'; b = x < 3;");
+        assert_eq!(tokens[1].kind, TokenKind::Lt);
+        assert!(!tokens[1].spaced);
+        assert_eq!(tokens[7].kind, TokenKind::Bits("00001x".to_owned()));
+        assert_eq!(tokens[12].kind, TokenKind::Lt);
+        assert!(tokens[12].spaced);
+        assert_eq!(
+            kinds("SEE \"ADD\";")[..2],
+            [
+                TokenKind::Keyword(Keyword::See),
+                TokenKind::Str("ADD".to_owned())
+            ]
+        );
+    }
+}
@@ -0,0 +1,10 @@
+mod ast;
+mod eval;
+mod lexer;
+mod parser;
+mod visit;
+
+pub use ast::*;
+pub use eval::{eval, eval_condition, Value};
+pub use parser::{parse, parse_expr, ParseFailure, ParsedPseudocode};
+pub use visit::{referenced_functions, walk_exprs};
@@ -0,0 +1,1141 @@
+use super::ast::*;
+use super::lexer::{tokenize, Keyword, Token, TokenKind};
+
+/// A source line the parser had to skip
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFailure {
+    /// 1-based line in the pseudocode text
+    pub line: usize,
+    /// The whole source line
+    pub text: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedPseudocode {
+    pub statements: Vec<Stmt>,
+    pub failures: Vec<ParseFailure>,
+}
+
+type ParseResult<T> = Result<T, String>;
+
+/// Keywords that end a single-line body: `if c then X; else Y;`, `when '0' X;`
+const INLINE_END_KEYWORDS: &[Keyword] = &[
+    Keyword::Elsif,
+    Keyword::Else,
+    Keyword::When,
+    Keyword::Otherwise,
+    Keyword::Until,
+];
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Span of the last consumed (non-comment) token
+    last_span: Span,
+    failures: Vec<ParseFailure>,
+    /// Comments skipped while looking ahead, emitted at the next statement boundary
+    comments: Vec<Stmt>,
+    /// Inside `x<...>`, where `:` and `>` belong to the slice
+    in_slice: bool,
+}
+
+/// Parse ASL decode or operation pseudocode.
+/// Lines that fail to parse are skipped and reported, the rest still produce the AST.
+pub fn parse(source: &str) -> ParsedPseudocode {
+    let mut parser = Parser {
+        source,
+        tokens: tokenize(source),
+        pos: 0,
+        last_span: Span::default(),
+        failures: Vec::new(),
+        comments: Vec::new(),
+        in_slice: false,
+    };
+    let mut statements = parser.parse_block(false);
+    parser.skip_comments();
+    statements.append(&mut parser.comments);
+    ParsedPseudocode {
+        statements,
+        failures: parser.failures,
+    }
+}
+
+/// Parse a single expression, such as an alias preference condition:
+/// `sf == '0' && N == '0' && imms == '011111'`
+pub fn parse_expr(source: &str) -> Result<Expr, ParseFailure> {
+    let mut parser = Parser {
+        source,
+        tokens: tokenize(source),
+        pos: 0,
+        last_span: Span::default(),
+        failures: Vec::new(),
+        comments: Vec::new(),
+        in_slice: false,
+    };
+    let result = parser.parse_expr().and_then(|expr| {
+        while parser.eat(TokenKind::Newline) {}
+        match parser.peek() {
+            None => Ok(expr),
+            Some(_) => Err(format!("unexpected {}", parser.describe_next())),
+        }
+    });
+    result.map_err(|message| {
+        let line = parser.current_line();
+        parser.fail(line, message);
+        parser.failures.remove(0)
+    })
+}
+
+fn binary_op(kind: &TokenKind, in_slice: bool) -> Option<(BinaryOp, u8)> {
+    let op = match kind {
+        TokenKind::OrOr => (BinaryOp::LogicalOr, 1),
+        TokenKind::AndAnd => (BinaryOp::LogicalAnd, 2),
+        TokenKind::Eq => (BinaryOp::Eq, 3),
+        TokenKind::Ne => (BinaryOp::Ne, 3),
+        TokenKind::Lt => (BinaryOp::Lt, 3),
+        TokenKind::Le => (BinaryOp::Le, 3),
+        TokenKind::Gt if !in_slice => (BinaryOp::Gt, 3),
+        TokenKind::Ge if !in_slice => (BinaryOp::Ge, 3),
+        TokenKind::Keyword(Keyword::In) => (BinaryOp::In, 3),
+        TokenKind::Keyword(Keyword::Or) => (BinaryOp::Or, 4),
+        TokenKind::Keyword(Keyword::Eor) => (BinaryOp::Eor, 4),
+        TokenKind::Keyword(Keyword::And) => (BinaryOp::And, 5),
+        TokenKind::Colon if !in_slice => (BinaryOp::Concat, 6),
+        TokenKind::Plus => (BinaryOp::Add, 6),
+        TokenKind::Minus => (BinaryOp::Sub, 6),
+        TokenKind::Star => (BinaryOp::Mul, 7),
+        TokenKind::Slash => (BinaryOp::Div, 7),
+        TokenKind::Keyword(Keyword::Div) => (BinaryOp::IntDiv, 7),
+        TokenKind::Keyword(Keyword::Mod) => (BinaryOp::Mod, 7),
+        TokenKind::Keyword(Keyword::Rem) => (BinaryOp::Rem, 7),
+        TokenKind::Shl => (BinaryOp::Shl, 8),
+        TokenKind::Shr if !in_slice => (BinaryOp::Shr, 8),
+        TokenKind::Caret => (BinaryOp::Pow, 9),
+        _ => return None,
+    };
+    Some(op)
+}
+
+/// Value of an integer literal: `64`, `0x1F`
+fn parse_number(text: &str) -> Option<u128> {
+    let text = text.replace('_', "");
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u128::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+impl Parser<'_> {
+    fn skip_comments(&mut self) {
+        while let Some(token) = self.tokens.get(self.pos) {
+            let TokenKind::Comment(text) = &token.kind else {
+                break;
+            };
+            self.comments.push(Stmt {
+                kind: StmtKind::Comment(text.clone()),
+                span: token.span,
+            });
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<&Token> {
+        self.skip_comments();
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_kind(&mut self) -> Option<TokenKind> {
+        self.peek().map(|token| token.kind.clone())
+    }
+
+    /// `n`-th upcoming token, ignoring comments
+    fn peek_nth_kind(&self, n: usize) -> Option<&TokenKind> {
+        self.tokens[self.pos.min(self.tokens.len())..]
+            .iter()
+            .filter(|token| !matches!(token.kind, TokenKind::Comment(_)))
+            .nth(n)
+            .map(|token| &token.kind)
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        self.skip_comments();
+        let token = self.tokens.get(self.pos).cloned()?;
+        self.pos += 1;
+        self.last_span = token.span;
+        Some(token)
+    }
+
+    fn eat(&mut self, kind: TokenKind) -> bool {
+        if self.peek_kind() == Some(kind) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: Keyword) -> bool {
+        self.eat(TokenKind::Keyword(keyword))
+    }
+
+    fn text(&self, span: Span) -> &str {
+        &self.source[span.start..span.end]
+    }
+
+    fn describe_next(&mut self) -> String {
+        match self.peek().map(|token| (token.kind.clone(), token.span)) {
+            Some((TokenKind::Newline, _)) => "end of line".to_owned(),
+            Some((TokenKind::Indent, _)) => "indentation".to_owned(),
+            Some((TokenKind::Dedent, _)) => "end of block".to_owned(),
+            Some((_, span)) => format!("`{}`", self.text(span)),
+            None => "end of input".to_owned(),
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind, what: &str) -> ParseResult<Token> {
+        if self.peek_kind() == Some(kind) {
+            Ok(self.bump().unwrap())
+        } else {
+            Err(format!("expected {what}, found {}", self.describe_next()))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: Keyword, what: &str) -> ParseResult<Token> {
+        self.expect(TokenKind::Keyword(keyword), what)
+    }
+
+    fn expect_ident(&mut self, what: &str) -> ParseResult<String> {
+        match self.peek_kind() {
+            Some(TokenKind::Ident(name)) => {
+                self.bump();
+                Ok(name)
+            }
+            _ => Err(format!("expected {what}, found {}", self.describe_next())),
+        }
+    }
+
+    /// Identifier, or a keyword used as a field name
+    fn expect_name(&mut self, what: &str) -> ParseResult<String> {
+        match self.peek_kind() {
+            Some(TokenKind::Keyword(_)) => {
+                let span = self.bump().unwrap().span;
+                Ok(self.text(span).to_owned())
+            }
+            _ => self.expect_ident(what),
+        }
+    }
+
+    fn current_line(&mut self) -> usize {
+        let last_line = self.last_span.line;
+        self.peek().map_or(last_line, |token| token.span.line)
+    }
+
+    fn fail(&mut self, line: usize, message: impl Into<String>) {
+        // One failure per line is enough to flag it
+        if self.failures.last().is_some_and(|f| f.line == line) {
+            return;
+        }
+        let text = self
+            .source
+            .lines()
+            .nth(line.saturating_sub(1))
+            .unwrap_or("")
+            .trim()
+            .to_owned();
+        self.failures.push(ParseFailure {
+            line,
+            text,
+            message: message.into(),
+        });
+    }
+
+    /// Skip the rest of a failed line, along with any block indented under it.
+    /// Stops before the `Dedent` that closes the current block.
+    fn recover(&mut self) {
+        let mut nesting = 0;
+        while let Some(token) = self.tokens.get(self.pos) {
+            match &token.kind {
+                TokenKind::Newline if nesting == 0 => {
+                    self.pos += 1;
+                    break;
+                }
+                TokenKind::Indent => nesting += 1,
+                TokenKind::Dedent if nesting == 0 => break,
+                TokenKind::Dedent => nesting -= 1,
+                TokenKind::Comment(text) => self.comments.push(Stmt {
+                    kind: StmtKind::Comment(text.clone()),
+                    span: token.span,
+                }),
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn checkpoint(&self) -> (usize, Span, usize) {
+        (self.pos, self.last_span, self.comments.len())
+    }
+
+    fn rewind(&mut self, (pos, last_span, comments): (usize, Span, usize)) {
+        self.pos = pos;
+        self.last_span = last_span;
+        self.comments.truncate(comments);
+    }
+
+    /// Statements up to the `Dedent` closing an indented block (or the end of input)
+    fn parse_block(&mut self, indented: bool) -> Vec<Stmt> {
+        let mut body = Vec::new();
+        loop {
+            self.skip_comments();
+            body.append(&mut self.comments);
+            let Some(token) = self.peek().cloned() else {
+                break;
+            };
+            match token.kind {
+                TokenKind::Newline => {
+                    self.bump();
+                }
+                TokenKind::Dedent => {
+                    self.bump();
+                    if indented {
+                        break;
+                    }
+                }
+                // Indentation without a header: keep the statements in this block
+                TokenKind::Indent => {
+                    self.bump();
+                    body.append(&mut self.parse_block(true));
+                }
+                TokenKind::Keyword(keyword) if INLINE_END_KEYWORDS.contains(&keyword) => {
+                    let text = self.text(token.span).to_owned();
+                    self.fail(token.span.line, format!("unexpected `{text}`"));
+                    self.recover();
+                }
+                _ => match self.parse_statement() {
+                    Ok(stmt) => body.push(stmt),
+                    Err(message) => {
+                        let line = self.current_line();
+                        self.fail(line, message);
+                        self.recover();
+                    }
+                },
+            }
+        }
+        body
+    }
+
+    /// Body after `then`, `else`, `do`, `when ...`: an indented block on the next lines,
+    /// or statements on the rest of the line
+    fn parse_body(&mut self) -> ParseResult<Vec<Stmt>> {
+        if self.peek_kind() == Some(TokenKind::Newline) {
+            // `otherwise // do nothing` - an empty body, maybe with a comment
+            if self.peek_nth_kind(1) != Some(&TokenKind::Indent) {
+                return Ok(std::mem::take(&mut self.comments));
+            }
+            self.bump();
+            self.bump();
+            return Ok(self.parse_block(true));
+        }
+        let mut body = Vec::new();
+        loop {
+            match self.peek_kind() {
+                None | Some(TokenKind::Newline | TokenKind::Dedent) => break,
+                Some(TokenKind::Keyword(keyword)) if INLINE_END_KEYWORDS.contains(&keyword) => {
+                    break
+                }
+                Some(_) => body.push(self.parse_statement()?),
+            }
+        }
+        body.append(&mut self.comments);
+        if body.is_empty() {
+            return Err(format!(
+                "expected a statement, found {}",
+                self.describe_next()
+            ));
+        }
+        Ok(body)
+    }
+
+    /// Move past the line end when the next line continues the statement (`else`, `until`)
+    fn eat_newline_before(&mut self, keywords: &[Keyword]) {
+        if self.peek_kind() == Some(TokenKind::Newline)
+            && matches!(self.peek_nth_kind(1), Some(TokenKind::Keyword(k)) if keywords.contains(k))
+        {
+            self.bump();
+        }
+    }
+
+    fn end_statement(&mut self) -> ParseResult<()> {
+        self.expect(TokenKind::Semicolon, "`;`").map(|_| ())
+    }
+
+    fn parse_statement(&mut self) -> ParseResult<Stmt> {
+        let start = self.peek().map(|token| token.span).unwrap_or_default();
+        let kind = match self.peek_kind() {
+            Some(TokenKind::Keyword(Keyword::If)) => self.parse_if()?,
+            Some(TokenKind::Keyword(Keyword::Case)) => self.parse_case()?,
+            Some(TokenKind::Keyword(Keyword::For)) => self.parse_for()?,
+            Some(TokenKind::Keyword(Keyword::While)) => {
+                self.bump();
+                let condition = self.parse_expr()?;
+                self.expect_keyword(Keyword::Do, "`do`")?;
+                let body = self.parse_body()?;
+                StmtKind::While { condition, body }
+            }
+            Some(TokenKind::Keyword(Keyword::Repeat)) => {
+                self.bump();
+                let body = self.parse_body()?;
+                self.eat_newline_before(&[Keyword::Until]);
+                self.expect_keyword(Keyword::Until, "`until`")?;
+                let condition = self.parse_expr()?;
+                self.end_statement()?;
+                StmtKind::Repeat { body, condition }
+            }
+            Some(TokenKind::Keyword(Keyword::Return)) => {
+                self.bump();
+                let value = if self.eat(TokenKind::Semicolon) {
+                    None
+                } else {
+                    let value = self.parse_expr()?;
+                    self.end_statement()?;
+                    Some(value)
+                };
+                StmtKind::Return(value)
+            }
+            Some(TokenKind::Keyword(Keyword::Assert)) => {
+                self.bump();
+                let condition = self.parse_expr()?;
+                self.end_statement()?;
+                StmtKind::Assert(condition)
+            }
+            Some(TokenKind::Keyword(Keyword::Undefined)) => {
+                self.bump();
+                self.end_statement()?;
+                StmtKind::Undefined
+            }
+            Some(TokenKind::Keyword(Keyword::Unpredictable)) => {
+                self.bump();
+                self.end_statement()?;
+                StmtKind::Unpredictable
+            }
+            Some(TokenKind::Keyword(Keyword::See)) => {
+                self.bump();
+                let target = match self.peek_kind() {
+                    Some(TokenKind::Str(text)) => {
+                        self.bump();
+                        text
+                    }
+                    _ => {
+                        let expr = self.parse_expr()?;
+                        self.text(expr.span).to_owned()
+                    }
+                };
+                self.end_statement()?;
+                StmtKind::See(target)
+            }
+            Some(TokenKind::Keyword(Keyword::ImplementationDefined)) => {
+                self.bump();
+                let description = self.parse_description();
+                self.end_statement()?;
+                StmtKind::ImplementationDefined(description)
+            }
+            Some(TokenKind::Keyword(Keyword::Constant)) => {
+                self.bump();
+                let ty = self.parse_type()?;
+                self.parse_decl(ty, true)?
+            }
+            _ => {
+                if let Some(ty) = self.try_decl_type() {
+                    self.parse_decl(ty, false)?
+                } else {
+                    let target = self.parse_expr()?;
+                    if self.eat(TokenKind::Assign) {
+                        let value = self.parse_expr()?;
+                        self.end_statement()?;
+                        StmtKind::Assign { target, value }
+                    } else {
+                        self.end_statement()?;
+                        StmtKind::Expr(target)
+                    }
+                }
+            }
+        };
+        Ok(Stmt {
+            kind,
+            span: start.to(self.last_span),
+        })
+    }
+
+    /// `bits(N)`, `integer`, `AccType`, `array [0..3] of bits(VL)`
+    fn parse_type(&mut self) -> ParseResult<Type> {
+        let name = self.expect_ident("type")?;
+        if name == "array" && self.eat(TokenKind::LBracket) {
+            let low = self.parse_expr()?;
+            self.expect(TokenKind::DotDot, "`..`")?;
+            let high = self.parse_expr()?;
+            self.expect(TokenKind::RBracket, "`]`")?;
+            self.expect_keyword(Keyword::Of, "`of`")?;
+            let element = self.parse_type()?;
+            Ok(Type::Array {
+                low: Box::new(low),
+                high: Box::new(high),
+                element: Box::new(element),
+            })
+        } else if name == "bits" && self.eat(TokenKind::LParen) {
+            let width = self.parse_expr()?;
+            self.expect(TokenKind::RParen, "`)`")?;
+            Ok(Type::Bits(Box::new(width)))
+        } else {
+            Ok(Type::Named(name))
+        }
+    }
+
+    /// Type of a declaration at the current position - a type followed by a name
+    fn try_decl_type(&mut self) -> Option<Type> {
+        if !matches!(self.peek_kind(), Some(TokenKind::Ident(_))) {
+            return None;
+        }
+        let checkpoint = self.checkpoint();
+        match self.parse_type() {
+            Ok(ty) if matches!(self.peek_kind(), Some(TokenKind::Ident(_))) => Some(ty),
+            _ => {
+                self.rewind(checkpoint);
+                None
+            }
+        }
+    }
+
+    fn parse_decl(&mut self, ty: Type, constant: bool) -> ParseResult<StmtKind> {
+        let mut names = vec![self.expect_ident("variable name")?];
+        while self.eat(TokenKind::Comma) {
+            names.push(self.expect_ident("variable name")?);
+        }
+        let value = if self.eat(TokenKind::Assign) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        self.end_statement()?;
+        Ok(StmtKind::Decl {
+            ty,
+            names,
+            value,
+            constant,
+        })
+    }
+
+    fn parse_if(&mut self) -> ParseResult<StmtKind> {
+        self.bump();
+        let condition = self.parse_expr()?;
+        self.expect_keyword(Keyword::Then, "`then`")?;
+        let then_branch = self.parse_body()?;
+        let mut elsif_branches = Vec::new();
+        let mut else_branch = Vec::new();
+        loop {
+            self.eat_newline_before(&[Keyword::Elsif, Keyword::Else]);
+            if self.eat_keyword(Keyword::Elsif) {
+                let condition = self.parse_expr()?;
+                self.expect_keyword(Keyword::Then, "`then`")?;
+                elsif_branches.push((condition, self.parse_body()?));
+            } else {
+                if self.eat_keyword(Keyword::Else) {
+                    else_branch = self.parse_body()?;
+                }
+                break;
+            }
+        }
+        Ok(StmtKind::If {
+            condition,
+            then_branch,
+            elsif_branches,
+            else_branch,
+        })
+    }
+
+    fn parse_case(&mut self) -> ParseResult<StmtKind> {
+        let line = self.bump().unwrap().span.line;
+        let subject = self.parse_expr()?;
+        self.expect_keyword(Keyword::Of, "`of`")?;
+        self.expect(TokenKind::Newline, "end of line")?;
+        // Arms are normally indented, but some pages put `when` level with `case`
+        let indented = self.eat(TokenKind::Indent);
+
+        let mut arms = Vec::new();
+        let mut otherwise = None;
+        loop {
+            while self.eat(TokenKind::Newline) {}
+            let start = self.peek().map(|token| token.span).unwrap_or_default();
+            match self.peek_kind() {
+                Some(TokenKind::Keyword(Keyword::When)) => {
+                    self.bump();
+                    let mut patterns = vec![self.parse_expr()?];
+                    while self.eat(TokenKind::Comma) {
+                        patterns.push(self.parse_expr()?);
+                    }
+                    let body = match self.parse_body() {
+                        Ok(body) => body,
+                        Err(message) => {
+                            let line = self.current_line();
+                            self.fail(line, message);
+                            self.recover();
+                            Vec::new()
+                        }
+                    };
+                    arms.push(WhenArm {
+                        patterns,
+                        body,
+                        span: start.to(self.last_span),
+                    });
+                }
+                Some(TokenKind::Keyword(Keyword::Otherwise)) => {
+                    self.bump();
+                    otherwise = Some(self.parse_body()?);
+                }
+                Some(TokenKind::Dedent) if indented => {
+                    self.bump();
+                    break;
+                }
+                None if indented => {
+                    self.fail(line, "`case` without arms block end");
+                    break;
+                }
+                _ if indented => {
+                    let line = self.current_line();
+                    let found = self.describe_next();
+                    self.fail(line, format!("expected `when`, found {found}"));
+                    self.recover();
+                }
+                _ => break,
+            }
+        }
+        Ok(StmtKind::Case {
+            subject,
+            arms,
+            otherwise,
+        })
+    }
+
+    fn parse_for(&mut self) -> ParseResult<StmtKind> {
+        self.bump();
+        let variable = self.expect_ident("loop variable")?;
+        self.expect(TokenKind::Assign, "`=`")?;
+        let start = self.parse_expr()?;
+        let descending = if self.eat_keyword(Keyword::Downto) {
+            true
+        } else {
+            self.expect_keyword(Keyword::To, "`to`")?;
+            false
+        };
+        let end = self.parse_expr()?;
+        self.eat_keyword(Keyword::Do);
+        let body = self.parse_body()?;
+        Ok(StmtKind::For {
+            variable,
+            start,
+            end,
+            descending,
+            body,
+        })
+    }
+
+    fn parse_expr(&mut self) -> ParseResult<Expr> {
+        self.parse_binary(1)
+    }
+
+    fn parse_binary(&mut self, min_precedence: u8) -> ParseResult<Expr> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            let in_slice = self.in_slice;
+            let Some((op, precedence)) = self
+                .peek_kind()
+                .as_ref()
+                .and_then(|kind| binary_op(kind, in_slice))
+            else {
+                break;
+            };
+            if precedence < min_precedence {
+                break;
+            }
+            self.bump();
+            let rhs = self.parse_binary(precedence + 1)?;
+            lhs = Expr {
+                span: lhs.span.to(rhs.span),
+                kind: ExprKind::Binary {
+                    op,
+                    lhs: Box::new(lhs),
+                    rhs: Box::new(rhs),
+                },
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> ParseResult<Expr> {
+        let op = match self.peek_kind() {
+            Some(TokenKind::Minus)
+                if matches!(
+                    self.peek_nth_kind(1),
+                    Some(TokenKind::Comma | TokenKind::RParen)
+                ) =>
+            {
+                let span = self.bump().unwrap().span;
+                return Ok(Expr {
+                    kind: ExprKind::Discard,
+                    span,
+                });
+            }
+            Some(TokenKind::Minus) => UnaryOp::Neg,
+            Some(TokenKind::Bang) => UnaryOp::Not,
+            Some(TokenKind::Keyword(Keyword::Not)) => UnaryOp::BitNot,
+            _ => return self.parse_postfix(),
+        };
+        let start = self.bump().unwrap().span;
+        let operand = self.parse_unary()?;
+        Ok(Expr {
+            span: start.to(operand.span),
+            kind: ExprKind::Unary {
+                op,
+                operand: Box::new(operand),
+            },
+        })
+    }
+
+    /// Run `parse` with `:` and `>` treated as slice delimiters (or not)
+    fn with_in_slice<T>(&mut self, in_slice: bool, parse: impl FnOnce(&mut Self) -> T) -> T {
+        let outer = std::mem::replace(&mut self.in_slice, in_slice);
+        let result = parse(self);
+        self.in_slice = outer;
+        result
+    }
+
+    /// Comma separated expressions up to `close`, which is consumed
+    fn parse_list(&mut self, close: TokenKind, what: &str) -> ParseResult<Vec<Expr>> {
+        self.with_in_slice(false, |parser| {
+            let mut items = Vec::new();
+            if !parser.eat(close.clone()) {
+                loop {
+                    items.push(parser.parse_expr()?);
+                    if !parser.eat(TokenKind::Comma) {
+                        break;
+                    }
+                }
+                parser.expect(close, what)?;
+            }
+            Ok(items)
+        })
+    }
+
+    /// Ranges of `x<...>` after the `<`
+    fn parse_slice_ranges(&mut self) -> ParseResult<Vec<SliceRange>> {
+        self.with_in_slice(true, |parser| {
+            let mut ranges = Vec::new();
+            loop {
+                let first = parser.parse_expr()?;
+                let range = if parser.eat(TokenKind::Colon) {
+                    let low = parser.parse_expr()?;
+                    SliceRange::Range { high: first, low }
+                } else if parser.eat(TokenKind::PlusColon) {
+                    let width = parser.parse_expr()?;
+                    SliceRange::Width { low: first, width }
+                } else {
+                    SliceRange::Bit(first)
+                };
+                ranges.push(range);
+                if !parser.eat(TokenKind::Comma) {
+                    break;
+                }
+            }
+            parser.expect(TokenKind::Gt, "`>`")?;
+            Ok(ranges)
+        })
+    }
+
+    fn parse_postfix(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            let start = expr.span;
+            let kind = match self.peek().map(|token| (token.kind.clone(), token.spaced)) {
+                Some((TokenKind::LBracket, _)) => {
+                    self.bump();
+                    let args = self.parse_list(TokenKind::RBracket, "`]`")?;
+                    ExprKind::Index {
+                        base: Box::new(expr),
+                        args,
+                    }
+                }
+                Some((TokenKind::Lt, false)) => {
+                    self.bump();
+                    let ranges = self.parse_slice_ranges()?;
+                    ExprKind::Slice {
+                        base: Box::new(expr),
+                        ranges,
+                    }
+                }
+                Some((TokenKind::Dot, _)) => {
+                    self.bump();
+                    if self.eat(TokenKind::Lt) {
+                        let mut names = vec![self.expect_name("field name")?];
+                        while self.eat(TokenKind::Comma) {
+                            names.push(self.expect_name("field name")?);
+                        }
+                        self.expect(TokenKind::Gt, "`>`")?;
+                        ExprKind::Fields {
+                            base: Box::new(expr),
+                            names,
+                        }
+                    } else {
+                        let name = self.expect_name("field name")?;
+                        ExprKind::Field {
+                            base: Box::new(expr),
+                            name,
+                        }
+                    }
+                }
+                Some((TokenKind::LParen, _)) => {
+                    let Some(function) = expr.dotted_name() else {
+                        break;
+                    };
+                    self.bump();
+                    let args = self.parse_list(TokenKind::RParen, "`)`")?;
+                    ExprKind::Call { function, args }
+                }
+                _ => break,
+            };
+            expr = Expr {
+                kind,
+                span: start.to(self.last_span),
+            };
+        }
+        Ok(expr)
+    }
+
+    fn parse_primary(&mut self) -> ParseResult<Expr> {
+        let Some(token) = self.peek().cloned() else {
+            return Err("unexpected end of input".to_owned());
+        };
+        let kind = match token.kind {
+            TokenKind::Number(text) => {
+                self.bump();
+                let value = parse_number(&text);
+                ExprKind::Number { text, value }
+            }
+            TokenKind::Bits(bits) => {
+                self.bump();
+                ExprKind::Bits(bits)
+            }
+            TokenKind::Str(text) => {
+                self.bump();
+                ExprKind::Str(text)
+            }
+            TokenKind::Ident(name) if name == "bits" && self.has_value_keyword_after_parens() => {
+                let ty = self.parse_type()?;
+                self.parse_type_value(ty)?
+            }
+            TokenKind::Ident(name) => {
+                self.bump();
+                if matches!(
+                    self.peek_kind(),
+                    Some(TokenKind::Keyword(
+                        Keyword::Unknown | Keyword::ImplementationDefined
+                    ))
+                ) {
+                    self.parse_type_value(Type::Named(name))?
+                } else {
+                    ExprKind::Ident(name)
+                }
+            }
+            TokenKind::Keyword(Keyword::If) => {
+                self.bump();
+                self.parse_conditional()?
+            }
+            // `<Y, X> = ...` assigns the concatenation
+            TokenKind::Lt => {
+                self.bump();
+                let items = self.with_in_slice(true, |parser| {
+                    let mut items = vec![parser.parse_expr()?];
+                    while parser.eat(TokenKind::Comma) {
+                        items.push(parser.parse_expr()?);
+                    }
+                    parser.expect(TokenKind::Gt, "`>`")?;
+                    Ok::<_, String>(items)
+                })?;
+                items
+                    .into_iter()
+                    .reduce(|lhs, rhs| Expr {
+                        span: lhs.span.to(rhs.span),
+                        kind: ExprKind::Binary {
+                            op: BinaryOp::Concat,
+                            lhs: Box::new(lhs),
+                            rhs: Box::new(rhs),
+                        },
+                    })
+                    .unwrap()
+                    .kind
+            }
+            TokenKind::LParen => {
+                self.bump();
+                let mut items = self.parse_list(TokenKind::RParen, "`)`")?;
+                if items.len() == 1 {
+                    items.pop().unwrap().kind
+                } else {
+                    ExprKind::Tuple(items)
+                }
+            }
+            TokenKind::LBrace => {
+                self.bump();
+                ExprKind::Set(self.parse_list(TokenKind::RBrace, "`}`")?)
+            }
+            _ => return Err(format!("unexpected {}", self.describe_next())),
+        };
+        Ok(Expr {
+            kind,
+            span: token.span.to(self.last_span),
+        })
+    }
+
+    /// `cond then a else b` after `if` / `elsif`
+    fn parse_conditional(&mut self) -> ParseResult<ExprKind> {
+        let condition = self.parse_expr()?;
+        self.expect_keyword(Keyword::Then, "`then`")?;
+        let then_value = self.parse_expr()?;
+        let else_value = if self.eat_keyword(Keyword::Elsif) {
+            let start = self.last_span;
+            let kind = self.parse_conditional()?;
+            Expr {
+                kind,
+                span: start.to(self.last_span),
+            }
+        } else {
+            self.expect_keyword(Keyword::Else, "`else`")?;
+            self.parse_expr()?
+        };
+        Ok(ExprKind::Conditional {
+            condition: Box::new(condition),
+            then_value: Box::new(then_value),
+            else_value: Box::new(else_value),
+        })
+    }
+
+    /// `UNKNOWN` or `IMPLEMENTATION_DEFINED "..."` after a type
+    fn parse_type_value(&mut self, ty: Type) -> ParseResult<ExprKind> {
+        if self.eat_keyword(Keyword::ImplementationDefined) {
+            let description = self.parse_description();
+            Ok(ExprKind::ImplementationDefined { ty, description })
+        } else {
+            self.expect_keyword(Keyword::Unknown, "`UNKNOWN`")?;
+            Ok(ExprKind::Unknown(ty))
+        }
+    }
+
+    /// Optional string after `IMPLEMENTATION_DEFINED`
+    fn parse_description(&mut self) -> Option<String> {
+        match self.peek_kind() {
+            Some(TokenKind::Str(text)) => {
+                self.bump();
+                Some(text)
+            }
+            _ => None,
+        }
+    }
+
+    /// At `bits`: whether it is followed by `(...)` and `UNKNOWN` / `IMPLEMENTATION_DEFINED`
+    fn has_value_keyword_after_parens(&self) -> bool {
+        if self.peek_nth_kind(1) != Some(&TokenKind::LParen) {
+            return false;
+        }
+        let mut depth = 0;
+        let mut tokens = self.tokens[self.pos..]
+            .iter()
+            .filter(|token| !matches!(token.kind, TokenKind::Comment(_)));
+        for token in tokens.by_ref() {
+            match token.kind {
+                TokenKind::LParen => depth += 1,
+                TokenKind::RParen => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        tokens.next().is_some_and(|token| {
+            matches!(
+                token.kind,
+                TokenKind::Keyword(Keyword::Unknown | Keyword::ImplementationDefined)
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ident(expr: &Expr) -> &str {
+        match &expr.kind {
+            ExprKind::Ident(name) => name,
+            kind => panic!("not an identifier: {kind:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_decode() {
+        let parsed = parse(concat!(
+            "integer d = UInt(Rd);\n",
+            "integer datasize = if sf == '1' then 64 else 32;\n",
+            "bits(datasize) imm;\n",
+            "constant integer esize = 8;\n",
+            "\n",
+            "case sh of\n",
+            "    when '0' imm = ZeroExtend(imm12, datasize);\n",
+            "    when '1' imm = ZeroExtend(imm12 : Zeros(12), datasize);\n",
+            "    otherwise UNDEFINED;\n",
+            "if size:Q == '110' && selem != 1 then UNDEFINED;\n",
+        ));
+        assert_eq!(parsed.failures, vec![]);
+        assert_eq!(parsed.statements.len(), 6);
+
+        let StmtKind::Decl {
+            ty, names, value, ..
+        } = &parsed.statements[1].kind
+        else {
+            panic!("not a declaration: {:?}", parsed.statements[1]);
+        };
+        assert_eq!(ty, &Type::Named("integer".to_owned()));
+        assert_eq!(names, &["datasize"]);
+        assert!(matches!(
+            value.as_ref().unwrap().kind,
+            ExprKind::Conditional { .. }
+        ));
+        let StmtKind::Decl { ty, constant, .. } = &parsed.statements[2].kind else {
+            panic!("not a declaration: {:?}", parsed.statements[2]);
+        };
+        assert!(matches!(ty, Type::Bits(width) if ident(width) == "datasize"));
+        assert!(!constant);
+        assert!(matches!(
+            parsed.statements[3].kind,
+            StmtKind::Decl { constant: true, .. }
+        ));
+
+        let StmtKind::Case {
+            subject,
+            arms,
+            otherwise,
+        } = &parsed.statements[4].kind
+        else {
+            panic!("not a case: {:?}", parsed.statements[4]);
+        };
+        assert_eq!(ident(subject), "sh");
+        assert_eq!(arms.len(), 2);
+        assert_eq!(arms[1].patterns[0].kind, ExprKind::Bits("1".to_owned()));
+        let StmtKind::Assign { value, .. } = &arms[1].body[0].kind else {
+            panic!("not an assignment: {:?}", arms[1].body[0]);
+        };
+        let ExprKind::Call { args, .. } = &value.kind else {
+            panic!("not a call: {value:?}");
+        };
+        assert!(matches!(
+            args[0].kind,
+            ExprKind::Binary {
+                op: BinaryOp::Concat,
+                ..
+            }
+        ));
+        assert_eq!(otherwise.as_ref().unwrap()[0].kind, StmtKind::Undefined);
+
+        let StmtKind::If {
+            condition,
+            then_branch,
+            ..
+        } = &parsed.statements[5].kind
+        else {
+            panic!("not an if: {:?}", parsed.statements[5]);
+        };
+        assert!(matches!(
+            condition.kind,
+            ExprKind::Binary {
+                op: BinaryOp::LogicalAnd,
+                ..
+            }
+        ));
+        assert_eq!(then_branch[0].kind, StmtKind::Undefined);
+    }
+
+    #[test]
+    fn test_parse_operation_blocks() {
+        let parsed = parse(concat!(
+            "(result, nzcv) = AddWithCarry(operand1, NOT(operand2), '1');\n",
+            "if n == 31 then\n",
+            "    CheckSPAlignment();\n",
+            "    address = SP[];\n",
+            "elsif n == 30 then\n",
+            "    address = X[n]<63:0>;\n",
+            "else // register\n",
+            "    address = X[n];\n",
+            "PSTATE.<N,Z,C,V> = nzcv;\n",
+            "for e = 0 to elements-1\n",
+            "    Elem[result, e, esize] = Elem[operand, e, esize]<esize-1:0>;\n",
+        ));
+        assert_eq!(parsed.failures, vec![]);
+
+        let StmtKind::Assign { target, value } = &parsed.statements[0].kind else {
+            panic!("not an assignment: {:?}", parsed.statements[0]);
+        };
+        assert!(matches!(&target.kind, ExprKind::Tuple(items) if items.len() == 2));
+        assert!(
+            matches!(&value.kind, ExprKind::Call { function, .. } if function == "AddWithCarry")
+        );
+
+        let StmtKind::If {
+            then_branch,
+            elsif_branches,
+            else_branch,
+            ..
+        } = &parsed.statements[1].kind
+        else {
+            panic!("not an if: {:?}", parsed.statements[1]);
+        };
+        assert_eq!(then_branch.len(), 2);
+        assert_eq!(elsif_branches.len(), 1);
+        let StmtKind::Assign { value, .. } = &elsif_branches[0].1[0].kind else {
+            panic!("not an assignment: {:?}", elsif_branches[0].1[0]);
+        };
+        let ExprKind::Slice { base, ranges } = &value.kind else {
+            panic!("not a slice: {value:?}");
+        };
+        assert!(matches!(base.kind, ExprKind::Index { .. }));
+        assert!(matches!(ranges[..], [SliceRange::Range { .. }]));
+        assert_eq!(
+            else_branch[0].kind,
+            StmtKind::Comment("register".to_owned())
+        );
+        assert_eq!(else_branch.len(), 2);
+
+        let StmtKind::Assign { target, .. } = &parsed.statements[2].kind else {
+            panic!("not an assignment: {:?}", parsed.statements[2]);
+        };
+        assert!(matches!(&target.kind, ExprKind::Fields { names, .. } if names.len() == 4));
+        assert!(matches!(
+            &parsed.statements[3].kind,
+            StmtKind::For { body, descending: false, .. } if body.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_parse_recovers_per_line() {
+        let parsed = parse("a = 1;\nb = = 2;\nif c then\n    d = 3;\n    e = ;\nf = 4;\n");
+        assert_eq!(
+            parsed.failures.iter().map(|f| f.line).collect::<Vec<_>>(),
+            vec![2, 5]
+        );
+        assert_eq!(parsed.failures[0].text, "b = = 2;");
+        assert_eq!(parsed.statements.len(), 3);
+        assert!(matches!(
+            &parsed.statements[1].kind,
+            StmtKind::If { then_branch, .. } if then_branch.len() == 1
+        ));
+    }
+}
@@ -0,0 +1,173 @@
+use super::ast::*;
+use std::collections::BTreeSet;
+
+/// Call `visit` on every expression in `statements`, outer expressions before their operands
+pub fn walk_exprs(statements: &[Stmt], visit: &mut impl FnMut(&Expr)) {
+    for stmt in statements {
+        walk_stmt(stmt, visit);
+    }
+}
+
+fn walk_stmt(stmt: &Stmt, visit: &mut impl FnMut(&Expr)) {
+    match &stmt.kind {
+        StmtKind::Decl { ty, value, .. } => {
+            walk_type(ty, visit);
+            if let Some(value) = value {
+                walk_expr(value, visit);
+            }
+        }
+        StmtKind::Assign { target, value } => {
+            walk_expr(target, visit);
+            walk_expr(value, visit);
+        }
+        StmtKind::Expr(expr) | StmtKind::Assert(expr) | StmtKind::Return(Some(expr)) => {
+            walk_expr(expr, visit)
+        }
+        StmtKind::If {
+            condition,
+            then_branch,
+            elsif_branches,
+            else_branch,
+        } => {
+            walk_expr(condition, visit);
+            walk_exprs(then_branch, visit);
+            for (condition, body) in elsif_branches {
+                walk_expr(condition, visit);
+                walk_exprs(body, visit);
+            }
+            walk_exprs(else_branch, visit);
+        }
+        StmtKind::Case {
+            subject,
+            arms,
+            otherwise,
+        } => {
+            walk_expr(subject, visit);
+            for arm in arms {
+                for pattern in &arm.patterns {
+                    walk_expr(pattern, visit);
+                }
+                walk_exprs(&arm.body, visit);
+            }
+            if let Some(otherwise) = otherwise {
+                walk_exprs(otherwise, visit);
+            }
+        }
+        StmtKind::For {
+            start, end, body, ..
+        } => {
+            walk_expr(start, visit);
+            walk_expr(end, visit);
+            walk_exprs(body, visit);
+        }
+        StmtKind::While { condition, body } | StmtKind::Repeat { body, condition } => {
+            walk_expr(condition, visit);
+            walk_exprs(body, visit);
+        }
+        StmtKind::Return(None)
+        | StmtKind::Undefined
+        | StmtKind::Unpredictable
+        | StmtKind::See(_)
+        | StmtKind::ImplementationDefined(_)
+        | StmtKind::Comment(_) => {}
+    }
+}
+
+fn walk_type(ty: &Type, visit: &mut impl FnMut(&Expr)) {
+    match ty {
+        Type::Bits(width) => walk_expr(width, visit),
+        Type::Named(_) => {}
+        Type::Array { low, high, element } => {
+            walk_expr(low, visit);
+            walk_expr(high, visit);
+            walk_type(element, visit);
+        }
+    }
+}
+
+fn walk_expr(expr: &Expr, visit: &mut impl FnMut(&Expr)) {
+    visit(expr);
+    match &expr.kind {
+        ExprKind::Number { .. }
+        | ExprKind::Bits(_)
+        | ExprKind::Str(_)
+        | ExprKind::Ident(_)
+        | ExprKind::Discard => {}
+        ExprKind::Field { base, .. } | ExprKind::Fields { base, .. } => walk_expr(base, visit),
+        ExprKind::Index { base, args } => {
+            walk_expr(base, visit);
+            args.iter().for_each(|arg| walk_expr(arg, visit));
+        }
+        ExprKind::Slice { base, ranges } => {
+            walk_expr(base, visit);
+            for range in ranges {
+                match range {
+                    SliceRange::Bit(bit) => walk_expr(bit, visit),
+                    SliceRange::Range { high, low } => {
+                        walk_expr(high, visit);
+                        walk_expr(low, visit);
+                    }
+                    SliceRange::Width { low, width } => {
+                        walk_expr(low, visit);
+                        walk_expr(width, visit);
+                    }
+                }
+            }
+        }
+        ExprKind::Call { args, .. } | ExprKind::Tuple(args) | ExprKind::Set(args) => {
+            args.iter().for_each(|arg| walk_expr(arg, visit));
+        }
+        ExprKind::Unary { operand, .. } => walk_expr(operand, visit),
+        ExprKind::Binary { lhs, rhs, .. } => {
+            walk_expr(lhs, visit);
+            walk_expr(rhs, visit);
+        }
+        ExprKind::Conditional {
+            condition,
+            then_value,
+            else_value,
+        } => {
+            walk_expr(condition, visit);
+            walk_expr(then_value, visit);
+            walk_expr(else_value, visit);
+        }
+        ExprKind::Unknown(ty) | ExprKind::ImplementationDefined { ty, .. } => walk_type(ty, visit),
+    }
+}
+
+/// Names of called functions and indexed accessors: `UInt`, `X`, `AArch64.MemSingle`
+pub fn referenced_functions(statements: &[Stmt]) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    walk_exprs(statements, &mut |expr| match &expr.kind {
+        ExprKind::Call { function, .. } => {
+            names.insert(function.clone());
+        }
+        ExprKind::Index { base, .. } => {
+            names.extend(base.dotted_name());
+        }
+        _ => {}
+    });
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse;
+    use super::*;
+
+    #[test]
+    fn test_referenced_functions() {
+        let parsed = parse(concat!(
+            "bits(datasize) operand1 = if n == 31 then SP[] else X[n];\n",
+            "if HaveMTEExt() then\n",
+            "    AArch64.MemSingle[address, 8, acctype] = Elem[rval, e, UInt(size)];\n",
+        ));
+        assert_eq!(parsed.failures, vec![]);
+        assert_eq!(
+            referenced_functions(&parsed.statements)
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec!["AArch64.MemSingle", "Elem", "HaveMTEExt", "SP", "UInt", "X"]
+        );
+    }
+}
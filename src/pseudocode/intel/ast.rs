@@ -0,0 +1,138 @@
+pub use crate::pseudocode::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    /// `-x`
+    Neg,
+    /// `NOT x`, `~x`
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Or,
+    Xor,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Shl,
+    Shr,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind {
+    /// `64`, `0FFFFH`, `11B` - value is None when the literal doesn't fit or is malformed
+    Number {
+        text: String,
+        value: Option<u128>,
+    },
+    /// `DEST`, `TEMP1`, `KL`
+    Ident(String),
+    /// `CR0.PE`, `EVEX.b`
+    Field {
+        base: Box<Expr>,
+        name: String,
+    },
+    /// `SRC[i]`
+    Index {
+        base: Box<Expr>,
+        index: Box<Expr>,
+    },
+    /// `DEST[127:64]`
+    Slice {
+        base: Box<Expr>,
+        high: Box<Expr>,
+        low: Box<Expr>,
+    },
+    /// `ZeroExtend(SRC[31:0])`
+    Call {
+        function: String,
+        args: Vec<Expr>,
+    },
+    Unary {
+        op: UnaryOp,
+        operand: Box<Expr>,
+    },
+    Binary {
+        op: BinaryOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+    /// `(KL, VL)`
+    Tuple(Vec<Expr>),
+    /// `#GP(0)`, `#UD`
+    Exception {
+        name: String,
+        arg: Option<Box<Expr>>,
+    },
+    /// Prose between asterisks: `*DEST[63:0] remains unchanged*`
+    Prose(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stmt {
+    pub kind: StmtKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StmtKind {
+    /// `DEST[127:64] := SRC[63:0];` / `DEST ← SRC;`
+    Assign {
+        target: Expr,
+        value: Expr,
+    },
+    /// Call, exception or prose used as a statement: `#GP(0);`, `*Set the flags*`
+    Expr(Expr),
+    /// `IF ... THEN ... ELSE ... FI;` - `ELSE IF` is an `If` inside `else_branch`
+    If {
+        condition: Expr,
+        then_branch: Vec<Stmt>,
+        else_branch: Vec<Stmt>,
+    },
+    /// `FOR i := 0 TO 7 ... ROF;` (also `DOWNTO`, `DO`, `ENDFOR`, `OD`)
+    For {
+        variable: String,
+        start: Expr,
+        end: Expr,
+        descending: bool,
+        body: Vec<Stmt>,
+    },
+    /// `WHILE cond DO ... OD;`
+    While {
+        condition: Expr,
+        body: Vec<Stmt>,
+    },
+    /// `CASE (imm8[1:0]) OF 0: ... ESAC;`
+    Case {
+        subject: Expr,
+        arms: Vec<CaseArm>,
+    },
+    Break,
+    Return(Option<Expr>),
+    /// `(* ... *)` or `; ...` comment
+    Comment(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseArm {
+    /// Empty for `DEFAULT:`
+    pub labels: Vec<Expr>,
+    pub body: Vec<Stmt>,
+    pub span: Span,
+}
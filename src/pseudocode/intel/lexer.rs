@@ -0,0 +1,320 @@
+use super::ast::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Keyword {
+    If,
+    Then,
+    Else,
+    Fi,
+    For,
+    To,
+    Downto,
+    Do,
+    Rof,
+    Endfor,
+    Od,
+    While,
+    Endwhile,
+    Case,
+    Of,
+    Esac,
+    Default,
+    Break,
+    Return,
+    And,
+    Or,
+    Xor,
+    Not,
+    Mod,
+}
+
+impl Keyword {
+    fn from_ident(ident: &str) -> Option<Self> {
+        let keyword = match ident {
+            "IF" => Keyword::If,
+            "THEN" => Keyword::Then,
+            "ELSE" => Keyword::Else,
+            "FI" => Keyword::Fi,
+            "FOR" => Keyword::For,
+            "TO" => Keyword::To,
+            "DOWNTO" => Keyword::Downto,
+            "DO" => Keyword::Do,
+            "ROF" => Keyword::Rof,
+            "ENDFOR" => Keyword::Endfor,
+            "OD" => Keyword::Od,
+            "WHILE" => Keyword::While,
+            "ENDWHILE" => Keyword::Endwhile,
+            "CASE" => Keyword::Case,
+            "OF" => Keyword::Of,
+            "ESAC" => Keyword::Esac,
+            "DEFAULT" => Keyword::Default,
+            "BREAK" => Keyword::Break,
+            "RETURN" => Keyword::Return,
+            "AND" | "and" => Keyword::And,
+            "OR" | "or" => Keyword::Or,
+            "XOR" | "xor" => Keyword::Xor,
+            "NOT" | "not" => Keyword::Not,
+            "MOD" => Keyword::Mod,
+            _ => return None,
+        };
+        Some(keyword)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum TokenKind {
+    Ident(String),
+    Keyword(Keyword),
+    Number(String),
+    /// `:=` or `←`
+    Assign,
+    Colon,
+    Semicolon,
+    Comma,
+    Dot,
+    Hash,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    /// `=` or `==`
+    Eq,
+    /// `≠`, `!=` or `<>`
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Shl,
+    Shr,
+    Ampersand,
+    Pipe,
+    Caret,
+    Tilde,
+    /// `*text*`
+    Prose(String),
+    /// `(* text *)` or `; text`
+    Comment(String),
+    /// Anything else - usually PDF extraction damage
+    Unknown(char),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Token {
+    pub(super) kind: TokenKind,
+    pub(super) span: Span,
+}
+
+impl Token {
+    /// Tokens after which `*` means multiplication instead of starting prose
+    fn ends_operand(&self) -> bool {
+        matches!(
+            self.kind,
+            TokenKind::Ident(_)
+                | TokenKind::Number(_)
+                | TokenKind::RParen
+                | TokenKind::RBracket
+                | TokenKind::Prose(_)
+        )
+    }
+}
+
+/// Decide whether the `;` at `rest` (text after it up to the end of line) is a
+/// statement terminator or starts a comment ("ELSE ; zeroing-masking").
+fn semicolon_starts_comment(rest: &str) -> bool {
+    let rest = rest.trim();
+    let first_word: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect();
+    !(rest.is_empty()
+        || Keyword::from_ident(&first_word).is_some()
+        || rest.starts_with("(*")
+        || rest.starts_with(';')
+        || rest.contains(":=")
+        || rest.contains('←')
+        || rest.ends_with(';'))
+}
+
+pub(super) fn tokenize(source: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let offset = |i: usize| chars.get(i).map_or(source.len(), |c| c.0);
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        if c == '\n' {
+            line += 1;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let token_line = line;
+        let next = chars.get(i + 1).map(|c| c.1);
+        let last_on_line = tokens
+            .iter()
+            .rev()
+            .find(|t| !matches!(t.kind, TokenKind::Comment(_)))
+            .filter(|t| t.span.line == line);
+
+        let (kind, len) = if c.is_ascii_alphabetic() || c == '_' {
+            let len = chars[i..]
+                .iter()
+                .take_while(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+                .count();
+            let ident: String = chars[i..i + len].iter().map(|c| c.1).collect();
+            let kind = match Keyword::from_ident(&ident) {
+                Some(keyword) => TokenKind::Keyword(keyword),
+                None => TokenKind::Ident(ident),
+            };
+            (kind, len)
+        } else if c.is_ascii_digit() {
+            let len = chars[i..]
+                .iter()
+                .take_while(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+                .count();
+            let number: String = chars[i..i + len].iter().map(|c| c.1).collect();
+            (TokenKind::Number(number), len)
+        } else if c == '(' && next == Some('*') {
+            let body_start = i + 2;
+            let close = (body_start..chars.len().saturating_sub(1))
+                .find(|&j| chars[j].1 == '*' && chars[j + 1].1 == ')');
+            let body_end = close.unwrap_or(chars.len());
+            let text: String = chars[body_start..body_end].iter().map(|c| c.1).collect();
+            line += text.matches('\n').count();
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            let len = close.map_or(chars.len(), |close| close + 2) - i;
+            (TokenKind::Comment(text), len)
+        } else if c == ';' {
+            let len_to_eol = chars[i + 1..]
+                .iter()
+                .take_while(|(_, c)| *c != '\n')
+                .count();
+            let rest: String = chars[i + 1..i + 1 + len_to_eol]
+                .iter()
+                .map(|c| c.1)
+                .collect();
+            if semicolon_starts_comment(&rest) {
+                (TokenKind::Comment(rest.trim().to_owned()), len_to_eol + 1)
+            } else {
+                (TokenKind::Semicolon, 1)
+            }
+        } else if (c == '*' || c == '∗') && !last_on_line.is_some_and(Token::ends_operand) {
+            // Prose runs to the closing asterisk on the same line
+            let close = chars[i + 1..]
+                .iter()
+                .take_while(|(_, c)| *c != '\n')
+                .position(|(_, c)| *c == '*' || *c == '∗');
+            match close {
+                Some(close) => {
+                    let text: String = chars[i + 1..i + 1 + close].iter().map(|c| c.1).collect();
+                    (TokenKind::Prose(text.trim().to_owned()), close + 2)
+                }
+                None => (TokenKind::Star, 1),
+            }
+        } else {
+            match (c, next) {
+                (':', Some('=')) => (TokenKind::Assign, 2),
+                ('<', Some('=')) => (TokenKind::Le, 2),
+                ('>', Some('=')) => (TokenKind::Ge, 2),
+                ('<', Some('>')) | ('!', Some('=')) => (TokenKind::Ne, 2),
+                ('<', Some('<')) => (TokenKind::Shl, 2),
+                ('>', Some('>')) => (TokenKind::Shr, 2),
+                ('=', Some('=')) => (TokenKind::Eq, 2),
+                ('←', _) => (TokenKind::Assign, 1),
+                (':', _) => (TokenKind::Colon, 1),
+                (',', _) => (TokenKind::Comma, 1),
+                ('.', _) => (TokenKind::Dot, 1),
+                ('#', _) => (TokenKind::Hash, 1),
+                ('(', _) => (TokenKind::LParen, 1),
+                (')', _) => (TokenKind::RParen, 1),
+                ('[', _) => (TokenKind::LBracket, 1),
+                (']', _) => (TokenKind::RBracket, 1),
+                ('+', _) => (TokenKind::Plus, 1),
+                ('-' | '−' | '–', _) => (TokenKind::Minus, 1),
+                ('*' | '∗', _) => (TokenKind::Star, 1),
+                ('/', _) => (TokenKind::Slash, 1),
+                ('=', _) => (TokenKind::Eq, 1),
+                ('≠', _) => (TokenKind::Ne, 1),
+                ('<', _) => (TokenKind::Lt, 1),
+                ('≤', _) => (TokenKind::Le, 1),
+                ('>', _) => (TokenKind::Gt, 1),
+                ('≥', _) => (TokenKind::Ge, 1),
+                ('&', _) => (TokenKind::Ampersand, 1),
+                ('|', _) => (TokenKind::Pipe, 1),
+                ('^', _) => (TokenKind::Caret, 1),
+                ('~' | '!', _) => (TokenKind::Tilde, 1),
+                _ => (TokenKind::Unknown(c), 1),
+            }
+        };
+
+        tokens.push(Token {
+            kind,
+            span: Span {
+                line: token_line,
+                start,
+                end: offset(i + len),
+            },
+        });
+        i += len;
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source).into_iter().map(|t| t.kind).collect()
+    }
+
+    #[test]
+    fn test_tokenize_assignment() {
+        assert_eq!(
+            kinds("DEST[127:64] ← SRC * 2;"),
+            vec![
+                TokenKind::Ident("DEST".to_owned()),
+                TokenKind::LBracket,
+                TokenKind::Number("127".to_owned()),
+                TokenKind::Colon,
+                TokenKind::Number("64".to_owned()),
+                TokenKind::RBracket,
+                TokenKind::Assign,
+                TokenKind::Ident("SRC".to_owned()),
+                TokenKind::Star,
+                TokenKind::Number("2".to_owned()),
+                TokenKind::Semicolon,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_comments_and_prose() {
+        assert_eq!(
+            kinds("ELSE ; zeroing-masking\n*DEST remains unchanged* (* keep *)\nFI; FI;"),
+            vec![
+                TokenKind::Keyword(Keyword::Else),
+                TokenKind::Comment("zeroing-masking".to_owned()),
+                TokenKind::Prose("DEST remains unchanged".to_owned()),
+                TokenKind::Comment("keep".to_owned()),
+                TokenKind::Keyword(Keyword::Fi),
+                TokenKind::Semicolon,
+                TokenKind::Keyword(Keyword::Fi),
+                TokenKind::Semicolon,
+            ]
+        );
+        let tokens = tokenize("A := 1\nB := 2");
+        assert_eq!(tokens[3].span.line, 2);
+        assert_eq!(tokens[3].span.start, 7);
+    }
+}
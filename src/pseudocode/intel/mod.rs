@@ -0,0 +1,6 @@
+mod ast;
+mod lexer;
+mod parser;
+
+pub use ast::*;
+pub use parser::{parse, ParseFailure, ParsedOperation};
@@ -0,0 +1,895 @@
+use super::ast::*;
+use super::lexer::{tokenize, Keyword, Token, TokenKind};
+
+/// A source line the parser had to skip (usually PDF extraction damage)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFailure {
+    /// 1-based line in the operation text
+    pub line: usize,
+    /// The whole source line
+    pub text: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedOperation {
+    pub statements: Vec<Stmt>,
+    pub failures: Vec<ParseFailure>,
+}
+
+type ParseResult<T> = Result<T, String>;
+
+/// Keywords that close (or continue) a block and never start a statement
+const BLOCK_END_KEYWORDS: &[Keyword] = &[
+    Keyword::Then,
+    Keyword::Else,
+    Keyword::Fi,
+    Keyword::Rof,
+    Keyword::Endfor,
+    Keyword::Od,
+    Keyword::Endwhile,
+    Keyword::Esac,
+];
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Span of the last consumed (non-comment) token
+    last_span: Span,
+    failures: Vec<ParseFailure>,
+    /// Comments skipped while looking ahead, emitted at the next statement boundary
+    comments: Vec<Stmt>,
+    /// Terminators of the enclosing blocks, innermost last
+    terminators: Vec<&'static [Keyword]>,
+}
+
+/// Parse an SDM "Operation" section.
+/// Lines that fail to parse are skipped and reported, the rest still produce the AST.
+pub fn parse(source: &str) -> ParsedOperation {
+    let mut parser = Parser {
+        source,
+        tokens: tokenize(source),
+        pos: 0,
+        last_span: Span::default(),
+        failures: Vec::new(),
+        comments: Vec::new(),
+        terminators: Vec::new(),
+    };
+    let mut statements = parser.parse_block(&[], false);
+    parser.skip_comments();
+    statements.append(&mut parser.comments);
+    ParsedOperation {
+        statements,
+        failures: parser.failures,
+    }
+}
+
+fn binary_op(kind: &TokenKind) -> Option<(BinaryOp, u8)> {
+    let op = match kind {
+        TokenKind::Keyword(Keyword::Or) | TokenKind::Pipe => (BinaryOp::Or, 1),
+        TokenKind::Keyword(Keyword::Xor) | TokenKind::Caret => (BinaryOp::Xor, 1),
+        TokenKind::Keyword(Keyword::And) | TokenKind::Ampersand => (BinaryOp::And, 2),
+        TokenKind::Eq => (BinaryOp::Eq, 3),
+        TokenKind::Ne => (BinaryOp::Ne, 3),
+        TokenKind::Lt => (BinaryOp::Lt, 3),
+        TokenKind::Le => (BinaryOp::Le, 3),
+        TokenKind::Gt => (BinaryOp::Gt, 3),
+        TokenKind::Ge => (BinaryOp::Ge, 3),
+        TokenKind::Shl => (BinaryOp::Shl, 4),
+        TokenKind::Shr => (BinaryOp::Shr, 4),
+        TokenKind::Plus => (BinaryOp::Add, 5),
+        TokenKind::Minus => (BinaryOp::Sub, 5),
+        TokenKind::Star => (BinaryOp::Mul, 6),
+        TokenKind::Slash => (BinaryOp::Div, 6),
+        TokenKind::Keyword(Keyword::Mod) => (BinaryOp::Mod, 6),
+        _ => return None,
+    };
+    Some(op)
+}
+
+/// Value of a numeric literal: `64`, `0FFH`, `0x1F`, `11B`
+fn parse_number(text: &str) -> Option<u128> {
+    let text = text.replace('_', "");
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        u128::from_str_radix(hex, 16).ok()
+    } else if let Some(hex) = text.strip_suffix(['H', 'h']) {
+        u128::from_str_radix(hex, 16).ok()
+    } else if let Some(binary) = text.strip_suffix(['B', 'b']) {
+        u128::from_str_radix(binary, 2).ok()
+    } else {
+        text.parse().ok()
+    }
+}
+
+impl Parser<'_> {
+    fn skip_comments(&mut self) {
+        while let Some(token) = self.tokens.get(self.pos) {
+            let TokenKind::Comment(text) = &token.kind else {
+                break;
+            };
+            self.comments.push(Stmt {
+                kind: StmtKind::Comment(text.clone()),
+                span: token.span,
+            });
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<&Token> {
+        self.skip_comments();
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_kind(&mut self) -> Option<TokenKind> {
+        self.peek().map(|token| token.kind.clone())
+    }
+
+    /// Next token if it is on the same line as the last consumed one
+    fn peek_same_line(&mut self) -> Option<TokenKind> {
+        let line = self.last_span.line;
+        self.peek()
+            .filter(|token| token.span.line == line)
+            .map(|token| token.kind.clone())
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        self.skip_comments();
+        let token = self.tokens.get(self.pos).cloned()?;
+        self.pos += 1;
+        self.last_span = token.span;
+        Some(token)
+    }
+
+    fn eat(&mut self, kind: TokenKind) -> bool {
+        if self.peek_kind() == Some(kind) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: Keyword) -> bool {
+        self.eat(TokenKind::Keyword(keyword))
+    }
+
+    fn text(&self, span: Span) -> &str {
+        &self.source[span.start..span.end]
+    }
+
+    fn describe_next(&mut self) -> String {
+        match self.peek().map(|token| token.span) {
+            Some(span) => format!("`{}`", self.text(span)),
+            None => "end of input".to_owned(),
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind, what: &str) -> ParseResult<Token> {
+        if self.peek_kind() == Some(kind) {
+            Ok(self.bump().unwrap())
+        } else {
+            Err(format!("expected {what}, found {}", self.describe_next()))
+        }
+    }
+
+    /// Identifier, or a keyword used as a name (`#OF`, `RFLAGS.IF`)
+    fn expect_name(&mut self, what: &str) -> ParseResult<String> {
+        match self.peek_kind() {
+            Some(TokenKind::Ident(name)) => {
+                self.bump();
+                Ok(name)
+            }
+            Some(TokenKind::Keyword(_)) => {
+                let span = self.bump().unwrap().span;
+                Ok(self.text(span).to_owned())
+            }
+            _ => Err(format!("expected {what}, found {}", self.describe_next())),
+        }
+    }
+
+    fn current_line(&mut self) -> usize {
+        let last_line = self.last_span.line;
+        self.peek().map_or(last_line, |token| token.span.line)
+    }
+
+    fn fail(&mut self, line: usize, message: impl Into<String>) {
+        // One failure per line is enough to flag it
+        if self.failures.last().is_some_and(|f| f.line == line) {
+            return;
+        }
+        let text = self
+            .source
+            .lines()
+            .nth(line.saturating_sub(1))
+            .unwrap_or("")
+            .trim()
+            .to_owned();
+        self.failures.push(ParseFailure {
+            line,
+            text,
+            message: message.into(),
+        });
+    }
+
+    /// Skip the rest of a failed line (always consuming at least one token)
+    fn recover(&mut self, line: usize) {
+        let start = self.pos;
+        while let Some(token) = self.tokens.get(self.pos) {
+            if token.span.line > line && self.pos > start {
+                break;
+            }
+            if let TokenKind::Comment(text) = &token.kind {
+                self.comments.push(Stmt {
+                    kind: StmtKind::Comment(text.clone()),
+                    span: token.span,
+                });
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn checkpoint(&self) -> (usize, Span, usize) {
+        (self.pos, self.last_span, self.comments.len())
+    }
+
+    fn rewind(&mut self, (pos, last_span, comments): (usize, Span, usize)) {
+        self.pos = pos;
+        self.last_span = last_span;
+        self.comments.truncate(comments);
+    }
+
+    fn is_enclosing_terminator(&self, keyword: Keyword) -> bool {
+        self.terminators.iter().any(|t| t.contains(&keyword))
+    }
+
+    /// Parse statements until one of `terminators` (or a terminator of an enclosing
+    /// block, which leaves the missing terminator to be reported by its owner).
+    fn parse_block(&mut self, terminators: &'static [Keyword], case_arm: bool) -> Vec<Stmt> {
+        self.terminators.push(terminators);
+        let mut body = Vec::new();
+        loop {
+            self.skip_comments();
+            body.append(&mut self.comments);
+            let Some(token) = self.peek().cloned() else {
+                break;
+            };
+            if let TokenKind::Keyword(keyword) = token.kind {
+                if self.is_enclosing_terminator(keyword) {
+                    break;
+                }
+                if BLOCK_END_KEYWORDS.contains(&keyword) {
+                    self.fail(
+                        token.span.line,
+                        format!("unexpected `{}`", self.text(token.span)),
+                    );
+                    self.bump();
+                    self.eat(TokenKind::Semicolon);
+                    continue;
+                }
+            }
+            if case_arm && self.at_case_label() {
+                break;
+            }
+            match self.parse_statement() {
+                Ok(stmt) => body.push(stmt),
+                Err(message) => {
+                    let line = self.current_line();
+                    self.fail(line, message);
+                    self.recover(line);
+                }
+            }
+        }
+        self.terminators.pop();
+        body
+    }
+
+    /// A statement ends with `;`, at the end of the line or before a block keyword
+    fn end_statement(&mut self) -> ParseResult<()> {
+        if self.eat(TokenKind::Semicolon) {
+            return Ok(());
+        }
+        let last_line = self.last_span.line;
+        match self.peek() {
+            None => Ok(()),
+            Some(token) if token.span.line != last_line => Ok(()),
+            Some(Token {
+                kind: TokenKind::Keyword(keyword),
+                ..
+            }) if BLOCK_END_KEYWORDS.contains(keyword) => Ok(()),
+            Some(_) => Err(format!("expected `;`, found {}", self.describe_next())),
+        }
+    }
+
+    fn parse_statement(&mut self) -> ParseResult<Stmt> {
+        let start = self.peek().map(|token| token.span).unwrap_or_default();
+        let kind = match self.peek_kind() {
+            Some(TokenKind::Keyword(Keyword::If)) => self.parse_if()?,
+            Some(TokenKind::Keyword(Keyword::For)) => self.parse_for()?,
+            Some(TokenKind::Keyword(Keyword::While)) => self.parse_while()?,
+            Some(TokenKind::Keyword(Keyword::Case)) => self.parse_case()?,
+            Some(TokenKind::Keyword(Keyword::Break)) => {
+                self.bump();
+                self.end_statement()?;
+                StmtKind::Break
+            }
+            Some(TokenKind::Keyword(Keyword::Return)) => {
+                self.bump();
+                let value = if self.end_statement().is_ok() {
+                    None
+                } else {
+                    let value = self.parse_expr()?;
+                    self.end_statement()?;
+                    Some(value)
+                };
+                StmtKind::Return(value)
+            }
+            _ => {
+                let target = self.parse_expr()?;
+                if self.eat(TokenKind::Assign) {
+                    let value = self.parse_expr()?;
+                    self.end_statement()?;
+                    StmtKind::Assign { target, value }
+                } else {
+                    self.end_statement()?;
+                    StmtKind::Expr(target)
+                }
+            }
+        };
+        Ok(Stmt {
+            kind,
+            span: start.to(self.last_span),
+        })
+    }
+
+    fn parse_if(&mut self) -> ParseResult<StmtKind> {
+        let line = self.bump().unwrap().span.line;
+        let condition = match self.find_then(line) {
+            Some(then) => {
+                let checkpoint = self.checkpoint();
+                let condition = match self.parse_expr() {
+                    Ok(condition) if self.pos == then => condition,
+                    // Informal conditions: "IF 64-Bit Mode", "IF (in VMX operation)"
+                    _ => {
+                        self.rewind(checkpoint);
+                        self.parse_informal(then)
+                    }
+                };
+                self.bump();
+                condition
+            }
+            None => {
+                let condition = self.parse_expr()?;
+                let found = self.describe_next();
+                self.fail(
+                    self.last_span.line,
+                    format!("expected `THEN`, found {found}"),
+                );
+                condition
+            }
+        };
+        self.eat(TokenKind::Semicolon);
+        let then_branch = self.parse_block(&[Keyword::Else, Keyword::Fi], false);
+        let mut else_branch = Vec::new();
+        if self.eat_keyword(Keyword::Else) {
+            self.eat(TokenKind::Semicolon);
+            else_branch = self.parse_block(&[Keyword::Fi], false);
+        }
+        if self.eat_keyword(Keyword::Fi) {
+            self.eat(TokenKind::Semicolon);
+        } else {
+            self.fail(line, "`IF` without `FI`");
+        }
+        Ok(StmtKind::If {
+            condition,
+            then_branch,
+            else_branch,
+        })
+    }
+
+    /// Position of the `THEN` of an `IF` starting on `line`: on the same or the next line,
+    /// before any other block keyword
+    fn find_then(&mut self, line: usize) -> Option<usize> {
+        self.skip_comments();
+        for (i, token) in self.tokens.iter().enumerate().skip(self.pos) {
+            if token.span.line > line + 1 {
+                return None;
+            }
+            match token.kind {
+                TokenKind::Keyword(Keyword::Then) => return Some(i),
+                TokenKind::Keyword(keyword) if BLOCK_END_KEYWORDS.contains(&keyword) => {
+                    return None
+                }
+                TokenKind::Keyword(Keyword::If) | TokenKind::Semicolon => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Tokens up to `end` as prose
+    fn parse_informal(&mut self, end: usize) -> Expr {
+        let start = self.tokens[self.pos].span;
+        let mut last = start;
+        while self.pos < end {
+            last = self.bump().unwrap().span;
+        }
+        let span = start.to(last);
+        let text = self
+            .text(span)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        Expr {
+            kind: ExprKind::Prose(text),
+            span,
+        }
+    }
+
+    fn parse_for(&mut self) -> ParseResult<StmtKind> {
+        let line = self.bump().unwrap().span.line;
+        let variable = self.expect_name("loop variable")?;
+        if !self.eat(TokenKind::Assign) {
+            self.expect(TokenKind::Eq, "`:=`")?;
+        }
+        let start = self.parse_expr()?;
+        let descending = if self.eat_keyword(Keyword::Downto) {
+            true
+        } else {
+            self.expect(TokenKind::Keyword(Keyword::To), "`TO`")?;
+            false
+        };
+        let end = self.parse_expr()?;
+        self.eat_keyword(Keyword::Do);
+        self.eat(TokenKind::Semicolon);
+        let body = self.parse_block(&[Keyword::Rof, Keyword::Endfor, Keyword::Od], false);
+        if self.eat_keyword(Keyword::Rof)
+            || self.eat_keyword(Keyword::Endfor)
+            || self.eat_keyword(Keyword::Od)
+        {
+            self.eat(TokenKind::Semicolon);
+        } else {
+            self.fail(line, "`FOR` without `ROF`");
+        }
+        Ok(StmtKind::For {
+            variable,
+            start,
+            end,
+            descending,
+            body,
+        })
+    }
+
+    fn parse_while(&mut self) -> ParseResult<StmtKind> {
+        let line = self.bump().unwrap().span.line;
+        let condition = self.parse_expr()?;
+        self.eat_keyword(Keyword::Do);
+        self.eat(TokenKind::Semicolon);
+        let body = self.parse_block(&[Keyword::Od, Keyword::Endwhile], false);
+        if self.eat_keyword(Keyword::Od) || self.eat_keyword(Keyword::Endwhile) {
+            self.eat(TokenKind::Semicolon);
+        } else {
+            self.fail(line, "`WHILE` without `OD`");
+        }
+        Ok(StmtKind::While { condition, body })
+    }
+
+    fn parse_case(&mut self) -> ParseResult<StmtKind> {
+        let line = self.bump().unwrap().span.line;
+        let subject = self.parse_expr()?;
+        if !self.eat_keyword(Keyword::Of) {
+            let found = self.describe_next();
+            self.fail(self.last_span.line, format!("expected `OF`, found {found}"));
+        }
+        self.eat(TokenKind::Semicolon);
+
+        let mut arms = Vec::new();
+        loop {
+            match self.peek_kind() {
+                Some(TokenKind::Keyword(Keyword::Esac)) => {
+                    self.bump();
+                    self.eat(TokenKind::Semicolon);
+                    break;
+                }
+                Some(TokenKind::Keyword(keyword)) if self.is_enclosing_terminator(keyword) => {
+                    self.fail(line, "`CASE` without `ESAC`");
+                    break;
+                }
+                None => {
+                    self.fail(line, "`CASE` without `ESAC`");
+                    break;
+                }
+                Some(_) => {}
+            }
+            let start = self.peek().unwrap().span;
+            let Some(labels) = self.parse_case_label() else {
+                let line = self.current_line();
+                let found = self.describe_next();
+                self.fail(line, format!("expected `CASE` label, found {found}"));
+                self.recover(line);
+                continue;
+            };
+            let body = self.parse_block(&[Keyword::Esac], true);
+            arms.push(CaseArm {
+                labels,
+                body,
+                span: start.to(self.last_span),
+            });
+        }
+        Ok(StmtKind::Case { subject, arms })
+    }
+
+    /// `0:`, `1, 2:`, `DEFAULT:` - restores the position when there is no label
+    fn parse_case_label(&mut self) -> Option<Vec<Expr>> {
+        let checkpoint = self.checkpoint();
+        if self.eat_keyword(Keyword::Default) {
+            if self.eat(TokenKind::Colon) {
+                return Some(Vec::new());
+            }
+        } else {
+            let mut labels = Vec::new();
+            while let Ok(label) = self.parse_expr() {
+                labels.push(label);
+                if self.eat(TokenKind::Colon) {
+                    return Some(labels);
+                }
+                if !self.eat(TokenKind::Comma) {
+                    break;
+                }
+            }
+        }
+        self.rewind(checkpoint);
+        None
+    }
+
+    fn at_case_label(&mut self) -> bool {
+        let checkpoint = self.checkpoint();
+        let found = self.parse_case_label().is_some();
+        self.rewind(checkpoint);
+        found
+    }
+
+    fn parse_expr(&mut self) -> ParseResult<Expr> {
+        self.parse_binary(1)
+    }
+
+    fn parse_binary(&mut self, min_precedence: u8) -> ParseResult<Expr> {
+        let mut lhs = self.parse_unary()?;
+        while let Some((op, precedence)) = self.peek_kind().as_ref().and_then(binary_op) {
+            if precedence < min_precedence {
+                break;
+            }
+            self.bump();
+            let rhs = self.parse_binary(precedence + 1)?;
+            lhs = Expr {
+                span: lhs.span.to(rhs.span),
+                kind: ExprKind::Binary {
+                    op,
+                    lhs: Box::new(lhs),
+                    rhs: Box::new(rhs),
+                },
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> ParseResult<Expr> {
+        let op = match self.peek_kind() {
+            Some(TokenKind::Minus) => UnaryOp::Neg,
+            Some(TokenKind::Keyword(Keyword::Not) | TokenKind::Tilde) => UnaryOp::Not,
+            _ => return self.parse_postfix(),
+        };
+        let start = self.bump().unwrap().span;
+        let operand = self.parse_unary()?;
+        Ok(Expr {
+            span: start.to(operand.span),
+            kind: ExprKind::Unary {
+                op,
+                operand: Box::new(operand),
+            },
+        })
+    }
+
+    fn parse_postfix(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            let start = expr.span;
+            let kind = match self.peek_same_line() {
+                Some(TokenKind::LBracket) => {
+                    self.bump();
+                    let high = self.parse_expr()?;
+                    let kind = if self.eat(TokenKind::Colon) {
+                        let low = self.parse_expr()?;
+                        ExprKind::Slice {
+                            base: Box::new(expr),
+                            high: Box::new(high),
+                            low: Box::new(low),
+                        }
+                    } else {
+                        ExprKind::Index {
+                            base: Box::new(expr),
+                            index: Box::new(high),
+                        }
+                    };
+                    self.expect(TokenKind::RBracket, "`]`")?;
+                    kind
+                }
+                Some(TokenKind::Dot) => {
+                    self.bump();
+                    let name = match self.peek_kind() {
+                        Some(TokenKind::Number(number)) => {
+                            self.bump();
+                            number
+                        }
+                        _ => self.expect_name("field name")?,
+                    };
+                    ExprKind::Field {
+                        base: Box::new(expr),
+                        name,
+                    }
+                }
+                Some(TokenKind::LParen) if matches!(expr.kind, ExprKind::Ident(_)) => {
+                    let ExprKind::Ident(function) = expr.kind else {
+                        unreachable!()
+                    };
+                    self.bump();
+                    let mut args = Vec::new();
+                    if !self.eat(TokenKind::RParen) {
+                        loop {
+                            args.push(self.parse_expr()?);
+                            if !self.eat(TokenKind::Comma) {
+                                break;
+                            }
+                        }
+                        self.expect(TokenKind::RParen, "`)`")?;
+                    }
+                    ExprKind::Call { function, args }
+                }
+                _ => break,
+            };
+            expr = Expr {
+                kind,
+                span: start.to(self.last_span),
+            };
+        }
+        Ok(expr)
+    }
+
+    fn parse_primary(&mut self) -> ParseResult<Expr> {
+        let Some(token) = self.peek().cloned() else {
+            return Err("unexpected end of input".to_owned());
+        };
+        let kind = match token.kind {
+            TokenKind::Number(text) => {
+                self.bump();
+                let value = parse_number(&text);
+                ExprKind::Number { text, value }
+            }
+            TokenKind::Ident(name) => {
+                self.bump();
+                ExprKind::Ident(name)
+            }
+            TokenKind::Prose(text) => {
+                self.bump();
+                ExprKind::Prose(text)
+            }
+            TokenKind::Hash => {
+                self.bump();
+                let name = self.expect_name("exception name")?;
+                let arg = if self.peek_same_line() == Some(TokenKind::LParen) {
+                    self.bump();
+                    let arg = self.parse_expr()?;
+                    self.expect(TokenKind::RParen, "`)`")?;
+                    Some(Box::new(arg))
+                } else {
+                    None
+                };
+                ExprKind::Exception { name, arg }
+            }
+            TokenKind::LParen => {
+                self.bump();
+                let mut items = vec![self.parse_expr()?];
+                while self.eat(TokenKind::Comma) {
+                    items.push(self.parse_expr()?);
+                }
+                self.expect(TokenKind::RParen, "`)`")?;
+                if items.len() == 1 {
+                    items.pop().unwrap().kind
+                } else {
+                    ExprKind::Tuple(items)
+                }
+            }
+            _ => return Err(format!("unexpected {}", self.describe_next())),
+        };
+        Ok(Expr {
+            kind,
+            span: token.span.to(self.last_span),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ident(expr: &Expr) -> &str {
+        match &expr.kind {
+            ExprKind::Ident(name) => name,
+            kind => panic!("not an identifier: {kind:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_if_else_masking() {
+        let parsed = parse(
+            "FOR j := 0 TO KL-1\n\
+             \x20   i := j * 64\n\
+             \x20   IF k1[j] OR *no writemask*\n\
+             \x20       THEN DEST[i+63:i] := SRC1[i+63:i] + SRC2[i+63:i]\n\
+             \x20       ELSE\n\
+             \x20           IF *merging-masking* ; merging-masking\n\
+             \x20               THEN *DEST[i+63:i] remains unchanged*\n\
+             \x20               ELSE ; zeroing-masking\n\
+             \x20                   DEST[i+63:i] := 0\n\
+             \x20           FI\n\
+             \x20   FI;\n\
+             ENDFOR\n\
+             DEST[MAXVL-1:VL] ← 0",
+        );
+        assert_eq!(parsed.failures, vec![]);
+        assert_eq!(parsed.statements.len(), 2);
+        let StmtKind::For {
+            variable,
+            end,
+            body,
+            descending,
+            ..
+        } = &parsed.statements[0].kind
+        else {
+            panic!("not a FOR: {:?}", parsed.statements[0]);
+        };
+        assert_eq!(variable, "j");
+        assert!(!descending);
+        assert!(matches!(
+            end.kind,
+            ExprKind::Binary {
+                op: BinaryOp::Sub,
+                ..
+            }
+        ));
+        let StmtKind::If {
+            condition,
+            then_branch,
+            else_branch,
+        } = &body[1].kind
+        else {
+            panic!("not an IF: {:?}", body[1]);
+        };
+        assert!(matches!(
+            condition.kind,
+            ExprKind::Binary {
+                op: BinaryOp::Or,
+                ..
+            }
+        ));
+        let StmtKind::Assign { target, .. } = &then_branch[0].kind else {
+            panic!("not an assignment: {:?}", then_branch[0]);
+        };
+        let ExprKind::Slice { base, .. } = &target.kind else {
+            panic!("not a slice: {target:?}");
+        };
+        assert_eq!(ident(base), "DEST");
+        assert_eq!(target.span.line, 4);
+        // Nested IF keeps its comments
+        let StmtKind::If {
+            then_branch,
+            else_branch,
+            ..
+        } = &else_branch[0].kind
+        else {
+            panic!("not an IF: {:?}", else_branch[0]);
+        };
+        assert_eq!(
+            then_branch[0].kind,
+            StmtKind::Comment("merging-masking".to_owned())
+        );
+        assert_eq!(
+            else_branch[0].kind,
+            StmtKind::Comment("zeroing-masking".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_parse_case_and_calls() {
+        let parsed = parse(
+            "CASE (imm8[1:0]) OF\n\
+             \x20   0: DEST[31:0] := ZeroExtend(SRC[7:0]);\n\
+             \x20   1, 2: DEST.X ← SignExtend(SRC[15:0], 32);\n\
+             \x20   DEFAULT: #GP(0);\n\
+             ESAC;\n\
+             (* done *)",
+        );
+        assert_eq!(parsed.failures, vec![]);
+        let StmtKind::Case { arms, .. } = &parsed.statements[0].kind else {
+            panic!("not a CASE: {:?}", parsed.statements[0]);
+        };
+        assert_eq!(arms.len(), 3);
+        assert_eq!(arms[1].labels.len(), 2);
+        assert!(arms[2].labels.is_empty());
+        let StmtKind::Assign { value, .. } = &arms[0].body[0].kind else {
+            panic!("not an assignment: {:?}", arms[0].body[0]);
+        };
+        assert!(
+            matches!(&value.kind, ExprKind::Call { function, args } if function == "ZeroExtend" && args.len() == 1)
+        );
+        assert!(matches!(
+            &arms[2].body[0].kind,
+            StmtKind::Expr(Expr {
+                kind: ExprKind::Exception { name, arg: Some(_) },
+                ..
+            }) if name == "GP"
+        ));
+        assert_eq!(
+            parsed.statements[1].kind,
+            StmtKind::Comment("done".to_owned())
+        );
+        assert_eq!(parse_number("0FFH"), Some(255));
+        assert_eq!(parse_number("101B"), Some(5));
+    }
+
+    #[test]
+    fn test_parse_informal_condition() {
+        let parsed = parse(
+            "IF 64-Bit Mode\n\
+             \x20   THEN\n\
+             \x20       #UD;\n\
+             \x20   ELSE\n\
+             \x20       IF ((AL and 0FH) > 9) or (AF = 1)\n\
+             \x20           THEN AX := AX + 106H;\n\
+             \x20       FI;\n\
+             FI;",
+        );
+        assert_eq!(parsed.failures, vec![]);
+        let StmtKind::If {
+            condition,
+            else_branch,
+            ..
+        } = &parsed.statements[0].kind
+        else {
+            panic!("not an IF: {:?}", parsed.statements[0]);
+        };
+        assert_eq!(condition.kind, ExprKind::Prose("64-Bit Mode".to_owned()));
+        let StmtKind::If { condition, .. } = &else_branch[0].kind else {
+            panic!("not an IF: {:?}", else_branch[0]);
+        };
+        assert!(matches!(
+            condition.kind,
+            ExprKind::Binary {
+                op: BinaryOp::Or,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_failures_are_reported() {
+        let parsed = parse(
+            "IF DEST = 0\n\
+             \x20   THEN ZF := 1;\n\
+             \x20   ELSE ZF := ) 0;\n\
+             FI;\n\
+             CF := 0;\n\
+             ROF;",
+        );
+        assert_eq!(
+            parsed
+                .failures
+                .iter()
+                .map(|f| (f.line, f.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(3, "ELSE ZF := ) 0;"), (6, "ROF;")]
+        );
+        // The damaged line is skipped, everything else is still parsed
+        assert_eq!(parsed.statements.len(), 2);
+    }
+}
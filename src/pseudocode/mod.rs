@@ -0,0 +1,21 @@
+pub mod asl;
+pub mod intel;
+
+/// Source position of a node: byte offsets into the pseudocode text and the 1-based line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// Span covering both nodes (keeps the first line)
+    pub fn to(self, other: Span) -> Span {
+        Span {
+            line: self.line,
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}